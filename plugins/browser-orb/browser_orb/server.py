@@ -22,6 +22,16 @@ DEFAULT_SNAPSHOT_DEPTH = 6
 FORBIDDEN_SCHEMES = {"javascript", "data"}
 PUBLIC_WEB_SCHEMES = {"http", "https"}
 UNRESTRICTED_EXTRA_SCHEMES = {"file", "about"}
+BLANK_PAGE_URL = "about:blank"
+LOCAL_HOSTNAMES = {"localhost", "localhost.localdomain"}
+# Cloud instance-metadata endpoints stay blocked even when listed as local exceptions.
+METADATA_HOSTS = {
+    "169.254.169.254",
+    "169.254.170.2",
+    "fd00:ec2::254",
+    "metadata.google.internal",
+    "metadata.goog",
+}
 
 
 @dataclass
@@ -48,6 +58,7 @@ class PluginState:
             "agent_browser_command": "agent-browser",
             "allow_unrestricted_navigation": False,
             "allowed_domains": "",
+            "allowed_local_hosts": "",
             "allow_eval": False,
             "allow_persistent_auth": False,
             "persistent_auth_session_name": "browser-orb",
@@ -384,7 +395,7 @@ def browser_open(arguments: dict[str, Any]) -> dict[str, Any]:
     if wait_until:
         run_agent_browser(["wait", "--load", wait_until], settings)
 
-    url_info = refresh_current_url(settings)
+    url_info = enforce_current_url(settings)
     title_info = run_agent_browser(["get", "title"], settings)
     return json_result(
         {
@@ -451,7 +462,7 @@ def browser_click(arguments: dict[str, Any]) -> dict[str, Any]:
             "status": "ok",
             "tool": "browser_click",
             "target": target,
-            "current_url": refresh_current_url(settings),
+            "current_url": enforce_current_url(settings),
         }
     )
 
@@ -506,7 +517,7 @@ def browser_wait(arguments: dict[str, Any]) -> dict[str, Any]:
         {
             "status": "ok",
             "tool": "browser_wait",
-            "current_url": refresh_current_url(settings),
+            "current_url": enforce_current_url(settings),
         }
     )
 
@@ -708,6 +719,43 @@ def refresh_current_url(settings: dict[str, Any]) -> str | None:
     return STATE.last_known_url
 
 
+def enforce_current_url(settings: dict[str, Any]) -> str | None:
+    # Redirects and clicks can land on a host validate_navigation_target would
+    # have refused up front. A blocked page, or one whose URL cannot be read,
+    # is swapped for about:blank before raising so later snapshots cannot read it.
+    if parse_bool(settings.get("allow_unrestricted_navigation"), False):
+        return refresh_current_url(settings)
+
+    try:
+        current = extract_scalar(run_agent_browser(["get", "url"], settings))
+    except Exception as error:
+        leave_current_page(settings)
+        raise RuntimeError(
+            "Browser-Orb could not confirm the page URL after navigation and left the page."
+        ) from error
+
+    current_url = str(current) if current else ""
+    if current_url and current_url != BLANK_PAGE_URL:
+        try:
+            validate_navigation_target(current_url, settings)
+        except ValueError as error:
+            leave_current_page(settings)
+            raise ValueError(
+                f"Browser-Orb left {current_url} after navigation landed there: {error}"
+            ) from error
+
+    STATE.last_known_url = current_url or None
+    return STATE.last_known_url
+
+
+def leave_current_page(settings: dict[str, Any]) -> None:
+    try:
+        run_agent_browser(["open", BLANK_PAGE_URL], settings)
+    except Exception:
+        pass
+    STATE.last_known_url = None
+
+
 def validate_navigation_target(url: str, settings: dict[str, Any]) -> None:
     parsed = urlparse(url)
     scheme = (parsed.scheme or "").lower()
@@ -729,11 +777,20 @@ def validate_navigation_target(url: str, settings: dict[str, Any]) -> None:
     if scheme in PUBLIC_WEB_SCHEMES:
         if not host:
             raise ValueError("Browser-Orb requires a hostname for http(s) navigation.")
-        if not unrestricted and is_local_or_private_host(host):
-            raise ValueError(
-                "Browser-Orb blocked navigation to a local or private host. "
-                "Enable unrestricted navigation if you explicitly want that."
-            )
+        if not unrestricted:
+            if is_metadata_host(host):
+                raise ValueError(
+                    "Browser-Orb blocked navigation to a cloud metadata endpoint."
+                )
+            local_exceptions = parse_host_list(settings.get("allowed_local_hosts"))
+            if not domain_matches_allowlist(
+                host, local_exceptions
+            ) and is_local_or_private_host(host):
+                raise ValueError(
+                    "Browser-Orb blocked navigation to a local or private host. "
+                    "Add it to Allowed Local Hosts or enable unrestricted navigation "
+                    "if you explicitly want that."
+                )
 
         allowed_domains = parse_domain_allowlist(settings)
         if allowed_domains and not domain_matches_allowlist(host, allowed_domains):
@@ -743,7 +800,11 @@ def validate_navigation_target(url: str, settings: dict[str, Any]) -> None:
 
 
 def parse_domain_allowlist(settings: dict[str, Any]) -> list[str]:
-    raw = str(settings.get("allowed_domains") or "")
+    return parse_host_list(settings.get("allowed_domains"))
+
+
+def parse_host_list(value: Any) -> list[str]:
+    raw = str(value or "")
     parts = []
     for chunk in raw.replace(",", "\n").splitlines():
         value = chunk.strip().lower()
@@ -770,9 +831,19 @@ def domain_matches_allowlist(host: str, allowed_domains: list[str]) -> bool:
     return False
 
 
+def is_metadata_host(host: str) -> bool:
+    lowered = host.lower().rstrip(".")
+    try:
+        ip = ipaddress.ip_address(lowered)
+    except ValueError:
+        return lowered in METADATA_HOSTS
+    mapped = getattr(ip, "ipv4_mapped", None)
+    return str(mapped or ip) in METADATA_HOSTS
+
+
 def is_local_or_private_host(host: str) -> bool:
-    lowered = host.lower()
-    if lowered in {"localhost", "localhost.localdomain"}:
+    lowered = host.lower().rstrip(".")
+    if lowered in LOCAL_HOSTNAMES or lowered.endswith(".localhost"):
         return True
 
     try:
@@ -802,8 +873,13 @@ def is_local_or_private_host(host: str) -> bool:
 
 
 def is_private_ip(ip: ipaddress._BaseAddress) -> bool:
+    # ::ffff:127.0.0.1 reaches 127.0.0.1; judge the embedded IPv4 address.
+    mapped = getattr(ip, "ipv4_mapped", None)
+    if mapped is not None:
+        ip = mapped
     return bool(
-        ip.is_private
+        not ip.is_global
+        or ip.is_private
         or ip.is_loopback
         or ip.is_link_local
         or ip.is_multicast
@@ -0,0 +1,147 @@
+from __future__ import annotations
+
+import socket
+import unittest
+from typing import Any
+from unittest import mock
+
+from browser_orb import server
+from browser_orb.server import validate_navigation_target
+
+
+def fake_resolver(addresses: dict[str, str]):
+    def getaddrinfo(host: str, *_args: Any, **_kwargs: Any) -> list[Any]:
+        if host not in addresses:
+            raise socket.gaierror(f"unknown host {host}")
+        return [(socket.AF_INET, socket.SOCK_STREAM, 6, "", (addresses[host], 0))]
+
+    return getaddrinfo
+
+
+class FakeBrowser:
+    def __init__(self, landed_url: str) -> None:
+        self.landed_url = landed_url
+        self.commands: list[list[str]] = []
+
+    def run(
+        self, command: list[str], _settings: dict[str, Any], **_kwargs: Any
+    ) -> dict[str, Any]:
+        self.commands.append(command)
+        if command[:1] == ["open"]:
+            # The first open "redirects"; leaving for about:blank lands there.
+            if command[1] == server.BLANK_PAGE_URL:
+                self.landed_url = server.BLANK_PAGE_URL
+            return {"success": True, "data": {}}
+        if command == ["get", "url"]:
+            return {"success": True, "data": {"url": self.landed_url}}
+        if command == ["get", "title"]:
+            return {"success": True, "data": {"title": "Landed"}}
+        return {"success": True, "data": {}}
+
+
+class NavigationSafetyTests(unittest.TestCase):
+    def assert_blocked(self, url: str, settings: dict[str, Any] | None = None) -> None:
+        with self.assertRaises(ValueError, msg=url):
+            validate_navigation_target(url, settings or {})
+
+    def assert_allowed(self, url: str, settings: dict[str, Any] | None = None) -> None:
+        validate_navigation_target(url, settings or {})
+
+    def test_blocks_loopback_and_localhost_names(self) -> None:
+        for url in (
+            "http://127.0.0.1/",
+            "http://127.8.9.10:8080/admin",
+            "http://[::1]/",
+            "http://localhost:3000/",
+            "http://LOCALHOST./",
+            "http://app.localhost/",
+            "http://0.0.0.0/",
+        ):
+            self.assert_blocked(url)
+
+    def test_blocks_private_and_cgnat_ranges(self) -> None:
+        for url in (
+            "http://10.0.0.5/",
+            "http://172.16.4.1/",
+            "http://192.168.1.1/",
+            "http://100.64.0.1/",
+            "http://[fd12:3456::1]/",
+        ):
+            self.assert_blocked(url)
+
+    def test_blocks_link_local_and_metadata_endpoints(self) -> None:
+        for url in (
+            "http://169.254.169.254/latest/meta-data/",
+            "http://169.254.1.1/",
+            "http://[fe80::1]/",
+            "http://metadata.google.internal/computeMetadata/v1/",
+        ):
+            self.assert_blocked(url)
+
+    def test_blocks_ipv4_mapped_ipv6(self) -> None:
+        for url in (
+            "http://[::ffff:127.0.0.1]/",
+            "http://[::ffff:10.0.0.1]/",
+            "http://[::ffff:169.254.169.254]/",
+        ):
+            self.assert_blocked(url)
+
+    def test_blocks_hostnames_resolving_to_private_addresses(self) -> None:
+        resolver = fake_resolver(
+            {"internal.example": "127.0.0.1", "public.example": "93.184.216.34"}
+        )
+        with mock.patch("browser_orb.server.socket.getaddrinfo", resolver):
+            self.assert_blocked("https://internal.example/")
+            self.assert_allowed("https://public.example/")
+
+    def test_allows_public_addresses(self) -> None:
+        self.assert_allowed("https://93.184.216.34/")
+        self.assert_allowed("https://[2606:2800:220:1:248:1893:25c8:1946]/")
+
+    def test_local_exceptions_never_admit_metadata(self) -> None:
+        settings = {
+            "allowed_local_hosts": "localhost, 192.168.1.20\n169.254.169.254"
+        }
+        self.assert_allowed("http://localhost:8188/", settings)
+        self.assert_allowed("http://192.168.1.20/", settings)
+        self.assert_blocked("http://192.168.1.21/", settings)
+        self.assert_blocked("http://169.254.169.254/latest/meta-data/", settings)
+        self.assert_blocked("http://[::ffff:169.254.169.254]/", settings)
+
+    def test_redirect_to_a_blocked_host_leaves_the_page(self) -> None:
+        for landed in (
+            "http://169.254.169.254/latest/meta-data/",
+            "http://127.0.0.1:8188/",
+        ):
+            browser = FakeBrowser(landed)
+            with mock.patch.object(server, "run_agent_browser", browser.run):
+                with self.assertRaises(ValueError, msg=landed):
+                    server.browser_open({"url": "https://93.184.216.34/redirect"})
+                with self.assertRaises(ValueError, msg=landed):
+                    browser.landed_url = landed
+                    server.browser_click({"target": "@e1"})
+            self.assertIn(["open", server.BLANK_PAGE_URL], browser.commands)
+            self.assertNotIn(["get", "title"], browser.commands)
+            self.assertIsNone(server.STATE.last_known_url)
+
+    def test_landing_on_a_public_page_reports_its_url(self) -> None:
+        browser = FakeBrowser("https://93.184.216.34/final")
+        with mock.patch.object(server, "run_agent_browser", browser.run):
+            result = server.browser_open({"url": "https://93.184.216.34/start"})
+            self.assertEqual(
+                result["data"]["current_url"], "https://93.184.216.34/final"
+            )
+            self.assertEqual(
+                server.browser_wait({"milliseconds": 10})["data"]["current_url"],
+                "https://93.184.216.34/final",
+            )
+        self.assertNotIn(["open", server.BLANK_PAGE_URL], browser.commands)
+
+    def test_unrestricted_navigation_skips_host_checks(self) -> None:
+        self.assert_allowed(
+            "http://127.0.0.1/", {"allow_unrestricted_navigation": True}
+        )
+
+
+if __name__ == "__main__":
+    unittest.main()
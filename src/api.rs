@@ -1,9 +1,11 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use flume::Sender;
+use flume::{Receiver, Sender, TrySendError};
 use futures_util::StreamExt;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::sync::{Arc, RwLock};
 use tokio::time::{sleep, Duration};
 use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
@@ -19,6 +21,22 @@ pub use ponderer_backend::plugin_contract::{
 };
 
 pub const DEFAULT_CHAT_CONVERSATION_ID: &str = "default";
+const DEFAULT_EVENT_HEARTBEAT_TIMEOUT_SECS: u64 = 45;
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 15;
+/// Database backup/restore move the whole SQLite file, so they get their own bound.
+const DATABASE_TRANSFER_TIMEOUT: Duration = Duration::from_secs(600);
+/// A comparison runs two full turns back to back on local models.
+const MODEL_COMPARISON_TIMEOUT: Duration = Duration::from_secs(600);
+/// Long chats are summarized in several chunked LLM passes.
+const SUMMARIZE_TIMEOUT: Duration = Duration::from_secs(300);
+/// Diagnostics probe the LLM and ComfyUI servers, each with its own short timeout.
+const DIAGNOSTICS_TIMEOUT: Duration = Duration::from_secs(60);
+/// Everything but RFC 3986 unreserved characters is escaped in a path segment.
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -35,6 +53,20 @@ pub enum ChatTurnPhase {
     Failed,
 }
 
+impl ChatTurnPhase {
+    /// Sprite/header state that should replace the backend's visual state while a
+    /// conversation is in this phase. Only a turn blocked on the operator overrides it.
+    pub fn visual_state_override(&self) -> Option<AgentVisualState> {
+        match self {
+            ChatTurnPhase::AwaitingApproval => Some(AgentVisualState::WaitingForUser),
+            ChatTurnPhase::Idle
+            | ChatTurnPhase::Processing
+            | ChatTurnPhase::Completed
+            | ChatTurnPhase::Failed => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatConversation {
     pub id: String,
@@ -46,6 +78,168 @@ pub struct ChatConversation {
     pub active_turn_id: Option<String>,
     pub message_count: usize,
     pub last_message_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Text the backend prepends to the character-derived system prompt for
+    /// this conversation only.
+    #[serde(default)]
+    pub system_prompt_prefix: Option<String>,
+    /// Fixed sampling seed for this conversation's turns; `None` lets the backend's
+    /// `AgenticConfig::seed` (or the server) decide.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// User-defined folder in the conversation picker; `None` is ungrouped.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Position within `group`, set by drag-to-reorder. Unordered conversations
+    /// follow the ordered ones in recency order.
+    #[serde(default)]
+    pub sort_index: Option<i64>,
+    /// Token usage summed over every turn; absent on backends without usage accounting.
+    #[serde(default)]
+    pub usage: Option<ConversationUsage>,
+    #[serde(default)]
+    pub tool_mode: ToolMode,
+    /// Exempt from auto-archiving (and every other retention pass).
+    #[serde(default)]
+    pub pinned: bool,
+    /// Set when the conversation was archived; archived listings and
+    /// `get_conversation` carry it.
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+impl ChatConversation {
+    /// Idle, empty conversation for tests. `fields` (e.g. `title`, `group`,
+    /// `archived_at`) are merged over the minimal wire payload.
+    pub(crate) fn fixture(id: &str, fields: Value) -> Self {
+        let mut payload = serde_json::json!({
+            "id": id,
+            "session_id": "s",
+            "title": id,
+            "created_at": "2026-02-17T05:19:24Z",
+            "updated_at": "2026-02-17T05:19:24Z",
+            "runtime_state": "idle",
+            "active_turn_id": null,
+            "message_count": 0,
+            "last_message_at": null,
+        });
+        if let (Some(payload), Value::Object(fields)) = (payload.as_object_mut(), fields) {
+            payload.extend(fields);
+        }
+        serde_json::from_value(payload).expect("conversation fixture")
+    }
+}
+
+/// Per-conversation control over tool use in chat turns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolMode {
+    /// Tools are offered and the model decides (`tool_choice: "auto"`).
+    #[default]
+    Auto,
+    /// No tools are sent, whatever the agent config enables.
+    Off,
+    /// Tools are sent with `tool_choice: "required"`.
+    Required,
+}
+
+impl ToolMode {
+    pub const ALL: [ToolMode; 3] = [ToolMode::Auto, ToolMode::Off, ToolMode::Required];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ToolMode::Auto => "Tools: auto",
+            ToolMode::Off => "Tools: off",
+            ToolMode::Required => "Tools: required",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ToolMode::Auto => "The agent decides when to call tools",
+            ToolMode::Off => "Plain chat: no tools are offered in this conversation",
+            ToolMode::Required => "Every model call in this conversation must call a tool",
+        }
+    }
+}
+
+/// Cumulative token usage and estimated spend for one conversation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConversationUsage {
+    #[serde(default)]
+    pub prompt_tokens: u64,
+    #[serde(default)]
+    pub completion_tokens: u64,
+    /// `None` when the model has no configured price.
+    #[serde(default)]
+    pub estimated_cost_usd: Option<f64>,
+}
+
+impl ConversationUsage {
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+
+    /// Short form for the conversation header, e.g. `12.4k tokens · $0.031`.
+    pub fn summary(&self) -> String {
+        let tokens = format!("{} tokens", compact_count(self.total_tokens()));
+        match self.estimated_cost_usd {
+            Some(cost) => format!("{} · {}", tokens, format_usd(cost)),
+            None => tokens,
+        }
+    }
+}
+
+fn compact_count(value: u64) -> String {
+    match value {
+        0..=999 => value.to_string(),
+        1_000..=999_999 => format!("{:.1}k", value as f64 / 1_000.0),
+        _ => format!("{:.2}M", value as f64 / 1_000_000.0),
+    }
+}
+
+fn format_usd(cost: f64) -> String {
+    if cost > 0.0 && cost < 0.01 {
+        format!("${:.4}", cost)
+    } else {
+        format!("${:.2}", cost)
+    }
+}
+
+/// Where one conversation sits in the picker, as persisted by `reorder_conversations`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationPlacement {
+    pub id: String,
+    pub group: Option<String>,
+    pub sort_index: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UpdateConversationRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// `Some("")` clears the stored note.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// `Some("")` clears the prefix, restoring the global prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_prompt_prefix: Option<String>,
+    /// `Some(None)` serializes as `null` and clears the fixed seed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_mode: Option<ToolMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
+    /// `Some(false)` restores an archived conversation; `Some(true)` archives it now.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +251,23 @@ pub struct ChatMessage {
     pub created_at: DateTime<Utc>,
     pub processed: bool,
     pub turn_id: Option<String>,
+    #[serde(default)]
+    pub truncated: bool,
+    /// Model reasoning the backend separated from the answer (a `<think>` block or
+    /// the API's `reasoning` field); `content` holds only the answer.
+    #[serde(default)]
+    pub reasoning: Option<String>,
+    /// Operator feedback on the turn that produced this message.
+    #[serde(default)]
+    pub rating: Option<TurnRating>,
+}
+
+/// Thumbs up/down on an agent turn; no rating is `None`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TurnRating {
+    Up,
+    Down,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,6 +275,269 @@ pub struct ChatTurnPrompt {
     pub turn_id: String,
     pub prompt_text: String,
     pub system_prompt_text: Option<String>,
+    /// Seed sent with the turn's LLM request, when one was configured.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Where the turn's wall-clock time went; absent for turns recorded before timing existed.
+    #[serde(default)]
+    pub timing: Option<TurnTiming>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimingPhaseKind {
+    ContextBuild,
+    LlmCall,
+    ToolCall,
+    /// Anything the backend timed that is not one of the above (compaction, persistence).
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnTimingPhase {
+    pub kind: TimingPhaseKind,
+    /// e.g. `llm call 2` or the tool name.
+    pub label: String,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub tool_call_id: Option<String>,
+}
+
+/// Per-phase durations the agentic loop recorded for one turn, in run order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnTiming {
+    pub total_ms: u64,
+    #[serde(default)]
+    pub phases: Vec<TurnTimingPhase>,
+}
+
+impl TurnTiming {
+    /// Time not covered by any phase (loop bookkeeping, event emission).
+    pub fn unaccounted_ms(&self) -> u64 {
+        let phases: u64 = self.phases.iter().map(|phase| phase.duration_ms).sum();
+        self.total_ms.saturating_sub(phases)
+    }
+
+    pub fn total_for(&self, kind: TimingPhaseKind) -> u64 {
+        self.phases
+            .iter()
+            .filter(|phase| phase.kind == kind)
+            .map(|phase| phase.duration_ms)
+            .sum()
+    }
+
+    /// Index of the longest phase, the likely bottleneck.
+    pub fn slowest_phase(&self) -> Option<usize> {
+        self.phases
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, phase)| phase.duration_ms)
+            .map(|(index, _)| index)
+    }
+}
+
+/// Where the backend keeps persistent state (rooted at `PONDERER_DATA_DIR` when set).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageInfo {
+    pub data_dir: String,
+    #[serde(default)]
+    pub database_path: Option<String>,
+    #[serde(default)]
+    pub media_dir: Option<String>,
+    /// `true` when `PONDERER_DATA_DIR` overrides the platform default.
+    #[serde(default)]
+    pub overridden: bool,
+}
+
+/// Automatic pruning policy. `None` keeps that kind of data forever; pinned
+/// conversations and flagged concerns are always exempt on the backend.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Delete stored turn prompts (prompt inspector data) older than this.
+    #[serde(default)]
+    pub turn_prompt_days: Option<u32>,
+    /// Archive conversations with no new messages for this long.
+    #[serde(default)]
+    pub archive_conversation_days: Option<u32>,
+    /// Delete journal entries older than this.
+    #[serde(default)]
+    pub journal_days: Option<u32>,
+}
+
+/// Retention policy plus the result of the most recent pruning pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionStatus {
+    #[serde(flatten)]
+    pub policy: RetentionPolicy,
+    #[serde(default)]
+    pub last_pruned_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_report: Option<PruneReport>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneReport {
+    #[serde(default)]
+    pub turn_prompts_deleted: u64,
+    #[serde(default)]
+    pub conversations_archived: u64,
+    #[serde(default)]
+    pub journal_entries_deleted: u64,
+}
+
+impl std::fmt::Display for PruneReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} turn prompts deleted, {} conversations archived, {} journal entries deleted",
+            self.turn_prompts_deleted, self.conversations_archived, self.journal_entries_deleted
+        )
+    }
+}
+
+/// Recap of a conversation from `POST /v1/conversations/:id/summarize`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationRecap {
+    pub summary: String,
+    #[serde(default)]
+    pub message_count: usize,
+    /// Number of history chunks summarized separately before being combined.
+    #[serde(default = "default_chunk_count")]
+    pub chunk_count: usize,
+    /// Set when the recap was also stored as a journal entry.
+    #[serde(default)]
+    pub journal_entry_id: Option<String>,
+}
+
+fn default_chunk_count() -> usize {
+    1
+}
+
+/// Why the agent took an action: the orientation and decision that preceded it
+/// and the part of the stored turn prompt it acted on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionExplanation {
+    pub action_id: String,
+    pub action: String,
+    #[serde(default)]
+    pub result: Option<String>,
+    /// Orientation disposition/summary in effect when the action was chosen.
+    #[serde(default)]
+    pub orientation: Option<String>,
+    /// The decision text (or tool-call rationale) that selected the action.
+    #[serde(default)]
+    pub decision: Option<String>,
+    /// Turn whose stored prompt led to the action; opens in the prompt inspector.
+    #[serde(default)]
+    pub turn_id: Option<String>,
+    /// Excerpt of that prompt the backend judged most relevant.
+    #[serde(default)]
+    pub prompt_section: Option<String>,
+}
+
+/// Same message, several models. The backend runs each through the agentic loop
+/// in dry-run mode and persists nothing.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompareModelsRequest {
+    /// Conversation whose history and prompt prefix give the turns context; `None` starts cold.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<String>,
+    pub content: String,
+    pub models: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelComparison {
+    /// One entry per requested model, in request order.
+    pub results: Vec<ModelComparisonResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelComparisonResult {
+    pub model: String,
+    #[serde(default)]
+    pub response: String,
+    /// Set when this model's run failed; the other results are still returned.
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub latency_ms: Option<u64>,
+    /// Tools the model tried to call. Side-effecting ones were not executed.
+    #[serde(default)]
+    pub tool_calls: Vec<String>,
+}
+
+/// Snapshot of the most recent outbound LLM request, for reproducing it by hand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmRequestCapture {
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    #[serde(default)]
+    pub body: Value,
+    #[serde(default)]
+    pub captured_at: Option<DateTime<Utc>>,
+}
+
+impl LlmRequestCapture {
+    /// Renders a pasteable `curl` command. Credential headers are masked even if
+    /// the backend already redacted them.
+    pub fn to_curl(&self) -> String {
+        let mut parts = vec![
+            "curl".to_string(),
+            "-X POST".to_string(),
+            shell_quote(&self.url),
+        ];
+        for (name, value) in &self.headers {
+            let value = if is_credential_header(name) {
+                mask_credential(value)
+            } else {
+                value.clone()
+            };
+            parts.push(format!(
+                "-H {}",
+                shell_quote(&format!("{}: {}", name, value))
+            ));
+        }
+        let body = serde_json::to_string_pretty(&self.body).unwrap_or_default();
+        parts.push(format!("--data-raw {}", shell_quote(&body)));
+        parts.join(" \\\n  ")
+    }
+}
+
+fn is_credential_header(name: &str) -> bool {
+    matches!(
+        name.to_ascii_lowercase().as_str(),
+        "authorization" | "api-key" | "x-api-key" | "proxy-authorization"
+    )
+}
+
+fn mask_credential(value: &str) -> String {
+    match value.split_once(' ') {
+        Some((scheme, _)) => format!("{} ***", scheme),
+        None => "***".to_string(),
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatTurnToolCall {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub turn_id: String,
+    #[serde(default)]
+    pub tool_call_id: Option<String>,
+    pub tool_name: String,
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+    #[serde(default)]
+    pub output: serde_json::Value,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,7 +566,209 @@ pub struct UpdateScheduledJobRequest {
     pub enabled: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolInfo {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub parameters: serde_json::Value,
+    #[serde(default)]
+    pub side_effecting: bool,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkingMemoryEntry {
+    pub key: String,
+    #[serde(default)]
+    pub content: String,
+    /// `None` means global; otherwise only surfaced in this conversation's context.
+    #[serde(default)]
+    pub conversation_id: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Behaviour switches served by `/v1/config/flags`. The backend persists them
+/// in `AgentConfig` and applies them itself, e.g. `concise_replies` adds a
+/// brevity instruction while assembling the prompt instead of editing
+/// `system_prompt`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigFlags {
+    /// Pause each turn's tool calls as a `ToolBatchPending` until the operator decides.
+    #[serde(default)]
+    pub confirm_tool_batches: bool,
+    #[serde(default)]
+    pub concise_replies: bool,
+}
+
+/// Who made a recorded config edit.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigChangeSource {
+    User,
+    Agent,
+}
+
+/// Config fields an agent proposal may never touch: credentials, where prompts
+/// and messages are sent, and the switches that keep the agent in check.
+/// Fields with secret-looking names (`is_secret_field_name`) are refused too.
+pub const PROTECTED_CONFIG_FIELDS: &[&str] = &[
+    "llm_api_url",
+    "telegram_chat_id",
+    "loose_mode",
+    "disable_tool_iteration_limit",
+    "disable_chat_turn_limit",
+    "disable_background_subtask_turn_limit",
+    "confirm_tool_batches",
+    "plugin_settings",
+];
+
+/// Names that hold credentials: ending in `key`, or mentioning a token, secret, or password.
+pub fn is_secret_field_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.ends_with("key")
+        || name.contains("token")
+        || name.contains("secret")
+        || name.contains("password")
+}
+
+/// One field of a config edit. History entries arrive with secret values redacted by the backend.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfigFieldChange {
+    pub field: String,
+    #[serde(default)]
+    pub old: Value,
+    #[serde(default)]
+    pub new: Value,
+}
+
+impl ConfigFieldChange {
+    /// `field: old → new`, with strings unquoted and values longer than
+    /// `max_value_chars` shortened.
+    pub fn describe(&self, max_value_chars: usize) -> String {
+        let show = |value: &Value| -> String {
+            let text = match value {
+                Value::Null => "(unset)".to_string(),
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            crate::ui::app::truncate_str(&text, max_value_chars)
+        };
+        format!("{}: {} → {}", self.field, show(&self.old), show(&self.new))
+    }
+
+    /// A secret or safety-relevant field that proposals are not allowed to change.
+    pub fn is_protected(&self) -> bool {
+        PROTECTED_CONFIG_FIELDS.contains(&self.field.as_str()) || is_secret_field_name(&self.field)
+    }
+}
+
+/// A single `update_config` call as recorded in the backend's change log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigChange {
+    pub changed_at: DateTime<Utc>,
+    pub source: ConfigChangeSource,
+    #[serde(default)]
+    pub changes: Vec<ConfigFieldChange>,
+}
+
+/// A config edit the agent asked for. Nothing changes until the operator approves it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigProposal {
+    pub id: String,
+    /// The agent's stated reason for the change.
+    #[serde(default)]
+    pub reason: String,
+    pub changes: Vec<ConfigFieldChange>,
+    #[serde(default)]
+    pub proposed_at: Option<DateTime<Utc>>,
+}
+
+impl ConfigProposal {
+    /// The backend refuses these; the desktop also never shows their values or applies them.
+    pub fn touches_protected_field(&self) -> bool {
+        self.changes.iter().any(ConfigFieldChange::is_protected)
+    }
+}
+
+/// One call in a turn's tool batch awaiting confirmation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingToolCall {
+    pub tool_call_id: String,
+    pub tool_name: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+/// All tool calls from one assistant message, held until the operator decides on each.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolBatch {
+    pub conversation_id: String,
+    pub turn_id: String,
+    pub calls: Vec<PendingToolCall>,
+}
+
+/// The operator's verdict on one call of a [`ToolBatch`].
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ToolCallDecision {
+    pub tool_call_id: String,
+    pub approve: bool,
+}
+
+/// Outcome of one diagnostics row.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// Not run, usually because a check it depends on failed.
+    Skip,
+}
+
+/// One row of the diagnostics view, from `GET /v1/diagnostics` or a client-side probe.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    /// Error text on failure, or a short fact on success ("llama3 loaded").
+    #[serde(default)]
+    pub detail: String,
+}
+
+impl DiagnosticCheck {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn pass(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Pass, detail)
+    }
+
+    pub fn fail(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Fail, detail)
+    }
+
+    pub fn skip(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Skip, detail)
+    }
+}
+
+/// Body for pinning operator-chosen text into working memory; the backend picks the key.
+#[derive(Debug, Clone, Serialize)]
+pub struct RememberRequest {
+    pub content: String,
+    /// Omitted for global scope.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum AgentVisualState {
     #[serde(alias = "Idle")]
@@ -109,6 +785,35 @@ pub enum AgentVisualState {
     Confused,
     #[serde(alias = "Paused")]
     Paused,
+    /// A turn is paused on an approval only the operator can give.
+    #[serde(alias = "WaitingForUser")]
+    WaitingForUser,
+}
+
+impl AgentVisualState {
+    pub const ALL: [AgentVisualState; 8] = [
+        AgentVisualState::Idle,
+        AgentVisualState::Reading,
+        AgentVisualState::Thinking,
+        AgentVisualState::Writing,
+        AgentVisualState::Happy,
+        AgentVisualState::Confused,
+        AgentVisualState::Paused,
+        AgentVisualState::WaitingForUser,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AgentVisualState::Idle => "Idle",
+            AgentVisualState::Reading => "Reading",
+            AgentVisualState::Thinking => "Thinking",
+            AgentVisualState::Writing => "Writing",
+            AgentVisualState::Happy => "Happy",
+            AgentVisualState::Confused => "Confused",
+            AgentVisualState::Paused => "Paused",
+            AgentVisualState::WaitingForUser => "Waiting for you",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,6 +832,9 @@ pub struct AgentRuntimeStatus {
     pub loose_mode: bool,
     #[serde(default)]
     pub current_intention: Option<RuntimeIntentionSummary>,
+    /// Autonomous cycles are paused while chat turns still run.
+    #[serde(default)]
+    pub autonomy_paused: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -139,7 +847,41 @@ pub struct RuntimeIntentionSummary {
     pub last_outcome: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+/// Where an [`UpcomingItem`] comes from.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpcomingKind {
+    ScheduledAction,
+    Reminder,
+    Intention,
+}
+
+impl UpcomingKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ScheduledAction => "scheduled",
+            Self::Reminder => "reminder",
+            Self::Intention => "intention",
+        }
+    }
+}
+
+/// One entry in the agent's queue of upcoming autonomous work. The backend
+/// merges pending scheduled actions, due reminders, and active intentions
+/// and returns them soonest first; undated intentions come last.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingItem {
+    pub kind: UpcomingKind,
+    pub id: String,
+    pub summary: String,
+    #[serde(default)]
+    pub due_at: Option<DateTime<Utc>>,
+    /// Status or motivation line shown on hover.
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct OrientationSummary {
     pub disposition: String,
     pub anomaly_count: usize,
@@ -154,7 +896,7 @@ pub struct TokenMetricSample {
     pub novelty: f32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum FrontendEvent {
     StateChanged(AgentVisualState),
     Observation(String),
@@ -163,6 +905,15 @@ pub enum FrontendEvent {
         conversation_id: String,
         tool_name: String,
         output_preview: String,
+        /// 1-based agentic-loop iteration this progress belongs to, when the backend reports it.
+        iteration: Option<u32>,
+        max_iterations: Option<u32>,
+        /// Set by streaming tools so successive chunks update one entry instead of appending.
+        tool_call_id: Option<String>,
+        /// `true` while a streaming tool is still producing output; the final event has `false`.
+        partial: bool,
+        /// The call's arguments as the model sent them; older backends omit them.
+        arguments: Option<Value>,
     },
     ChatStreaming {
         conversation_id: String,
@@ -189,6 +940,8 @@ pub enum FrontendEvent {
     ActionTaken {
         action: String,
         result: String,
+        /// Lets the UI ask for the reasoning behind this action; absent on older backends.
+        action_id: Option<String>,
     },
     OrientationUpdate(OrientationSummary),
     JournalWritten(String),
@@ -210,6 +963,106 @@ pub enum FrontendEvent {
     CycleStart {
         label: String,
     },
+    /// Conversations, journal, concerns, and working memory were wiped by a factory reset.
+    AgentReset,
+    /// Older turns of a conversation were folded into a summary and no longer
+    /// reach the prompt verbatim.
+    ConversationCompacted {
+        conversation_id: String,
+        summary: String,
+        /// Last message covered by the summary, when the backend reports it.
+        through_message_id: Option<String>,
+    },
+    /// A turn stopped at `max_iterations`. `mid_progress` is set when the last
+    /// iteration still made tool calls, i.e. the limit cut off active work.
+    IterationLimitHit {
+        conversation_id: String,
+        max_iterations: Option<u32>,
+        mid_progress: bool,
+    },
+    /// The agent proposed a config change; it waits for `ApiClient::resolve_config_proposal`.
+    ConfigProposed(ConfigProposal),
+    /// A turn paused before running its tool calls; answered with `ApiClient::decide_tool_batch`.
+    ToolBatchPending(ToolBatch),
+    /// A tool asked the operator a question mid-turn; the turn stays paused in
+    /// `AwaitingApproval` until `ApiClient::provide_tool_input` answers it.
+    ToolInputRequested {
+        conversation_id: String,
+        turn_id: String,
+        tool_call_id: String,
+        tool_name: String,
+        prompt: String,
+    },
+}
+
+impl FrontendEvent {
+    /// High-frequency updates that a later event of the same kind supersedes
+    /// (accumulated streaming text, partial tool output, token-metric samples).
+    /// `EventSink` may drop these under backpressure; everything else is kept.
+    /// A partial tool chunk that carries `arguments` is kept: streaming tools
+    /// send them only once, so later chunks cannot replace it.
+    pub fn is_droppable(&self) -> bool {
+        matches!(
+            self,
+            FrontendEvent::ChatStreaming { done: false, .. }
+                | FrontendEvent::ToolCallProgress {
+                    partial: true,
+                    arguments: None,
+                    ..
+                }
+                | FrontendEvent::GenerationMetrics { .. }
+        )
+    }
+}
+
+/// Events the UI can fall behind by before `EventSink` starts dropping.
+pub const EVENT_CHANNEL_CAPACITY: usize = 512;
+
+/// Producer side of the bounded UI event channel. When the channel is full, the
+/// oldest droppable event is evicted to make room. Critical events are never
+/// dropped: if nothing queued is droppable they wait for the UI to catch up.
+#[derive(Clone)]
+pub struct EventSink {
+    tx: Sender<FrontendEvent>,
+    /// Second handle on the queue, used only to evict while full.
+    queued: Receiver<FrontendEvent>,
+}
+
+impl EventSink {
+    pub fn bounded(capacity: usize) -> (Self, Receiver<FrontendEvent>) {
+        let (tx, rx) = flume::bounded(capacity);
+        (
+            Self {
+                tx,
+                queued: rx.clone(),
+            },
+            rx,
+        )
+    }
+
+    pub async fn push(&self, event: FrontendEvent) {
+        let event = match self.tx.try_send(event) {
+            Ok(()) | Err(TrySendError::Disconnected(_)) => return,
+            Err(TrySendError::Full(event)) => event,
+        };
+
+        // This sink is the only producer, so re-sending the drained backlog keeps
+        // order; the UI may take some of it meanwhile, which only frees room.
+        let mut backlog: Vec<FrontendEvent> = self.queued.drain().collect();
+        if let Some(oldest) = backlog.iter().position(FrontendEvent::is_droppable) {
+            backlog.remove(oldest);
+            backlog.push(event);
+        } else if event.is_droppable() {
+            tracing::debug!("UI event channel full; dropping a streaming update");
+        } else {
+            backlog.push(event);
+        }
+        for event in backlog {
+            if self.tx.send_async(event).await.is_err() {
+                return;
+            }
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -218,6 +1071,45 @@ struct ApiEventEnvelope {
     payload: Value,
 }
 
+/// Envelope plus the replay cursor fields.
+#[derive(Debug, Deserialize)]
+struct SequencedEventEnvelope {
+    #[serde(flatten)]
+    envelope: ApiEventEnvelope,
+    /// Monotonic per backend process; absent on older backends.
+    #[serde(default)]
+    event_id: Option<u64>,
+    /// Identifies the backend process, so ids restarting from 1 are not taken as replays.
+    #[serde(default)]
+    stream_id: Option<String>,
+}
+
+/// Where the client is in the backend's event sequence. Sent back on reconnect so
+/// the backend can replay what was missed, and used to drop replayed events the
+/// client already has.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct EventCursor {
+    stream_id: Option<String>,
+    last_event_id: Option<u64>,
+}
+
+impl EventCursor {
+    /// Returns `false` for an event that was already delivered.
+    fn accept(&mut self, stream_id: Option<&str>, event_id: Option<u64>) -> bool {
+        let Some(event_id) = event_id else {
+            return true;
+        };
+        if stream_id != self.stream_id.as_deref() {
+            // New backend process: its ids are unrelated to ours.
+            self.stream_id = stream_id.map(ToString::to_string);
+        } else if self.last_event_id.is_some_and(|last| event_id <= last) {
+            return false;
+        }
+        self.last_event_id = Some(event_id);
+        true
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct SendMessageResponse {
     message_id: String,
@@ -228,6 +1120,10 @@ struct ChatTurnPromptResponse {
     turn_id: String,
     prompt_text: String,
     system_prompt_text: Option<String>,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    timing: Option<TurnTiming>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -240,6 +1136,17 @@ struct LooseModeResponse {
     enabled: bool,
 }
 
+#[derive(Debug, Deserialize)]
+struct TriggerCycleResponse {
+    started: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionStartResponse {
+    #[serde(default)]
+    greeting_queued: bool,
+}
+
 #[derive(Debug, Deserialize)]
 struct StopResponse {
     stopped: bool,
@@ -255,7 +1162,242 @@ pub struct ApiClient {
     http: reqwest::Client,
     base_url: String,
     ws_url: String,
-    token: Option<String>,
+    /// Shared across clones so a token re-entered in the UI also reaches the event stream.
+    token: Arc<RwLock<Option<String>>>,
+    heartbeat_timeout: Duration,
+    request_timeout: Duration,
+    bypass_proxy: bool,
+}
+
+/// Why a backend call failed. Non-success responses are returned as this type
+/// (inside `anyhow::Error`, so callers keep their `.context(...)`); transport and
+/// decode failures are recognised from the underlying error by [`ApiError::of`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    /// The backend could not be reached or the connection dropped.
+    Network(String),
+    /// HTTP 401/403: the bearer token was rejected.
+    Auth { status: u16 },
+    /// No response within the client's request timeout.
+    Timeout,
+    /// HTTP 404: the conversation, job, or other resource no longer exists.
+    NotFound { body: String },
+    /// Any other non-success status.
+    Server { status: u16, body: String },
+    /// The response arrived but did not have the expected shape.
+    Decode(String),
+}
+
+/// Error bodies are only for display; cap them so an HTML error page stays readable.
+const API_ERROR_BODY_LIMIT: usize = 300;
+
+impl ApiError {
+    /// Classifies `error` by walking its context chain. `None` means the failure
+    /// did not come from talking to the backend (e.g. a local file error).
+    pub fn of(error: &anyhow::Error) -> Option<ApiError> {
+        error.chain().find_map(|cause| {
+            if let Some(api_error) = cause.downcast_ref::<ApiError>() {
+                return Some(api_error.clone());
+            }
+            if let Some(http_error) = cause.downcast_ref::<reqwest::Error>() {
+                return Some(if http_error.is_timeout() {
+                    ApiError::Timeout
+                } else if http_error.is_decode() {
+                    ApiError::Decode(http_error.to_string())
+                } else {
+                    ApiError::Network(http_error.to_string())
+                });
+            }
+            cause
+                .downcast_ref::<serde_json::Error>()
+                .map(|json_error| ApiError::Decode(json_error.to_string()))
+        })
+    }
+
+    fn from_status(status: reqwest::StatusCode, body: &str) -> Self {
+        let body = body.trim();
+        let body = match body.char_indices().nth(API_ERROR_BODY_LIMIT) {
+            Some((cut, _)) => format!("{}…", &body[..cut]),
+            None => body.to_string(),
+        };
+        match status {
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => ApiError::Auth {
+                status: status.as_u16(),
+            },
+            reqwest::StatusCode::NOT_FOUND => ApiError::NotFound { body },
+            _ => ApiError::Server {
+                status: status.as_u16(),
+                body,
+            },
+        }
+    }
+
+    /// Whether resending the same request could plausibly succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::Network(_) | ApiError::Timeout => true,
+            ApiError::Auth { .. } | ApiError::NotFound { .. } | ApiError::Decode(_) => false,
+            ApiError::Server { status, .. } => {
+                *status >= 500
+                    || *status == reqwest::StatusCode::REQUEST_TIMEOUT.as_u16()
+                    || *status == reqwest::StatusCode::TOO_MANY_REQUESTS.as_u16()
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::Network(detail) => write!(f, "backend unreachable: {}", detail),
+            ApiError::Timeout => write!(f, "backend did not respond in time"),
+            ApiError::Auth { status } => write!(
+                f,
+                "backend authentication failed (HTTP {}); check PONDERER_BACKEND_TOKEN",
+                status
+            ),
+            ApiError::NotFound { body } if body.is_empty() => write!(f, "not found (HTTP 404)"),
+            ApiError::NotFound { body } => write!(f, "not found (HTTP 404): {}", body),
+            ApiError::Server { status, body } if body.is_empty() => {
+                write!(f, "backend returned HTTP {}", status)
+            }
+            ApiError::Server { status, body } => {
+                write!(f, "backend returned HTTP {}: {}", status, body)
+            }
+            ApiError::Decode(detail) => write!(f, "unexpected backend response: {}", detail),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+/// Phrase the user must type (and the backend must receive) to factory-reset the agent.
+pub const AGENT_RESET_CONFIRMATION: &str = "RESET";
+
+/// A turn from an external export, mapped onto Ponderer's `operator` / `agent` roles.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportedMessage {
+    pub role: String,
+    pub content: String,
+}
+
+/// Parsed import file: the turns to create plus how many entries were dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversationImport {
+    pub title: Option<String>,
+    pub messages: Vec<ImportedMessage>,
+    pub skipped: usize,
+}
+
+/// Result of `ApiClient::import_conversation`.
+#[derive(Debug, Clone)]
+pub struct ImportedConversation {
+    pub conversation: ChatConversation,
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+/// Accepts OpenAI-style `{ "messages": [{ "role", "content" }] }` (optionally
+/// with `title`) or a bare message array. `user`/`assistant` map to
+/// `operator`/`agent`, Ponderer's own roles pass through, and array content
+/// parts are joined by their `text`. Other roles and empty turns are skipped
+/// with a warning.
+pub fn parse_conversation_import(json: &str) -> Result<ConversationImport> {
+    let root: Value = serde_json::from_str(json).context("Import file is not valid JSON")?;
+    let (title, entries) = match &root {
+        Value::Array(entries) => (None, entries),
+        Value::Object(object) => (
+            object
+                .get("title")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|title| !title.is_empty())
+                .map(str::to_string),
+            object
+                .get("messages")
+                .and_then(Value::as_array)
+                .context("Import file has no \"messages\" array")?,
+        ),
+        _ => anyhow::bail!("Import file must be a message array or an object with \"messages\""),
+    };
+
+    let mut messages = Vec::with_capacity(entries.len());
+    let mut skipped = 0;
+    for (index, entry) in entries.iter().enumerate() {
+        let raw_role = entry
+            .get("role")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let role = match raw_role.to_ascii_lowercase().as_str() {
+            "user" | "human" | "operator" => "operator",
+            "assistant" | "ai" | "model" | "agent" => "agent",
+            _ => {
+                tracing::warn!(
+                    "Skipping imported message {} with role {:?}",
+                    index,
+                    raw_role
+                );
+                skipped += 1;
+                continue;
+            }
+        };
+        let content = match entry.get("content") {
+            Some(Value::String(text)) => text.clone(),
+            Some(Value::Array(parts)) => parts
+                .iter()
+                .filter_map(|part| part.get("text").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => String::new(),
+        };
+        if content.trim().is_empty() {
+            tracing::warn!("Skipping imported message {} with no text content", index);
+            skipped += 1;
+            continue;
+        }
+        messages.push(ImportedMessage {
+            role: role.to_string(),
+            content,
+        });
+    }
+
+    if messages.is_empty() {
+        anyhow::bail!("Import file contains no user or assistant messages");
+    }
+    Ok(ConversationImport {
+        title,
+        messages,
+        skipped,
+    })
+}
+
+pub fn is_auth_error(error: &anyhow::Error) -> bool {
+    matches!(ApiError::of(error), Some(ApiError::Auth { .. }))
+}
+
+/// Whether resending the same request could plausibly succeed. Failures that
+/// did not come from the backend are assumed transient.
+pub fn is_retryable_error(error: &anyhow::Error) -> bool {
+    ApiError::of(error).map_or(true, |api_error| api_error.is_retryable())
+}
+
+trait ResponseStatusExt: Sized {
+    /// Like `error_for_status`, but the failure is an [`ApiError`] carrying the
+    /// response body.
+    async fn ensure_success(self) -> Result<Self>;
+}
+
+impl ResponseStatusExt for reqwest::Response {
+    async fn ensure_success(self) -> Result<Self> {
+        let status = self.status();
+        if status.is_success() {
+            return Ok(self);
+        }
+        let body = self.text().await.unwrap_or_default();
+        Err(ApiError::from_status(status, &body).into())
+    }
 }
 
 impl ApiClient {
@@ -281,38 +1423,57 @@ impl ApiClient {
     fn build(base_url: String, token: Option<String>, bypass_proxy: bool) -> Self {
         let normalized_base = normalize_base_url(&base_url);
         let ws_url = normalize_ws_url(&normalized_base);
-
-        let mut builder = reqwest::Client::builder().timeout(Duration::from_secs(15));
-        if bypass_proxy {
-            builder = builder.no_proxy();
-        }
-        let http = builder.build().unwrap_or_else(|error| {
-            tracing::warn!("Failed to build bounded API client: {}", error);
-            reqwest::Client::builder()
-                .no_proxy()
-                .timeout(Duration::from_secs(15))
-                .build()
-                .expect("bounded no-proxy API client")
-        });
+        let request_timeout = request_timeout_from_env();
 
         Self {
-            http,
+            http: build_http_client(request_timeout, bypass_proxy),
             base_url: normalized_base,
             ws_url,
-            token,
+            token: Arc::new(RwLock::new(token)),
+            heartbeat_timeout: event_heartbeat_timeout_from_env(),
+            request_timeout,
+            bypass_proxy,
         }
     }
 
+    /// Overrides the per-request timeout (default `PONDERER_API_TIMEOUT_SECS` or 15s).
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.http = build_http_client(timeout, self.bypass_proxy);
+        self.request_timeout = timeout;
+        self
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
 
+    /// Replaces the bearer token for this client and every clone of it.
+    pub fn set_token(&self, token: Option<String>) {
+        let token = token.filter(|value| !value.trim().is_empty());
+        match self.token.write() {
+            Ok(mut guard) => *guard = token,
+            Err(poisoned) => *poisoned.into_inner() = token,
+        }
+    }
+
+    fn current_token(&self) -> Option<String> {
+        match self.token.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
     pub async fn health(&self) -> Result<()> {
         let response = self
             .request(reqwest::Method::GET, "/v1/health")
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("GET /v1/health failed")?
             .json::<HealthResponse>()
             .await
@@ -320,11 +1481,27 @@ impl ApiClient {
         validate_health_status(&response.status)
     }
 
+    /// Backend-side health checks (LLM server, ComfyUI, data dir, GPU). Probing
+    /// a cold LLM server can take a while, hence `DIAGNOSTICS_TIMEOUT`.
+    pub async fn diagnostics(&self) -> Result<Vec<DiagnosticCheck>> {
+        self.request(reqwest::Method::GET, "/v1/diagnostics")
+            .timeout(DIAGNOSTICS_TIMEOUT)
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/diagnostics failed")?
+            .json::<Vec<DiagnosticCheck>>()
+            .await
+            .context("Failed to decode diagnostics")
+    }
+
     pub async fn get_config(&self) -> Result<crate::config::AgentConfig> {
         self.request(reqwest::Method::GET, "/v1/config")
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("GET /v1/config failed")?
             .json::<crate::config::AgentConfig>()
             .await
@@ -339,18 +1516,87 @@ impl ApiClient {
             .json(config)
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("PUT /v1/config failed")?
             .json::<crate::config::AgentConfig>()
             .await
             .context("Failed to decode updated config")
     }
 
+    pub async fn get_config_flags(&self) -> Result<ConfigFlags> {
+        self.request(reqwest::Method::GET, "/v1/config/flags")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/config/flags failed")?
+            .json::<ConfigFlags>()
+            .await
+            .context("Failed to decode config flags")
+    }
+
+    pub async fn set_config_flags(&self, flags: &ConfigFlags) -> Result<ConfigFlags> {
+        self.request(reqwest::Method::PUT, "/v1/config/flags")
+            .json(flags)
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("PUT /v1/config/flags failed")?
+            .json::<ConfigFlags>()
+            .await
+            .context("Failed to decode config flags")
+    }
+
+    /// Recorded config edits, newest first.
+    pub async fn config_history(&self) -> Result<Vec<ConfigChange>> {
+        self.request(reqwest::Method::GET, "/v1/config/history")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/config/history failed")?
+            .json::<Vec<ConfigChange>>()
+            .await
+            .context("Failed to decode config history")
+    }
+
+    /// Config proposals still waiting on the operator.
+    pub async fn list_config_proposals(&self) -> Result<Vec<ConfigProposal>> {
+        self.request(reqwest::Method::GET, "/v1/config/proposals")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/config/proposals failed")?
+            .json::<Vec<ConfigProposal>>()
+            .await
+            .context("Failed to decode config proposals")
+    }
+
+    /// Approve (apply) or reject a pending config proposal.
+    pub async fn resolve_config_proposal(&self, id: &str, approve: bool) -> Result<()> {
+        let path = format!(
+            "/v1/config/proposals/{}/{}",
+            path_segment(id)?,
+            if approve { "approve" } else { "reject" }
+        );
+        self.request(reqwest::Method::POST, &path)
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .with_context(|| format!("POST {} failed", path))?;
+        Ok(())
+    }
+
     pub async fn list_plugins(&self) -> Result<Vec<PluginManifest>> {
         self.request(reqwest::Method::GET, "/v1/plugins")
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("GET /v1/plugins failed")?
             .json::<Vec<PluginManifest>>()
             .await
@@ -362,7 +1608,8 @@ impl ApiClient {
         self.request(reqwest::Method::GET, "/v1/plugins/status")
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("GET /v1/plugins/status failed")?
             .json::<Vec<PluginRuntimeStatus>>()
             .await
@@ -375,7 +1622,8 @@ impl ApiClient {
             .query(&[("limit", limit)])
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("GET /v1/conversations failed")?;
 
         let body = response
@@ -388,6 +1636,37 @@ impl ApiClient {
         ))
     }
 
+    /// One conversation by id, archived or not; `ApiError::NotFound` once deleted.
+    pub async fn get_conversation(&self, conversation_id: &str) -> Result<ChatConversation> {
+        self.request(
+            reqwest::Method::GET,
+            &format!("/v1/conversations/{}", path_segment(conversation_id)?),
+        )
+        .send()
+        .await?
+        .ensure_success()
+        .await
+        .with_context(|| format!("GET /v1/conversations/{} failed", conversation_id))?
+        .json::<ChatConversation>()
+        .await
+        .context("Failed to decode conversation")
+    }
+
+    /// Archived conversations only, most recently archived first.
+    pub async fn list_archived_conversations(&self, limit: usize) -> Result<Vec<ChatConversation>> {
+        self.request(reqwest::Method::GET, "/v1/conversations")
+            .query(&[("archived", "only")])
+            .query(&[("limit", limit)])
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/conversations?archived=only failed")?
+            .json::<Vec<ChatConversation>>()
+            .await
+            .context("Failed to decode archived conversation list")
+    }
+
     pub async fn create_conversation(&self, title: Option<&str>) -> Result<ChatConversation> {
         #[derive(Serialize)]
         struct CreateConversationRequest<'a> {
@@ -398,42 +1677,212 @@ impl ApiClient {
             .json(&CreateConversationRequest { title })
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("POST /v1/conversations failed")?
             .json::<ChatConversation>()
             .await
             .context("Failed to decode created conversation")
     }
 
+    /// Creates a conversation pre-filled with the turns from an exported chat
+    /// (see `parse_conversation_import`). Imported turns are stored as history
+    /// only; the agent does not reply to them.
+    pub async fn import_conversation(&self, json: &str) -> Result<ImportedConversation> {
+        let import = parse_conversation_import(json)?;
+
+        #[derive(Serialize)]
+        struct ImportConversationRequest<'a> {
+            title: Option<&'a str>,
+            messages: &'a [ImportedMessage],
+        }
+
+        let conversation = self
+            .request(reqwest::Method::POST, "/v1/conversations/import")
+            .json(&ImportConversationRequest {
+                title: import.title.as_deref(),
+                messages: &import.messages,
+            })
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("POST /v1/conversations/import failed")?
+            .json::<ChatConversation>()
+            .await
+            .context("Failed to decode imported conversation")?;
+        Ok(ImportedConversation {
+            conversation,
+            imported: import.messages.len(),
+            skipped: import.skipped,
+        })
+    }
+
+    /// Summarizes the whole history with a tool-free LLM pass, chunking it when
+    /// it exceeds the context window. Nothing is written unless `save_to_journal`.
+    pub async fn summarize_conversation(
+        &self,
+        conversation_id: &str,
+        save_to_journal: bool,
+    ) -> Result<ConversationRecap> {
+        #[derive(Serialize)]
+        struct SummarizeConversationRequest {
+            save_to_journal: bool,
+        }
+
+        self.request(
+            reqwest::Method::POST,
+            &format!(
+                "/v1/conversations/{}/summarize",
+                path_segment(conversation_id)?
+            ),
+        )
+        .timeout(SUMMARIZE_TIMEOUT)
+        .json(&SummarizeConversationRequest { save_to_journal })
+        .send()
+        .await?
+        .ensure_success()
+        .await
+        .with_context(|| {
+            format!(
+                "POST /v1/conversations/{}/summarize failed",
+                conversation_id
+            )
+        })?
+        .json::<ConversationRecap>()
+        .await
+        .context("Failed to decode conversation summary")
+    }
+
+    /// Creates a new conversation holding a copy of `conversation_id`'s history
+    /// up to and including `at_message_id`. The source is left untouched.
+    pub async fn fork_conversation(
+        &self,
+        conversation_id: &str,
+        at_message_id: &str,
+    ) -> Result<ChatConversation> {
+        #[derive(Serialize)]
+        struct ForkConversationRequest<'a> {
+            at_message_id: &'a str,
+        }
+
+        self.request(
+            reqwest::Method::POST,
+            &format!("/v1/conversations/{}/fork", path_segment(conversation_id)?),
+        )
+        .json(&ForkConversationRequest { at_message_id })
+        .send()
+        .await?
+        .ensure_success()
+        .await
+        .with_context(|| format!("POST /v1/conversations/{}/fork failed", conversation_id))?
+        .json::<ChatConversation>()
+        .await
+        .context("Failed to decode forked conversation")
+    }
+
     pub async fn delete_conversation(&self, conversation_id: &str) -> Result<()> {
         self.request(
             reqwest::Method::DELETE,
-            &format!("/v1/conversations/{}", conversation_id),
+            &format!("/v1/conversations/{}", path_segment(conversation_id)?),
         )
         .send()
         .await?
-        .error_for_status()
+        .ensure_success()
+        .await
         .with_context(|| format!("DELETE /v1/conversations/{} failed", conversation_id))?;
         Ok(())
     }
 
+    /// Persists group membership and order for the given conversations in one call.
+    pub async fn reorder_conversations(&self, placements: &[ConversationPlacement]) -> Result<()> {
+        #[derive(Serialize)]
+        struct ReorderConversationsRequest<'a> {
+            placements: &'a [ConversationPlacement],
+        }
+
+        self.request(reqwest::Method::PUT, "/v1/conversations/order")
+            .json(&ReorderConversationsRequest { placements })
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("PUT /v1/conversations/order failed")?;
+        Ok(())
+    }
+
     pub async fn update_conversation_title(
         &self,
         conversation_id: &str,
         title: &str,
     ) -> Result<ChatConversation> {
-        #[derive(Serialize)]
-        struct UpdateConversationRequest<'a> {
-            title: &'a str,
-        }
+        let request = UpdateConversationRequest {
+            title: Some(title.to_string()),
+            ..Default::default()
+        };
+        self.update_conversation(conversation_id, &request).await
+    }
+
+    pub async fn set_conversation_tool_mode(
+        &self,
+        conversation_id: &str,
+        tool_mode: ToolMode,
+    ) -> Result<ChatConversation> {
+        let request = UpdateConversationRequest {
+            tool_mode: Some(tool_mode),
+            ..Default::default()
+        };
+        self.update_conversation(conversation_id, &request).await
+    }
+
+    pub async fn set_conversation_metadata(
+        &self,
+        conversation_id: &str,
+        tags: &[String],
+        note: Option<&str>,
+        system_prompt_prefix: Option<&str>,
+        seed: Option<u64>,
+        pinned: bool,
+    ) -> Result<ChatConversation> {
+        let request = UpdateConversationRequest {
+            tags: Some(tags.to_vec()),
+            note: Some(note.unwrap_or_default().to_string()),
+            system_prompt_prefix: Some(system_prompt_prefix.unwrap_or_default().to_string()),
+            seed: Some(seed),
+            pinned: Some(pinned),
+            ..Default::default()
+        };
+        self.update_conversation(conversation_id, &request).await
+    }
+
+    /// Archive (`true`) or restore (`false`) a conversation. Archived ones drop
+    /// out of `list_conversations` but keep their messages.
+    pub async fn set_conversation_archived(
+        &self,
+        conversation_id: &str,
+        archived: bool,
+    ) -> Result<ChatConversation> {
+        let request = UpdateConversationRequest {
+            archived: Some(archived),
+            ..Default::default()
+        };
+        self.update_conversation(conversation_id, &request).await
+    }
+
+    pub async fn update_conversation(
+        &self,
+        conversation_id: &str,
+        request: &UpdateConversationRequest,
+    ) -> Result<ChatConversation> {
         self.request(
             reqwest::Method::PATCH,
-            &format!("/v1/conversations/{}", conversation_id),
+            &format!("/v1/conversations/{}", path_segment(conversation_id)?),
         )
-        .json(&UpdateConversationRequest { title })
+        .json(request)
         .send()
         .await?
-        .error_for_status()
+        .ensure_success()
+        .await
         .with_context(|| format!("PATCH /v1/conversations/{} failed", conversation_id))?
         .json::<ChatConversation>()
         .await
@@ -447,12 +1896,16 @@ impl ApiClient {
     ) -> Result<Vec<ChatMessage>> {
         self.request(
             reqwest::Method::GET,
-            &format!("/v1/conversations/{}/messages", conversation_id),
+            &format!(
+                "/v1/conversations/{}/messages",
+                path_segment(conversation_id)?
+            ),
         )
         .query(&[("limit", limit)])
         .send()
         .await?
-        .error_for_status()
+        .ensure_success()
+        .await
         .with_context(|| format!("GET /v1/conversations/{}/messages failed", conversation_id))?
         .json::<Vec<ChatMessage>>()
         .await
@@ -468,12 +1921,16 @@ impl ApiClient {
         let response = self
             .request(
                 reqwest::Method::POST,
-                &format!("/v1/conversations/{}/messages", conversation_id),
+                &format!(
+                    "/v1/conversations/{}/messages",
+                    path_segment(conversation_id)?
+                ),
             )
             .json(&SendMessageRequest { content })
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .with_context(|| format!("POST /v1/conversations/{}/messages failed", conversation_id))?
             .json::<SendMessageResponse>()
             .await
@@ -482,15 +1939,195 @@ impl ApiClient {
         Ok(response.message_id)
     }
 
+    pub async fn continue_message(
+        &self,
+        conversation_id: &str,
+        message_id: &str,
+    ) -> Result<String> {
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                &format!(
+                    "/v1/conversations/{}/messages/{}/continue",
+                    path_segment(conversation_id)?,
+                    path_segment(message_id)?
+                ),
+            )
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .with_context(|| {
+                format!(
+                    "POST /v1/conversations/{}/messages/{}/continue failed",
+                    conversation_id, message_id
+                )
+            })?
+            .json::<SendMessageResponse>()
+            .await
+            .context("Failed to decode continue message response")?;
+
+        Ok(response.message_id)
+    }
+
+    pub async fn get_storage_info(&self) -> Result<StorageInfo> {
+        self.request(reqwest::Method::GET, "/v1/system/storage")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/system/storage failed")?
+            .json::<StorageInfo>()
+            .await
+            .context("Failed to decode storage info")
+    }
+
+    pub async fn get_retention(&self) -> Result<RetentionStatus> {
+        self.request(reqwest::Method::GET, "/v1/system/retention")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/system/retention failed")?
+            .json::<RetentionStatus>()
+            .await
+            .context("Failed to decode retention policy")
+    }
+
+    pub async fn set_retention(&self, policy: &RetentionPolicy) -> Result<RetentionStatus> {
+        self.request(reqwest::Method::PUT, "/v1/system/retention")
+            .json(policy)
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("PUT /v1/system/retention failed")?
+            .json::<RetentionStatus>()
+            .await
+            .context("Failed to decode retention policy")
+    }
+
+    /// Runs a pruning pass now instead of waiting for the periodic task.
+    pub async fn prune_now(&self) -> Result<PruneReport> {
+        self.request(reqwest::Method::POST, "/v1/system/retention/prune")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("POST /v1/system/retention/prune failed")?
+            .json::<PruneReport>()
+            .await
+            .context("Failed to decode prune report")
+    }
+
+    /// Factory reset: clears conversations, journal, concerns, and working memory
+    /// while keeping config and character. The backend requires the literal
+    /// confirmation phrase so a stray request cannot wipe state.
+    pub async fn reset_agent(&self) -> Result<()> {
+        self.request(reqwest::Method::POST, "/v1/agent/reset")
+            .json(&serde_json::json!({ "confirm": AGENT_RESET_CONFIRMATION }))
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("POST /v1/agent/reset failed")?;
+        Ok(())
+    }
+
+    /// Downloads a consistent snapshot of the agent database (taken by the backend
+    /// with SQLite's online backup API) and writes it to `dest`. Returns bytes written.
+    pub async fn export_database(&self, dest: &std::path::Path) -> Result<u64> {
+        let bytes = self
+            .request(reqwest::Method::GET, "/v1/system/backup")
+            .timeout(DATABASE_TRANSFER_TIMEOUT)
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/system/backup failed")?
+            .bytes()
+            .await
+            .context("Failed to read database backup")?;
+        if !bytes.starts_with(SQLITE_HEADER) {
+            anyhow::bail!("Backend returned something that is not a SQLite database");
+        }
+        tokio::fs::write(dest, &bytes)
+            .await
+            .with_context(|| format!("Failed to write backup to {}", dest.display()))?;
+        Ok(bytes.len() as u64)
+    }
+
+    /// Replaces the backend's database with the snapshot at `source`.
+    pub async fn restore_database(&self, source: &std::path::Path) -> Result<()> {
+        let bytes = tokio::fs::read(source)
+            .await
+            .with_context(|| format!("Failed to read {}", source.display()))?;
+        if !bytes.starts_with(SQLITE_HEADER) {
+            anyhow::bail!("{} is not a SQLite database", source.display());
+        }
+        self.request(reqwest::Method::POST, "/v1/system/restore")
+            .timeout(DATABASE_TRANSFER_TIMEOUT)
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(bytes)
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("POST /v1/system/restore failed")?;
+        Ok(())
+    }
+
+    pub async fn compare_models(&self, request: &CompareModelsRequest) -> Result<ModelComparison> {
+        self.request(reqwest::Method::POST, "/v1/debug/compare-models")
+            .timeout(MODEL_COMPARISON_TIMEOUT)
+            .json(request)
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("POST /v1/debug/compare-models failed")?
+            .json::<ModelComparison>()
+            .await
+            .context("Failed to decode model comparison")
+    }
+
+    pub async fn explain_action(&self, action_id: &str) -> Result<ActionExplanation> {
+        self.request(
+            reqwest::Method::GET,
+            &format!("/v1/actions/{}/explanation", path_segment(action_id)?),
+        )
+        .send()
+        .await?
+        .ensure_success()
+        .await
+        .context("GET /v1/actions/:id/explanation failed")?
+        .json::<ActionExplanation>()
+        .await
+        .context("Failed to decode action explanation")
+    }
+
+    pub async fn get_last_llm_request(&self) -> Result<LlmRequestCapture> {
+        self.request(reqwest::Method::GET, "/v1/debug/last-llm-request")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/debug/last-llm-request failed")?
+            .json::<LlmRequestCapture>()
+            .await
+            .context("Failed to decode last LLM request")
+    }
+
     pub async fn get_turn_prompt(&self, turn_id: &str) -> Result<ChatTurnPrompt> {
         let response = self
             .request(
                 reqwest::Method::GET,
-                &format!("/v1/turns/{}/prompt", turn_id),
+                &format!("/v1/turns/{}/prompt", path_segment(turn_id)?),
             )
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .with_context(|| format!("GET /v1/turns/{}/prompt failed", turn_id))?
             .json::<ChatTurnPromptResponse>()
             .await
@@ -500,15 +2137,201 @@ impl ApiClient {
             turn_id: response.turn_id,
             prompt_text: response.prompt_text,
             system_prompt_text: response.system_prompt_text,
+            seed: response.seed,
+            timing: response.timing,
         })
     }
 
+    /// Set or clear (`None`) the operator's rating of a turn.
+    pub async fn rate_turn(&self, turn_id: &str, rating: Option<TurnRating>) -> Result<()> {
+        let path = format!("/v1/turns/{}/rating", path_segment(turn_id)?);
+        self.request(reqwest::Method::PUT, &path)
+            .json(&serde_json::json!({ "rating": rating }))
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .with_context(|| format!("PUT {} failed", path))?;
+        Ok(())
+    }
+
+    /// Answer a tool's mid-turn question; the backend injects `answer` as that
+    /// tool call's result and resumes the loop.
+    pub async fn provide_tool_input(
+        &self,
+        turn_id: &str,
+        tool_call_id: &str,
+        answer: &str,
+    ) -> Result<()> {
+        let path = format!(
+            "/v1/turns/{}/tool-calls/{}/input",
+            path_segment(turn_id)?,
+            path_segment(tool_call_id)?
+        );
+        self.request(reqwest::Method::POST, &path)
+            .json(&serde_json::json!({ "answer": answer }))
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .with_context(|| format!("POST {} failed", path))?;
+        Ok(())
+    }
+
+    /// Resolve a paused tool batch. Approved calls run; denied ones are reported
+    /// to the model as refused. Every call in the batch needs a decision.
+    pub async fn decide_tool_batch(
+        &self,
+        turn_id: &str,
+        decisions: &[ToolCallDecision],
+    ) -> Result<()> {
+        let path = format!("/v1/turns/{}/tool-batch", path_segment(turn_id)?);
+        self.request(reqwest::Method::POST, &path)
+            .json(&serde_json::json!({ "decisions": decisions }))
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .with_context(|| format!("POST {} failed", path))?;
+        Ok(())
+    }
+
+    pub async fn get_turn_tools(&self, turn_id: &str) -> Result<Vec<ChatTurnToolCall>> {
+        self.request(
+            reqwest::Method::GET,
+            &format!("/v1/turns/{}/tool-calls", path_segment(turn_id)?),
+        )
+        .send()
+        .await?
+        .ensure_success()
+        .await
+        .with_context(|| format!("GET /v1/turns/{}/tool-calls failed", turn_id))?
+        .json::<Vec<ChatTurnToolCall>>()
+        .await
+        .context("Failed to decode turn tool calls")
+    }
+
+    pub async fn list_tools(&self) -> Result<Vec<ToolInfo>> {
+        self.request(reqwest::Method::GET, "/v1/tools")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/tools failed")?
+            .json::<Vec<ToolInfo>>()
+            .await
+            .context("Failed to decode tool list")
+    }
+
+    pub async fn set_tool_enabled(&self, tool_name: &str, enabled: bool) -> Result<ToolInfo> {
+        #[derive(Serialize)]
+        struct SetToolEnabledRequest {
+            enabled: bool,
+        }
+
+        self.request(
+            reqwest::Method::PUT,
+            &format!("/v1/tools/{}", path_segment(tool_name)?),
+        )
+        .json(&SetToolEnabledRequest { enabled })
+        .send()
+        .await?
+        .ensure_success()
+        .await
+        .with_context(|| format!("PUT /v1/tools/{} failed", tool_name))?
+        .json::<ToolInfo>()
+        .await
+        .context("Failed to decode updated tool")
+    }
+
+    pub async fn list_working_memory(&self) -> Result<Vec<WorkingMemoryEntry>> {
+        self.request(reqwest::Method::GET, "/v1/memory/working")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/memory/working failed")?
+            .json::<Vec<WorkingMemoryEntry>>()
+            .await
+            .context("Failed to decode working memory")
+    }
+
+    pub async fn list_intentions(&self) -> Result<Vec<UpcomingItem>> {
+        self.request(reqwest::Method::GET, "/v1/agent/upcoming")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("GET /v1/agent/upcoming failed")?
+            .json::<Vec<UpcomingItem>>()
+            .await
+            .context("Failed to decode upcoming intentions")
+    }
+
+    /// Forgets one entry. Keys are only unique within a scope, so `scope` is
+    /// the entry's conversation id, or `None` for a global entry.
+    pub async fn delete_working_memory_entry(&self, key: &str, scope: Option<&str>) -> Result<()> {
+        let mut request = self.request(
+            reqwest::Method::DELETE,
+            &format!("/v1/memory/working/{}", path_segment(key)?),
+        );
+        if let Some(conversation_id) = scope {
+            request = request.query(&[("conversation_id", conversation_id)]);
+        }
+        request
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .with_context(|| format!("DELETE /v1/memory/working/{} failed", key))?;
+        Ok(())
+    }
+
+    /// Pin `text` into working memory. `scope` is a conversation id, or `None` for global.
+    pub async fn remember(&self, text: &str, scope: Option<&str>) -> Result<WorkingMemoryEntry> {
+        let body = RememberRequest {
+            content: text.to_string(),
+            conversation_id: scope.map(str::to_string),
+        };
+        self.request(reqwest::Method::POST, "/v1/memory/working")
+            .json(&body)
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("POST /v1/memory/working failed")?
+            .json::<WorkingMemoryEntry>()
+            .await
+            .context("Failed to decode remembered entry")
+    }
+
+    /// Clears every working-memory entry and returns how many were removed.
+    pub async fn clear_working_memory(&self) -> Result<usize> {
+        #[derive(Deserialize)]
+        struct ClearWorkingMemoryResponse {
+            cleared: usize,
+        }
+
+        let response = self
+            .request(reqwest::Method::DELETE, "/v1/memory/working")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("DELETE /v1/memory/working failed")?
+            .json::<ClearWorkingMemoryResponse>()
+            .await
+            .context("Failed to decode working memory clear response")?;
+        Ok(response.cleared)
+    }
+
     pub async fn list_scheduled_jobs(&self, limit: usize) -> Result<Vec<ScheduledJob>> {
         self.request(reqwest::Method::GET, "/v1/scheduled-jobs")
             .query(&[("limit", limit)])
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("GET /v1/scheduled-jobs failed")?
             .json::<Vec<ScheduledJob>>()
             .await
@@ -536,7 +2359,8 @@ impl ApiClient {
             })
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("POST /v1/scheduled-jobs failed")?
             .json::<ScheduledJob>()
             .await
@@ -550,12 +2374,13 @@ impl ApiClient {
     ) -> Result<ScheduledJob> {
         self.request(
             reqwest::Method::PUT,
-            &format!("/v1/scheduled-jobs/{}", job_id),
+            &format!("/v1/scheduled-jobs/{}", path_segment(job_id)?),
         )
         .json(request)
         .send()
         .await?
-        .error_for_status()
+        .ensure_success()
+        .await
         .with_context(|| format!("PUT /v1/scheduled-jobs/{} failed", job_id))?
         .json::<ScheduledJob>()
         .await
@@ -565,11 +2390,12 @@ impl ApiClient {
     pub async fn delete_scheduled_job(&self, job_id: &str) -> Result<()> {
         self.request(
             reqwest::Method::DELETE,
-            &format!("/v1/scheduled-jobs/{}", job_id),
+            &format!("/v1/scheduled-jobs/{}", path_segment(job_id)?),
         )
         .send()
         .await?
-        .error_for_status()
+        .ensure_success()
+        .await
         .with_context(|| format!("DELETE /v1/scheduled-jobs/{} failed", job_id))?;
         Ok(())
     }
@@ -578,19 +2404,37 @@ impl ApiClient {
         self.request(reqwest::Method::GET, "/v1/agent/status")
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("GET /v1/agent/status failed")?
             .json::<AgentRuntimeStatus>()
             .await
             .context("Failed to decode agent status")
     }
 
+    /// Pauses or resumes only autonomous cycles; chat keeps responding.
+    pub async fn set_autonomy_paused(&self, paused: bool) -> Result<bool> {
+        let response = self
+            .request(reqwest::Method::PUT, "/v1/agent/pause")
+            .json(&serde_json::json!({ "paused": paused, "scope": "autonomy" }))
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("PUT /v1/agent/pause (autonomy) failed")?
+            .json::<PauseStateResponse>()
+            .await
+            .context("Failed to decode autonomy pause response")?;
+        Ok(response.paused)
+    }
+
     pub async fn toggle_pause(&self) -> Result<bool> {
         let response = self
             .request(reqwest::Method::POST, "/v1/agent/toggle-pause")
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("POST /v1/agent/toggle-pause failed")?
             .json::<PauseStateResponse>()
             .await
@@ -598,13 +2442,46 @@ impl ApiClient {
         Ok(response.paused)
     }
 
+    /// Starts an out-of-band autonomous cycle. Returns `false` when a cycle was
+    /// already running and the request was ignored.
+    pub async fn trigger_cycle(&self) -> Result<bool> {
+        let response = self
+            .request(reqwest::Method::POST, "/v1/agent/trigger-cycle")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("POST /v1/agent/trigger-cycle failed")?
+            .json::<TriggerCycleResponse>()
+            .await
+            .context("Failed to decode trigger cycle response")?;
+        Ok(response.started)
+    }
+
+    /// Tells the backend a desktop window just opened. The backend decides
+    /// (config toggle + rate limit) whether to greet; returns whether it will.
+    pub async fn notify_session_start(&self) -> Result<bool> {
+        let response = self
+            .request(reqwest::Method::POST, "/v1/presence/session-start")
+            .send()
+            .await?
+            .ensure_success()
+            .await
+            .context("POST /v1/presence/session-start failed")?
+            .json::<SessionStartResponse>()
+            .await
+            .context("Failed to decode session start response")?;
+        Ok(response.greeting_queued)
+    }
+
     pub async fn set_loose_mode(&self, enabled: bool) -> Result<bool> {
         let response = self
             .request(reqwest::Method::PUT, "/v1/agent/loose-mode")
             .json(&serde_json::json!({ "enabled": enabled }))
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("PUT /v1/agent/loose-mode failed")?
             .json::<LooseModeResponse>()
             .await
@@ -616,11 +2493,12 @@ impl ApiClient {
     pub async fn approve_tool(&self, tool_name: &str) -> Result<()> {
         self.request(
             reqwest::Method::POST,
-            &format!("/v1/agent/tools/{}/approve", tool_name),
+            &format!("/v1/agent/tools/{}/approve", path_segment(tool_name)?),
         )
         .send()
         .await?
-        .error_for_status()
+        .ensure_success()
+        .await
         .with_context(|| format!("POST /v1/agent/tools/{}/approve failed", tool_name))?;
         Ok(())
     }
@@ -630,7 +2508,8 @@ impl ApiClient {
             .request(reqwest::Method::POST, "/v1/agent/stop")
             .send()
             .await?
-            .error_for_status()
+            .ensure_success()
+            .await
             .context("POST /v1/agent/stop failed")?
             .json::<StopResponse>()
             .await
@@ -638,9 +2517,10 @@ impl ApiClient {
         Ok(response.stopped)
     }
 
-    pub async fn stream_events_forever(self, tx: Sender<FrontendEvent>) {
+    pub async fn stream_events_forever(self, tx: EventSink) {
+        let mut cursor = EventCursor::default();
         loop {
-            match self.stream_events_once(&tx).await {
+            match self.stream_events_once(&tx, &mut cursor).await {
                 Ok(()) => {
                     tracing::info!("Event stream disconnected; reconnecting in 1s");
                 }
@@ -652,13 +2532,23 @@ impl ApiClient {
         }
     }
 
-    async fn stream_events_once(&self, tx: &Sender<FrontendEvent>) -> Result<()> {
+    async fn stream_events_once(&self, tx: &EventSink, cursor: &mut EventCursor) -> Result<()> {
         let ws_endpoint = format!("{}/v1/ws/events", self.ws_url);
         let mut request = ws_endpoint
             .into_client_request()
             .context("Invalid websocket endpoint URL")?;
 
-        if let Some(token) = self.token.as_deref() {
+        if let (Some(stream_id), Some(last_event_id)) =
+            (cursor.stream_id.as_deref(), cursor.last_event_id)
+        {
+            let headers = request.headers_mut();
+            headers.insert("Last-Event-ID", WsHeaderValue::from(last_event_id));
+            if let Ok(value) = WsHeaderValue::from_str(stream_id) {
+                headers.insert("X-Ponderer-Stream-Id", value);
+            }
+        }
+
+        if let Some(token) = self.current_token() {
             let value = WsHeaderValue::from_str(&format!("Bearer {}", token))
                 .context("Invalid bearer token for websocket auth")?;
             request
@@ -671,17 +2561,19 @@ impl ApiClient {
             .context("Failed to connect websocket event stream")?;
         let (_write, mut read) = stream.split();
 
-        while let Some(message) = read.next().await {
+        // Any frame (including backend `heartbeat` envelopes and WS pings) resets
+        // the deadline; silence beyond it means a proxy dropped the connection.
+        while let Some(message) = next_within_heartbeat(&mut read, self.heartbeat_timeout).await? {
             match message.context("Websocket read error")? {
                 Message::Text(text) => {
-                    if let Some(event) = parse_event_envelope(&text)? {
-                        let _ = tx.send(event);
+                    if let Some(event) = parse_event_envelope(&text, cursor)? {
+                        tx.push(event).await;
                     }
                 }
                 Message::Binary(bytes) => {
                     if let Ok(text) = String::from_utf8(bytes.to_vec()) {
-                        if let Some(event) = parse_event_envelope(&text)? {
-                            let _ = tx.send(event);
+                        if let Some(event) = parse_event_envelope(&text, cursor)? {
+                            tx.push(event).await;
                         }
                     }
                 }
@@ -698,17 +2590,75 @@ impl ApiClient {
     fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
         let url = format!("{}{}", self.base_url, path);
         let mut builder = self.http.request(method, url);
-        if let Some(token) = self.token.as_deref() {
+        if let Some(token) = self.current_token() {
             builder = builder.bearer_auth(token);
         }
         builder
     }
 }
 
-fn parse_event_envelope(text: &str) -> Result<Option<FrontendEvent>> {
-    let envelope: ApiEventEnvelope =
+/// Percent-encodes one id, key or name for use as a request path segment.
+/// Dot segments are refused because URL parsers collapse even `%2e%2e`.
+fn path_segment(value: &str) -> Result<String> {
+    if value.is_empty() || value == "." || value == ".." {
+        anyhow::bail!("'{}' is not a valid path segment", value);
+    }
+    Ok(utf8_percent_encode(value, PATH_SEGMENT).to_string())
+}
+
+async fn next_within_heartbeat<S>(stream: &mut S, timeout: Duration) -> Result<Option<S::Item>>
+where
+    S: futures_util::Stream + Unpin,
+{
+    match tokio::time::timeout(timeout, stream.next()).await {
+        Ok(item) => Ok(item),
+        Err(_) => anyhow::bail!(
+            "No event-stream heartbeat within {}s; treating connection as dropped",
+            timeout.as_secs()
+        ),
+    }
+}
+
+fn build_http_client(timeout: Duration, bypass_proxy: bool) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder().timeout(timeout);
+    if bypass_proxy {
+        builder = builder.no_proxy();
+    }
+    builder.build().unwrap_or_else(|error| {
+        tracing::warn!("Failed to build bounded API client: {}", error);
+        reqwest::Client::builder()
+            .no_proxy()
+            .timeout(timeout)
+            .build()
+            .expect("bounded no-proxy API client")
+    })
+}
+
+fn request_timeout_from_env() -> Duration {
+    let secs = std::env::var("PONDERER_API_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS);
+    Duration::from_secs(secs)
+}
+
+fn event_heartbeat_timeout_from_env() -> Duration {
+    let secs = std::env::var("PONDERER_EVENT_HEARTBEAT_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_EVENT_HEARTBEAT_TIMEOUT_SECS);
+    Duration::from_secs(secs)
+}
+
+fn parse_event_envelope(text: &str, cursor: &mut EventCursor) -> Result<Option<FrontendEvent>> {
+    let sequenced: SequencedEventEnvelope =
         serde_json::from_str(text).context("Failed to decode API event envelope")?;
-    Ok(map_event(envelope))
+    if !cursor.accept(sequenced.stream_id.as_deref(), sequenced.event_id) {
+        return Ok(None);
+    }
+    Ok(map_event(sequenced.envelope))
 }
 
 fn string_field(payload: &Value, key: &str) -> String {
@@ -723,6 +2673,13 @@ fn optional_string_field(payload: &Value, key: &str) -> Option<String> {
     payload.get(key).and_then(Value::as_str).map(str::to_string)
 }
 
+fn optional_u32_field(payload: &Value, key: &str) -> Option<u32> {
+    payload
+        .get(key)
+        .and_then(Value::as_u64)
+        .and_then(|value| u32::try_from(value).ok())
+}
+
 fn map_event(envelope: ApiEventEnvelope) -> Option<FrontendEvent> {
     match envelope.event_type.as_str() {
         "state_changed" => {
@@ -770,6 +2727,23 @@ fn map_event(envelope: ApiEventEnvelope) -> Option<FrontendEvent> {
                 .and_then(Value::as_str)
                 .unwrap_or_default()
                 .to_string(),
+            iteration: optional_u32_field(&envelope.payload, "iteration"),
+            max_iterations: optional_u32_field(&envelope.payload, "max_iterations"),
+            tool_call_id: envelope
+                .payload
+                .get("tool_call_id")
+                .and_then(Value::as_str)
+                .map(ToString::to_string),
+            partial: envelope
+                .payload
+                .get("partial")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            arguments: envelope
+                .payload
+                .get("arguments")
+                .filter(|arguments| !arguments.is_null())
+                .cloned(),
         }),
         "chat_streaming" => Some(FrontendEvent::ChatStreaming {
             conversation_id: envelope
@@ -825,6 +2799,7 @@ fn map_event(envelope: ApiEventEnvelope) -> Option<FrontendEvent> {
                 .and_then(Value::as_str)
                 .unwrap_or_default()
                 .to_string(),
+            action_id: optional_string_field(&envelope.payload, "action_id"),
         }),
         "orientation_update" => {
             let disposition = envelope
@@ -917,72 +2892,1294 @@ fn map_event(envelope: ApiEventEnvelope) -> Option<FrontendEvent> {
                 .unwrap_or("cycle")
                 .to_string(),
         }),
+        "agent_reset" => Some(FrontendEvent::AgentReset),
+        "events_dropped" => Some(FrontendEvent::Error(format!(
+            "Missed {} events while the event stream was disconnected",
+            envelope
+                .payload
+                .get("count")
+                .and_then(Value::as_u64)
+                .unwrap_or_default()
+        ))),
+        "conversation_compacted" => Some(FrontendEvent::ConversationCompacted {
+            conversation_id: envelope
+                .payload
+                .get("conversation_id")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            summary: envelope
+                .payload
+                .get("summary")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            through_message_id: envelope
+                .payload
+                .get("through_message_id")
+                .and_then(Value::as_str)
+                .map(ToString::to_string),
+        }),
+        "iteration_limit_hit" => Some(FrontendEvent::IterationLimitHit {
+            conversation_id: envelope
+                .payload
+                .get("conversation_id")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            max_iterations: optional_u32_field(&envelope.payload, "max_iterations"),
+            mid_progress: envelope
+                .payload
+                .get("mid_progress")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        }),
+        "config_proposal" => serde_json::from_value(envelope.payload)
+            .map(FrontendEvent::ConfigProposed)
+            .map_err(|error| tracing::warn!("Malformed config proposal: {}", error))
+            .ok(),
+        "tool_batch_pending" => serde_json::from_value(envelope.payload)
+            .map(FrontendEvent::ToolBatchPending)
+            .map_err(|error| tracing::warn!("Malformed tool batch: {}", error))
+            .ok(),
+        "tool_input_request" => Some(FrontendEvent::ToolInputRequested {
+            conversation_id: string_field(&envelope.payload, "conversation_id"),
+            turn_id: string_field(&envelope.payload, "turn_id"),
+            tool_call_id: string_field(&envelope.payload, "tool_call_id"),
+            tool_name: string_field(&envelope.payload, "tool_name"),
+            prompt: string_field(&envelope.payload, "prompt"),
+        }),
+        // Keepalive only; receiving it already reset the stream deadline.
+        "heartbeat" => None,
+        _ => None,
+    }
+}
+
+fn parse_visual_state(raw: &str) -> Option<AgentVisualState> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "idle" => Some(AgentVisualState::Idle),
+        "reading" => Some(AgentVisualState::Reading),
+        "thinking" => Some(AgentVisualState::Thinking),
+        "writing" => Some(AgentVisualState::Writing),
+        "happy" => Some(AgentVisualState::Happy),
+        "confused" => Some(AgentVisualState::Confused),
+        "paused" => Some(AgentVisualState::Paused),
+        "waiting_for_user" => Some(AgentVisualState::WaitingForUser),
         _ => None,
     }
 }
 
-fn parse_visual_state(raw: &str) -> Option<AgentVisualState> {
-    match raw.trim().to_ascii_lowercase().as_str() {
-        "idle" => Some(AgentVisualState::Idle),
-        "reading" => Some(AgentVisualState::Reading),
-        "thinking" => Some(AgentVisualState::Thinking),
-        "writing" => Some(AgentVisualState::Writing),
-        "happy" => Some(AgentVisualState::Happy),
-        "confused" => Some(AgentVisualState::Confused),
-        "paused" => Some(AgentVisualState::Paused),
-        _ => None,
+fn json_value_to_short_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "null".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn normalize_base_url(raw: &str) -> String {
+    let trimmed = raw.trim().trim_end_matches('/');
+    if trimmed.is_empty() {
+        "http://127.0.0.1:8787".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn normalize_ws_url(base_http_url: &str) -> String {
+    if let Some(rest) = base_http_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = base_http_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        format!("ws://{}", base_http_url)
+    }
+}
+
+fn validate_health_status(status: &str) -> Result<()> {
+    match status {
+        "ok" | "degraded" => Ok(()),
+        other => anyhow::bail!("Backend reported unrecognized health status '{other}'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_visual_state_case_insensitive() {
+        assert!(matches!(
+            parse_visual_state("Thinking"),
+            Some(AgentVisualState::Thinking)
+        ));
+        assert!(matches!(
+            parse_visual_state("paused"),
+            Some(AgentVisualState::Paused)
+        ));
+        assert!(parse_visual_state("unknown").is_none());
+    }
+
+    #[test]
+    fn path_segment_escapes_reserved_characters_and_refuses_dot_segments() {
+        assert_eq!(path_segment("conv-1_a.b~c").unwrap(), "conv-1_a.b~c");
+        assert_eq!(path_segment("a/b?c#d e").unwrap(), "a%2Fb%3Fc%23d%20e");
+        assert_eq!(path_segment("café").unwrap(), "caf%C3%A9");
+        for bad in ["", ".", ".."] {
+            assert!(path_segment(bad).is_err(), "{:?} accepted", bad);
+        }
+    }
+
+    #[test]
+    fn chat_message_truncated_defaults_to_false() {
+        let message: ChatMessage = serde_json::from_value(serde_json::json!({
+            "id": "m1",
+            "conversation_id": "c1",
+            "role": "agent",
+            "content": "partial",
+            "created_at": "2026-02-17T05:19:24Z",
+            "processed": true,
+            "turn_id": null
+        }))
+        .expect("decode");
+        assert!(!message.truncated);
+
+        let message: ChatMessage = serde_json::from_value(serde_json::json!({
+            "id": "m2",
+            "conversation_id": "c1",
+            "role": "agent",
+            "content": "partial",
+            "created_at": "2026-02-17T05:19:24Z",
+            "processed": true,
+            "turn_id": "t1",
+            "truncated": true
+        }))
+        .expect("decode");
+        assert!(message.truncated);
+    }
+
+    #[test]
+    fn decodes_tool_list_shape() {
+        let tools: Vec<ToolInfo> = serde_json::from_value(serde_json::json!([
+            {
+                "name": "shell",
+                "description": "Run a shell command",
+                "parameters": {
+                    "type": "object",
+                    "properties": {"command": {"type": "string"}},
+                    "required": ["command"]
+                },
+                "side_effecting": true,
+                "enabled": false
+            },
+            {"name": "recall", "enabled": true}
+        ]))
+        .expect("decode");
+
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].name, "shell");
+        assert!(tools[0].side_effecting);
+        assert!(!tools[0].enabled);
+        assert_eq!(tools[0].parameters["required"][0], "command");
+        assert!(tools[1].description.is_empty());
+        assert!(tools[1].parameters.is_null());
+        assert!(!tools[1].side_effecting);
+    }
+
+    #[test]
+    fn decodes_turn_tool_call_records() {
+        let records: Vec<ChatTurnToolCall> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "tc-1",
+                "turn_id": "turn-1",
+                "tool_call_id": "call_0",
+                "tool_name": "shell",
+                "arguments": {"command": "ls"},
+                "output": "a.txt\nb.txt",
+                "created_at": "2026-02-17T05:19:24Z"
+            },
+            {"tool_name": "recall", "created_at": null}
+        ]))
+        .expect("decode");
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].arguments["command"], "ls");
+        assert_eq!(records[0].output.as_str(), Some("a.txt\nb.txt"));
+        assert!(records[1].arguments.is_null());
+        assert!(records[1].tool_call_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn missed_heartbeat_ends_the_stream_attempt() {
+        let mut silent = futures_util::stream::pending::<u8>();
+        let result = next_within_heartbeat(&mut silent, Duration::from_millis(20)).await;
+        assert!(result.is_err());
+
+        let mut chatty = futures_util::stream::iter([1u8]);
+        let result = next_within_heartbeat(&mut chatty, Duration::from_millis(20)).await;
+        assert_eq!(result.expect("within deadline"), Some(1));
+    }
+
+    /// JSON body of a request captured by `serve_once`.
+    fn request_json(request: &str) -> Value {
+        let body = request.split("\r\n\r\n").nth(1).expect("request body");
+        serde_json::from_str(body).expect("json body")
+    }
+
+    /// Serves exactly one canned HTTP response and hands back the raw request text.
+    async fn serve_once(
+        status_line: &'static str,
+        body: &'static str,
+    ) -> (std::net::SocketAddr, tokio::task::JoinHandle<String>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock backend");
+        let addr = listener.local_addr().expect("mock addr");
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("accept");
+            // Read the whole request (headers plus Content-Length body) so tests can
+            // assert on what was sent.
+            let mut buffer = Vec::new();
+            let mut chunk = [0u8; 4096];
+            loop {
+                let read = socket.read(&mut chunk).await.expect("read request");
+                buffer.extend_from_slice(&chunk[..read]);
+                let text = String::from_utf8_lossy(&buffer);
+                let Some(header_end) = text.find("\r\n\r\n") else {
+                    if read == 0 {
+                        break;
+                    }
+                    continue;
+                };
+                let content_length = text[..header_end]
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())
+                            .flatten()
+                    })
+                    .unwrap_or(0);
+                if read == 0 || buffer.len() >= header_end + 4 + content_length {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status_line,
+                body.len(),
+                body
+            );
+            socket
+                .write_all(response.as_bytes())
+                .await
+                .expect("write response");
+            String::from_utf8_lossy(&buffer).into_owned()
+        });
+        (addr, server)
+    }
+
+    #[tokio::test]
+    async fn import_conversation_maps_roles_and_skips_unknown_ones() {
+        let export = r#"{
+            "title": "From elsewhere",
+            "messages": [
+                {"role": "system", "content": "You are helpful."},
+                {"role": "user", "content": "What is a koan?"},
+                {"role": "assistant", "content": [{"type": "text", "text": "A paradox"}, {"type": "text", "text": "for meditation."}]},
+                {"role": "tool", "content": "{}"},
+                {"role": "user", "content": "  "}
+            ]
+        }"#;
+        let parsed = parse_conversation_import(export).expect("parse");
+        assert_eq!(parsed.title.as_deref(), Some("From elsewhere"));
+        assert_eq!(parsed.skipped, 3);
+        assert_eq!(
+            parsed.messages,
+            [
+                ImportedMessage {
+                    role: "operator".to_string(),
+                    content: "What is a koan?".to_string(),
+                },
+                ImportedMessage {
+                    role: "agent".to_string(),
+                    content: "A paradox\nfor meditation.".to_string(),
+                },
+            ]
+        );
+        assert!(parse_conversation_import(r#"[{"role": "system", "content": "x"}]"#).is_err());
+        assert!(parse_conversation_import("not json").is_err());
+
+        let (addr, server) = serve_once(
+            "200 OK",
+            r#"{"id":"imp1","session_id":"s1","title":"From elsewhere","created_at":"2026-01-01T00:00:00Z","updated_at":"2026-01-01T00:00:00Z","runtime_state":"idle","active_turn_id":null,"message_count":2,"last_message_at":null}"#,
+        )
+        .await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let imported = client.import_conversation(export).await.expect("import");
+        assert_eq!(imported.conversation.id, "imp1");
+        assert_eq!((imported.imported, imported.skipped), (2, 3));
+
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with("POST /v1/conversations/import HTTP/1.1"));
+        let body = request_json(&request);
+        assert_eq!(body["title"], "From elsewhere");
+        assert_eq!(body["messages"][0]["role"], "operator");
+        assert_eq!(body["messages"][1]["content"], "A paradox\nfor meditation.");
+    }
+
+    #[tokio::test]
+    async fn clear_working_memory_issues_delete_and_reports_count() {
+        let (addr, server) = serve_once("200 OK", r#"{"cleared":3}"#).await;
+
+        let client = ApiClient::new_local(format!("http://{}", addr), Some("t".to_string()));
+        let cleared = client.clear_working_memory().await.expect("clear");
+        assert_eq!(cleared, 3);
+
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with("DELETE /v1/memory/working HTTP/1.1"));
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("authorization: bearer t"));
+    }
+
+    #[tokio::test]
+    async fn delete_working_memory_entry_encodes_the_key_and_sends_the_scope() {
+        let (addr, server) = serve_once("204 No Content", "").await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        client
+            .delete_working_memory_entry("notes/today", Some("conv 1"))
+            .await
+            .expect("delete");
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with(
+            "DELETE /v1/memory/working/notes%2Ftoday?conversation_id=conv+1 HTTP/1.1"
+        ));
+
+        let (addr, server) = serve_once("204 No Content", "").await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        client
+            .delete_working_memory_entry("focus", None)
+            .await
+            .expect("delete");
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with("DELETE /v1/memory/working/focus HTTP/1.1"));
+    }
+
+    #[tokio::test]
+    async fn archived_listing_and_restore_use_the_archive_flag() {
+        let (addr, server) = serve_once(
+            "200 OK",
+            r#"[{"id":"c9","session_id":"s","title":"Old trip plans","created_at":"2026-01-02T00:00:00Z","updated_at":"2026-01-05T00:00:00Z","runtime_state":"idle","active_turn_id":null,"message_count":12,"last_message_at":"2026-01-05T00:00:00Z","archived_at":"2026-04-05T00:00:00Z"}]"#,
+        )
+        .await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let archived = client.list_archived_conversations(50).await.expect("list");
+        assert_eq!(archived[0].id, "c9");
+        assert!(archived[0].archived_at.is_some());
+        assert!(!archived[0].pinned);
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with("GET /v1/conversations?archived=only&limit=50 HTTP/1.1"));
+
+        let (addr, server) = serve_once(
+            "200 OK",
+            r#"{"id":"c9","session_id":"s","title":"Old trip plans","created_at":"2026-01-02T00:00:00Z","updated_at":"2026-05-01T00:00:00Z","runtime_state":"idle","active_turn_id":null,"message_count":12,"last_message_at":"2026-01-05T00:00:00Z"}"#,
+        )
+        .await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let restored = client
+            .set_conversation_archived("c9", false)
+            .await
+            .expect("restore");
+        assert!(restored.archived_at.is_none());
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with("PATCH /v1/conversations/c9 HTTP/1.1"));
+        assert!(request.ends_with(r#"{"archived":false}"#));
+    }
+
+    #[tokio::test]
+    async fn rate_turn_puts_the_rating_and_null_to_clear() {
+        let (addr, server) = serve_once("204 No Content", "").await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        client
+            .rate_turn("t1", Some(TurnRating::Up))
+            .await
+            .expect("rate");
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with("PUT /v1/turns/t1/rating HTTP/1.1"));
+        assert!(request.ends_with(r#"{"rating":"up"}"#));
+
+        let (addr, server) = serve_once("204 No Content", "").await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        client.rate_turn("t1", None).await.expect("clear rating");
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with("PUT /v1/turns/t1/rating HTTP/1.1"));
+        assert!(request.ends_with(r#"{"rating":null}"#));
+    }
+
+    #[tokio::test]
+    async fn session_start_posts_and_reports_whether_a_greeting_is_queued() {
+        let (addr, server) = serve_once("200 OK", r#"{"greeting_queued":false}"#).await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        assert!(!client.notify_session_start().await.expect("session start"));
+        assert!(server
+            .await
+            .expect("mock server")
+            .starts_with("POST /v1/presence/session-start HTTP/1.1"));
+    }
+
+    #[tokio::test]
+    async fn compare_models_sends_both_models_and_keeps_per_model_errors() {
+        let (addr, server) = serve_once(
+            "200 OK",
+            r#"{"results":[
+                {"model":"qwen2.5:7b","response":"Hello there.","latency_ms":812,"tool_calls":["shell"]},
+                {"model":"llama3.1:8b","error":"model not found"}
+            ]}"#,
+        )
+        .await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let comparison = client
+            .compare_models(&CompareModelsRequest {
+                conversation_id: None,
+                content: "Say hi".to_string(),
+                models: vec!["qwen2.5:7b".to_string(), "llama3.1:8b".to_string()],
+            })
+            .await
+            .expect("comparison");
+
+        let [first, second] = comparison.results.as_slice() else {
+            panic!("expected two results");
+        };
+        assert_eq!(first.response, "Hello there.");
+        assert_eq!(first.tool_calls, ["shell"]);
+        assert!(first.error.is_none());
+        assert_eq!(second.error.as_deref(), Some("model not found"));
+        assert!(second.response.is_empty());
+
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with("POST /v1/debug/compare-models HTTP/1.1"));
+        let body = request_json(&request);
+        assert_eq!(
+            body,
+            serde_json::json!({"content": "Say hi", "models": ["qwen2.5:7b", "llama3.1:8b"]})
+        );
+    }
+
+    #[tokio::test]
+    async fn explain_action_fetches_reasoning_for_the_action_id() {
+        let (addr, server) = serve_once(
+            "200 OK",
+            r#"{"action_id":"a7","action":"write_journal","decision":"Reflect on the day","turn_id":"t3"}"#,
+        )
+        .await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let explanation = client.explain_action("a7").await.expect("explanation");
+        assert_eq!(explanation.decision.as_deref(), Some("Reflect on the day"));
+        assert_eq!(explanation.turn_id.as_deref(), Some("t3"));
+        assert!(explanation.orientation.is_none());
+        assert!(server
+            .await
+            .expect("mock server")
+            .starts_with("GET /v1/actions/a7/explanation HTTP/1.1"));
+
+        let envelope = ApiEventEnvelope {
+            event_type: "action_taken".to_string(),
+            payload: serde_json::json!({"action": "write_journal", "result": "ok", "action_id": "a7"}),
+        };
+        assert!(matches!(
+            map_event(envelope),
+            Some(FrontendEvent::ActionTaken { action_id: Some(id), .. }) if id == "a7"
+        ));
+    }
+
+    #[test]
+    fn working_memory_scope_defaults_to_global() {
+        let entries: Vec<WorkingMemoryEntry> = serde_json::from_value(serde_json::json!([
+            {"key": "plan", "content": "ship it"},
+            {"key": "draft", "content": "hi", "conversation_id": "c1"}
+        ]))
+        .expect("decode working memory");
+        assert_eq!(entries[0].conversation_id, None);
+        assert_eq!(entries[1].conversation_id.as_deref(), Some("c1"));
+    }
+
+    #[tokio::test]
+    async fn export_database_writes_the_snapshot_and_rejects_non_sqlite() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let dest = dir.path().join("backup.db");
+
+        let (addr, server) = serve_once("200 OK", "SQLite format 3\0rest-of-page").await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let written = client.export_database(&dest).await.expect("export");
+        assert_eq!(written, 28);
+        assert!(std::fs::read(&dest).unwrap().starts_with(SQLITE_HEADER));
+        assert!(server
+            .await
+            .expect("mock server")
+            .starts_with("GET /v1/system/backup HTTP/1.1"));
+
+        let (addr, _server) = serve_once("200 OK", "<html>proxy error</html>").await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let other = dir.path().join("other.db");
+        assert!(client.export_database(&other).await.is_err());
+        assert!(!other.exists());
+    }
+
+    #[tokio::test]
+    async fn unauthorized_response_maps_to_auth_error() {
+        let (addr, server) = serve_once("401 Unauthorized", r#"{"error":"bad token"}"#).await;
+        let client = ApiClient::new_local(format!("http://{}", addr), Some("old".to_string()));
+        client.set_token(Some("new".to_string()));
+
+        let error = client.get_agent_status().await.expect_err("401 must fail");
+        assert!(is_auth_error(&error));
+        assert_eq!(ApiError::of(&error), Some(ApiError::Auth { status: 401 }));
+
+        let request = server.await.expect("mock server");
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("authorization: bearer new"));
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retryable_but_server_errors_are() {
+        let (addr, _server) = serve_once("422 Unprocessable Entity", "{}").await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let error = client.send_message("c1", "hi").await.expect_err("422");
+        assert!(!is_retryable_error(&error));
+
+        let (addr, _server) = serve_once("503 Service Unavailable", "{}").await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let error = client.send_message("c1", "hi").await.expect_err("503");
+        assert!(is_retryable_error(&error));
+
+        let auth = anyhow::Error::from(ApiError::Auth { status: 403 }).context("POST failed");
+        assert!(!is_retryable_error(&auth));
+        assert!(is_retryable_error(&anyhow::anyhow!("connection refused")));
+    }
+
+    #[tokio::test]
+    async fn http_responses_map_to_api_error_variants() {
+        let (addr, _server) =
+            serve_once("404 Not Found", r#"{"error":"no such conversation"}"#).await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let error = client.list_messages("gone", 50).await.expect_err("404");
+        assert_eq!(
+            ApiError::of(&error),
+            Some(ApiError::NotFound {
+                body: r#"{"error":"no such conversation"}"#.to_string()
+            })
+        );
+
+        let (addr, _server) = serve_once("500 Internal Server Error", "db locked").await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let error = client.get_agent_status().await.expect_err("500");
+        assert_eq!(
+            ApiError::of(&error),
+            Some(ApiError::Server {
+                status: 500,
+                body: "db locked".to_string()
+            })
+        );
+        assert!(format!("{:#}", error).contains("db locked"));
+
+        let (addr, _server) = serve_once("200 OK", "not json").await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let error = client.get_agent_status().await.expect_err("bad body");
+        assert!(matches!(ApiError::of(&error), Some(ApiError::Decode(_))));
+        assert!(!is_retryable_error(&error));
+
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = closed.local_addr().unwrap();
+        drop(closed);
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let error = client.get_agent_status().await.expect_err("refused");
+        assert!(matches!(ApiError::of(&error), Some(ApiError::Network(_))));
+        assert!(is_retryable_error(&error));
+
+        assert_eq!(ApiError::of(&anyhow::anyhow!("disk full")), None);
+    }
+
+    #[tokio::test]
+    async fn unresponsive_backend_times_out_within_the_bound() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind silent backend");
+        let addr = listener.local_addr().expect("silent addr");
+        // Accept and hold the connection without ever answering.
+        let _server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.expect("accept");
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            drop(socket);
+        });
+
+        let client = ApiClient::new_local(format!("http://{}", addr), None)
+            .with_request_timeout(Duration::from_millis(200));
+        let started = std::time::Instant::now();
+        let error = client.get_agent_status().await.expect_err("must time out");
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(ApiError::of(&error), Some(ApiError::Timeout));
+        assert!(is_retryable_error(&error));
+    }
+
+    #[test]
+    fn event_cursor_drops_replayed_duplicates_and_resets_on_new_backend() {
+        let mut cursor = EventCursor::default();
+        assert!(cursor.accept(Some("a"), Some(1)));
+        assert!(cursor.accept(Some("a"), Some(2)));
+        assert!(!cursor.accept(Some("a"), Some(2)));
+        assert!(!cursor.accept(Some("a"), Some(1)));
+        assert!(cursor.accept(None, None), "unsequenced events always pass");
+
+        // Backend restarted: ids start over under a new stream id.
+        assert!(cursor.accept(Some("b"), Some(1)));
+        assert_eq!(cursor.last_event_id, Some(1));
+    }
+
+    #[tokio::test]
+    async fn full_event_sink_drops_streaming_updates_but_keeps_errors() {
+        let (sink, rx) = EventSink::bounded(4);
+        let delta = |n: usize| FrontendEvent::ChatStreaming {
+            conversation_id: "c".to_string(),
+            content: "x".repeat(n),
+            done: false,
+        };
+
+        for n in 1..=4 {
+            sink.push(delta(n)).await;
+        }
+        sink.push(FrontendEvent::Error("first".to_string())).await;
+        sink.push(delta(5)).await;
+        sink.push(FrontendEvent::ApprovalRequest {
+            tool_name: "shell".to_string(),
+            reason: "side effects".to_string(),
+        })
+        .await;
+        sink.push(FrontendEvent::Error("second".to_string())).await;
+
+        let received: Vec<FrontendEvent> = rx.drain().collect();
+        assert_eq!(received.len(), 4);
+        assert!(matches!(
+            received.as_slice(),
+            [
+                FrontendEvent::Error(first),
+                FrontendEvent::ChatStreaming { content, .. },
+                FrontendEvent::ApprovalRequest { .. },
+                FrontendEvent::Error(second),
+            ] if first == "first" && content.len() == 5 && second == "second"
+        ));
+
+        // With only critical events queued, a new streaming update is dropped
+        // rather than displacing one of them.
+        for n in 0..4 {
+            sink.push(FrontendEvent::Error(n.to_string())).await;
+        }
+        sink.push(delta(6)).await;
+        let received: Vec<FrontendEvent> = rx.drain().collect();
+        assert_eq!(received.len(), 4);
+        assert!(received.iter().all(|event| !event.is_droppable()));
+
+        // Only the first chunk of a streaming tool names its arguments.
+        let chunk = |arguments: Option<Value>| FrontendEvent::ToolCallProgress {
+            conversation_id: "c".to_string(),
+            tool_name: "shell".to_string(),
+            output_preview: "...".to_string(),
+            iteration: None,
+            max_iterations: None,
+            tool_call_id: Some("call-1".to_string()),
+            partial: true,
+            arguments,
+        };
+        assert!(!chunk(Some(serde_json::json!({ "command": "ls" }))).is_droppable());
+        assert!(chunk(None).is_droppable());
+    }
+
+    #[tokio::test]
+    #[allow(clippy::result_large_err)] // tungstenite's handshake callback signature
+    async fn reconnect_sends_cursor_and_replayed_gap_is_delivered_once() {
+        use futures_util::SinkExt;
+        use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock event stream");
+        let addr = listener.local_addr().expect("mock addr");
+        let journal = |id: u64, summary: &str| {
+            Message::text(
+                serde_json::json!({
+                    "event_type": "journal_written",
+                    "event_id": id,
+                    "stream_id": "backend-1",
+                    "payload": {"summary": summary}
+                })
+                .to_string(),
+            )
+        };
+        let server = tokio::spawn(async move {
+            let mut cursors = Vec::new();
+            // First connection delivers 1-2 then drops; the second replays from the
+            // client's cursor (overlapping event 2) and adds 3, emitted during the gap.
+            for batch in [vec![(1, "one"), (2, "two")], vec![(2, "two"), (3, "three")]] {
+                let (socket, _) = listener.accept().await.expect("accept");
+                let mut seen = None;
+                let mut ws = tokio_tungstenite::accept_hdr_async(
+                    socket,
+                    |request: &Request, response: Response| {
+                        seen = request
+                            .headers()
+                            .get("Last-Event-ID")
+                            .and_then(|value| value.to_str().ok())
+                            .map(ToString::to_string);
+                        Ok(response)
+                    },
+                )
+                .await
+                .expect("ws handshake");
+                cursors.push(seen);
+                for (id, summary) in batch {
+                    ws.send(journal(id, summary)).await.expect("send event");
+                }
+                ws.close(None).await.expect("close");
+            }
+            cursors
+        });
+
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let (tx, rx) = EventSink::bounded(EVENT_CHANNEL_CAPACITY);
+        let mut cursor = EventCursor::default();
+        client
+            .stream_events_once(&tx, &mut cursor)
+            .await
+            .expect("first");
+        client
+            .stream_events_once(&tx, &mut cursor)
+            .await
+            .expect("second");
+
+        let summaries: Vec<String> = rx
+            .try_iter()
+            .map(|event| match event {
+                FrontendEvent::JournalWritten(summary) => summary,
+                other => panic!("unexpected event: {other:?}"),
+            })
+            .collect();
+        assert_eq!(summaries, ["one", "two", "three"]);
+        assert_eq!(
+            server.await.expect("mock server"),
+            [None, Some("2".to_string())]
+        );
+    }
+
+    #[test]
+    fn formats_llm_request_as_curl_with_masked_token() {
+        let capture = LlmRequestCapture {
+            url: "http://localhost:1234/v1/chat/completions".to_string(),
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Authorization".to_string(), "Bearer sk-secret".to_string()),
+            ],
+            body: serde_json::json!({"model": "m", "messages": [{"role": "user", "content": "it's"}]}),
+            captured_at: None,
+        };
+        let curl = capture.to_curl();
+
+        assert!(curl
+            .starts_with("curl \\\n  -X POST \\\n  'http://localhost:1234/v1/chat/completions'"));
+        assert!(curl.contains("-H 'Content-Type: application/json'"));
+        assert!(curl.contains("-H 'Authorization: Bearer ***'"));
+        assert!(!curl.contains("sk-secret"));
+        assert!(curl.contains("it'\\''s"));
     }
-}
 
-fn json_value_to_short_string(value: &Value) -> String {
-    match value {
-        Value::String(s) => s.clone(),
-        Value::Null => "null".to_string(),
-        other => other.to_string(),
+    #[test]
+    fn decodes_storage_info_with_optional_paths() {
+        let info: StorageInfo = serde_json::from_value(serde_json::json!({
+            "data_dir": "/home/u/.local/share/ponderer"
+        }))
+        .expect("decode storage info");
+        assert_eq!(info.data_dir, "/home/u/.local/share/ponderer");
+        assert!(info.database_path.is_none());
+        assert!(!info.overridden);
     }
-}
 
-fn normalize_base_url(raw: &str) -> String {
-    let trimmed = raw.trim().trim_end_matches('/');
-    if trimmed.is_empty() {
-        "http://127.0.0.1:8787".to_string()
-    } else {
-        trimmed.to_string()
+    #[tokio::test]
+    async fn set_config_flags_puts_the_flags_and_decodes_missing_ones_as_off() {
+        let (addr, server) = serve_once("200 OK", r#"{"concise_replies":true}"#).await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let flags = client
+            .set_config_flags(&ConfigFlags {
+                confirm_tool_batches: false,
+                concise_replies: true,
+            })
+            .await
+            .expect("set flags");
+        assert!(flags.concise_replies);
+        assert!(!flags.confirm_tool_batches);
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with("PUT /v1/config/flags HTTP/1.1"));
+        assert!(request.ends_with(r#"{"confirm_tool_batches":false,"concise_replies":true}"#));
+
+        let flags: ConfigFlags = serde_json::from_str("{}").expect("decode flags");
+        assert_eq!(flags, ConfigFlags::default());
     }
-}
 
-fn normalize_ws_url(base_http_url: &str) -> String {
-    if let Some(rest) = base_http_url.strip_prefix("https://") {
-        format!("wss://{}", rest)
-    } else if let Some(rest) = base_http_url.strip_prefix("http://") {
-        format!("ws://{}", rest)
-    } else {
-        format!("ws://{}", base_http_url)
+    #[test]
+    fn retention_status_flattens_policy_and_keeps_unset_limits() {
+        let status: RetentionStatus = serde_json::from_value(serde_json::json!({
+            "turn_prompt_days": 30,
+            "archive_conversation_days": null,
+            "last_report": { "turn_prompts_deleted": 12 }
+        }))
+        .expect("decode retention status");
+        assert_eq!(status.policy.turn_prompt_days, Some(30));
+        assert_eq!(status.policy.archive_conversation_days, None);
+        assert_eq!(status.policy.journal_days, None);
+        assert_eq!(
+            status.last_report.map(|report| report.turn_prompts_deleted),
+            Some(12)
+        );
+
+        // Unset limits go out as explicit nulls so PUT can clear them.
+        let body = serde_json::to_value(&status.policy).expect("encode policy");
+        assert_eq!(body["turn_prompt_days"], 30);
+        assert!(body["journal_days"].is_null());
+        assert!(body
+            .as_object()
+            .expect("object")
+            .contains_key("journal_days"));
     }
-}
 
-fn validate_health_status(status: &str) -> Result<()> {
-    match status {
-        "ok" | "degraded" => Ok(()),
-        other => anyhow::bail!("Backend reported unrecognized health status '{other}'"),
+    #[test]
+    fn maps_agent_reset_event() {
+        let envelope = ApiEventEnvelope {
+            event_type: "agent_reset".to_string(),
+            payload: serde_json::json!({}),
+        };
+        assert!(matches!(
+            map_event(envelope),
+            Some(FrontendEvent::AgentReset)
+        ));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn maps_conversation_compacted_event() {
+        let envelope = ApiEventEnvelope {
+            event_type: "conversation_compacted".to_string(),
+            payload: serde_json::json!({
+                "conversation_id": "c1",
+                "summary": "Discussed the garden plan.",
+                "through_message_id": "m42"
+            }),
+        };
+        match map_event(envelope) {
+            Some(FrontendEvent::ConversationCompacted {
+                conversation_id,
+                summary,
+                through_message_id,
+            }) => {
+                assert_eq!(conversation_id, "c1");
+                assert_eq!(summary, "Discussed the garden plan.");
+                assert_eq!(through_message_id.as_deref(), Some("m42"));
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
 
     #[test]
-    fn parses_visual_state_case_insensitive() {
+    fn iteration_limit_event_distinguishes_mid_progress_from_idle() {
+        let limit_event = |payload: Value| {
+            map_event(ApiEventEnvelope {
+                event_type: "iteration_limit_hit".to_string(),
+                payload,
+            })
+        };
+        match limit_event(serde_json::json!({
+            "conversation_id": "c1",
+            "max_iterations": 3,
+            "mid_progress": true
+        })) {
+            Some(FrontendEvent::IterationLimitHit {
+                conversation_id,
+                max_iterations,
+                mid_progress,
+            }) => {
+                assert_eq!(conversation_id, "c1");
+                assert_eq!(max_iterations, Some(3));
+                assert!(mid_progress);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        // Older or idle reports without the flag never trigger the hint.
         assert!(matches!(
-            parse_visual_state("Thinking"),
-            Some(AgentVisualState::Thinking)
+            limit_event(serde_json::json!({ "conversation_id": "c1" })),
+            Some(FrontendEvent::IterationLimitHit {
+                mid_progress: false,
+                max_iterations: None,
+                ..
+            })
         ));
+    }
+
+    #[test]
+    fn config_proposal_event_decodes_the_diff() {
+        let envelope = ApiEventEnvelope {
+            event_type: "config_proposal".to_string(),
+            payload: serde_json::json!({
+                "id": "p1",
+                "reason": "Check in more often while the user is active",
+                "changes": [{ "field": "heartbeat_interval_mins", "old": 30, "new": 10 }]
+            }),
+        };
+        match map_event(envelope) {
+            Some(FrontendEvent::ConfigProposed(proposal)) => {
+                assert_eq!(proposal.id, "p1");
+                assert_eq!(
+                    proposal.changes[0].describe(40),
+                    "heartbeat_interval_mins: 30 → 10"
+                );
+                assert!(proposal.proposed_at.is_none());
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        // A proposal without a diff is dropped rather than shown as an empty card.
+        let sneaky: ConfigProposal = serde_json::from_value(serde_json::json!({
+            "id": "p3",
+            "changes": [
+                { "field": "heartbeat_interval_mins", "old": 30, "new": 10 },
+                { "field": "llm_api_key", "old": "sk-a", "new": "sk-b" }
+            ]
+        }))
+        .expect("proposal");
+        assert!(sneaky.touches_protected_field());
+        for field in [
+            "telegram_bot_token",
+            "loose_mode",
+            "plugin_settings",
+            "llm_api_url",
+        ] {
+            let change = ConfigFieldChange {
+                field: field.to_string(),
+                old: Value::Null,
+                new: Value::Null,
+            };
+            assert!(change.is_protected(), "{field}");
+        }
+        assert!(map_event(ApiEventEnvelope {
+            event_type: "config_proposal".to_string(),
+            payload: serde_json::json!({ "id": "p2" }),
+        })
+        .is_none());
+    }
+
+    #[test]
+    fn tool_batch_event_lists_every_pending_call() {
+        let envelope = ApiEventEnvelope {
+            event_type: "tool_batch_pending".to_string(),
+            payload: serde_json::json!({
+                "conversation_id": "c1",
+                "turn_id": "t4",
+                "calls": [
+                    { "tool_call_id": "a", "tool_name": "shell", "arguments": { "command": "ls" } },
+                    { "tool_call_id": "b", "tool_name": "read_file" }
+                ]
+            }),
+        };
+        match map_event(envelope) {
+            Some(FrontendEvent::ToolBatchPending(batch)) => {
+                assert_eq!(batch.turn_id, "t4");
+                assert_eq!(batch.calls.len(), 2);
+                assert_eq!(batch.calls[0].arguments["command"], "ls");
+                assert!(batch.calls[1].arguments.is_null());
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        assert_eq!(
+            serde_json::to_value(ToolCallDecision {
+                tool_call_id: "b".to_string(),
+                approve: false,
+            })
+            .expect("encode"),
+            serde_json::json!({ "tool_call_id": "b", "approve": false })
+        );
+    }
+
+    #[test]
+    fn tool_input_request_carries_the_ids_needed_to_resume() {
+        let envelope = ApiEventEnvelope {
+            event_type: "tool_input_request".to_string(),
+            payload: serde_json::json!({
+                "conversation_id": "c1",
+                "turn_id": "t9",
+                "tool_call_id": "call_2",
+                "tool_name": "read_file",
+                "prompt": "Which file did you mean?"
+            }),
+        };
+        match map_event(envelope) {
+            Some(FrontendEvent::ToolInputRequested {
+                conversation_id,
+                turn_id,
+                tool_call_id,
+                tool_name,
+                prompt,
+            }) => {
+                assert_eq!(conversation_id, "c1");
+                assert_eq!(turn_id, "t9");
+                assert_eq!(tool_call_id, "call_2");
+                assert_eq!(tool_name, "read_file");
+                assert_eq!(prompt, "Which file did you mean?");
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn heartbeat_envelope_is_not_surfaced() {
+        let envelope = ApiEventEnvelope {
+            event_type: "heartbeat".to_string(),
+            payload: serde_json::json!({"interval_secs": 15}),
+        };
+        assert!(map_event(envelope).is_none());
+    }
+
+    #[test]
+    fn conversation_tags_round_trip() {
+        let request = UpdateConversationRequest {
+            tags: Some(vec!["work".to_string(), "rust".to_string()]),
+            note: Some("follow up friday".to_string()),
+            ..Default::default()
+        };
+        let body = serde_json::to_value(&request).expect("encode");
+        assert_eq!(
+            body,
+            serde_json::json!({"tags": ["work", "rust"], "note": "follow up friday"})
+        );
+
+        let conversation: ChatConversation = serde_json::from_value(serde_json::json!({
+            "id": "c1",
+            "session_id": "s1",
+            "title": "Chat",
+            "created_at": "2026-02-17T05:19:24Z",
+            "updated_at": "2026-02-17T05:19:24Z",
+            "runtime_state": "idle",
+            "active_turn_id": null,
+            "message_count": 0,
+            "last_message_at": null,
+            "tags": body["tags"].clone(),
+            "note": body["note"].clone()
+        }))
+        .expect("decode");
+        assert_eq!(conversation.tags, vec!["work", "rust"]);
+        assert_eq!(conversation.note.as_deref(), Some("follow up friday"));
+    }
+
+    #[test]
+    fn conversation_without_metadata_decodes_with_defaults() {
+        let conversation: ChatConversation = serde_json::from_value(serde_json::json!({
+            "id": "c1",
+            "session_id": "s1",
+            "title": "Chat",
+            "created_at": "2026-02-17T05:19:24Z",
+            "updated_at": "2026-02-17T05:19:24Z",
+            "runtime_state": "idle",
+            "active_turn_id": null,
+            "message_count": 0,
+            "last_message_at": null
+        }))
+        .expect("decode");
+        assert!(conversation.tags.is_empty());
+        assert!(conversation.note.is_none());
+        assert!(conversation.system_prompt_prefix.is_none());
+        assert!(conversation.seed.is_none());
+        assert!(conversation.group.is_none());
+        assert!(conversation.sort_index.is_none());
+    }
+
+    #[tokio::test]
+    async fn fork_conversation_posts_the_branch_point() {
+        let (addr, server) = serve_once(
+            "200 OK",
+            r#"{"id":"c2","session_id":"s","title":"Trip plans (branch)","created_at":"2026-01-02T00:00:00Z","updated_at":"2026-01-02T00:00:00Z","runtime_state":"idle","active_turn_id":null,"message_count":4,"last_message_at":"2026-01-01T00:00:00Z"}"#,
+        )
+        .await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        let branch = client.fork_conversation("c1", "m4").await.expect("fork");
+        assert_eq!(branch.id, "c2");
+        assert_eq!(branch.message_count, 4);
+
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with("POST /v1/conversations/c1/fork HTTP/1.1"));
+        assert_eq!(
+            request_json(&request),
+            serde_json::json!({ "at_message_id": "m4" })
+        );
+    }
+
+    #[tokio::test]
+    async fn reorder_conversations_puts_every_placement() {
+        let (addr, server) = serve_once("204 No Content", "").await;
+        let client = ApiClient::new_local(format!("http://{}", addr), None);
+        client
+            .reorder_conversations(&[
+                ConversationPlacement {
+                    id: "c2".to_string(),
+                    group: Some("Work".to_string()),
+                    sort_index: 0,
+                },
+                ConversationPlacement {
+                    id: "c1".to_string(),
+                    group: None,
+                    sort_index: 0,
+                },
+            ])
+            .await
+            .expect("reorder");
+
+        let request = server.await.expect("mock server");
+        assert!(request.starts_with("PUT /v1/conversations/order HTTP/1.1"));
+        let body = request_json(&request);
+        assert_eq!(
+            body,
+            serde_json::json!({"placements": [
+                {"id": "c2", "group": "Work", "sort_index": 0},
+                {"id": "c1", "group": null, "sort_index": 0}
+            ]})
+        );
+    }
+
+    #[test]
+    fn seed_is_sent_only_when_configured() {
+        let untouched = UpdateConversationRequest {
+            note: Some("n".to_string()),
+            ..Default::default()
+        };
+        let body = serde_json::to_value(&untouched).expect("encode");
+        assert!(body.get("seed").is_none());
+
+        let fixed = UpdateConversationRequest {
+            seed: Some(Some(42)),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&fixed).expect("encode"),
+            serde_json::json!({"seed": 42})
+        );
+
+        let cleared = UpdateConversationRequest {
+            seed: Some(None),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&cleared).expect("encode"),
+            serde_json::json!({"seed": null})
+        );
+
+        let prompt: ChatTurnPrompt = serde_json::from_value(serde_json::json!({
+            "turn_id": "t1",
+            "prompt_text": "p",
+            "system_prompt_text": null,
+            "seed": 42
+        }))
+        .expect("decode");
+        assert_eq!(prompt.seed, Some(42));
+    }
+
+    #[test]
+    fn system_prompt_prefix_is_sent_only_when_set() {
+        let untouched = UpdateConversationRequest {
+            title: Some("Renamed".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&untouched).expect("encode"),
+            serde_json::json!({"title": "Renamed"})
+        );
+
+        let cleared = UpdateConversationRequest {
+            system_prompt_prefix: Some(String::new()),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&cleared).expect("encode"),
+            serde_json::json!({"system_prompt_prefix": ""})
+        );
+    }
+
+    #[test]
+    fn parses_tool_progress_iteration_numbers() {
+        let envelope = ApiEventEnvelope {
+            event_type: "tool_call_progress".to_string(),
+            payload: serde_json::json!({
+                "conversation_id": "c1",
+                "tool_name": "shell",
+                "output_preview": "ok",
+                "iteration": 3,
+                "max_iterations": 10
+            }),
+        };
+        match map_event(envelope).expect("mapped") {
+            FrontendEvent::ToolCallProgress {
+                iteration,
+                max_iterations,
+                ..
+            } => {
+                assert_eq!(iteration, Some(3));
+                assert_eq!(max_iterations, Some(10));
+            }
+            _ => panic!("wrong variant"),
+        }
+
+        let envelope = ApiEventEnvelope {
+            event_type: "tool_call_progress".to_string(),
+            payload: serde_json::json!({"conversation_id": "c1", "tool_name": "shell"}),
+        };
         assert!(matches!(
-            parse_visual_state("paused"),
-            Some(AgentVisualState::Paused)
+            map_event(envelope),
+            Some(FrontendEvent::ToolCallProgress {
+                iteration: None,
+                max_iterations: None,
+                tool_call_id: None,
+                partial: false,
+                arguments: None,
+                ..
+            })
         ));
-        assert!(parse_visual_state("unknown").is_none());
+    }
+
+    #[test]
+    fn parses_streaming_tool_progress_chunks() {
+        let envelope = ApiEventEnvelope {
+            event_type: "tool_call_progress".to_string(),
+            payload: serde_json::json!({
+                "conversation_id": "c1",
+                "tool_name": "shell",
+                "tool_call_id": "call_7",
+                "output_preview": "Compiling foo v0.1.0",
+                "partial": true,
+                "arguments": { "command": "cargo build" }
+            }),
+        };
+        match map_event(envelope).expect("mapped") {
+            FrontendEvent::ToolCallProgress {
+                tool_call_id,
+                partial,
+                output_preview,
+                arguments,
+                ..
+            } => {
+                assert_eq!(tool_call_id.as_deref(), Some("call_7"));
+                assert!(partial);
+                assert_eq!(output_preview, "Compiling foo v0.1.0");
+                assert_eq!(
+                    arguments,
+                    Some(serde_json::json!({ "command": "cargo build" }))
+                );
+            }
+            _ => panic!("wrong variant"),
+        }
     }
 
     #[test]
@@ -1079,6 +4276,23 @@ mod tests {
         assert!(validate_health_status("unknown").is_err());
     }
 
+    #[test]
+    fn only_awaiting_approval_overrides_the_visual_state() {
+        let cases = [
+            (ChatTurnPhase::Idle, None),
+            (ChatTurnPhase::Processing, None),
+            (ChatTurnPhase::Completed, None),
+            (
+                ChatTurnPhase::AwaitingApproval,
+                Some(AgentVisualState::WaitingForUser),
+            ),
+            (ChatTurnPhase::Failed, None),
+        ];
+        for (phase, expected) in cases {
+            assert_eq!(phase.visual_state_override(), expected, "{phase:?}");
+        }
+    }
+
     #[test]
     fn chat_conversation_deserializes_snake_case_runtime_state() {
         let payload = serde_json::json!([{
@@ -1100,6 +4314,56 @@ mod tests {
             parsed[0].runtime_state,
             ChatTurnPhase::AwaitingApproval
         ));
+        assert_eq!(parsed[0].usage, None);
+        assert_eq!(parsed[0].tool_mode, ToolMode::Auto);
+    }
+
+    #[test]
+    fn conversation_recap_defaults_to_a_single_unsaved_chunk() {
+        let recap: ConversationRecap =
+            serde_json::from_value(serde_json::json!({ "summary": "We planned the trip." }))
+                .expect("decode recap");
+        assert_eq!(recap.chunk_count, 1);
+        assert_eq!(recap.message_count, 0);
+        assert!(recap.journal_entry_id.is_none());
+    }
+
+    #[test]
+    fn tool_mode_patch_sends_only_the_mode() {
+        let request = UpdateConversationRequest {
+            tool_mode: Some(ToolMode::Required),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&request).expect("encode"),
+            serde_json::json!({ "tool_mode": "required" })
+        );
+        let off: ToolMode = serde_json::from_value(serde_json::json!("off")).expect("decode");
+        assert_eq!(off, ToolMode::Off);
+    }
+
+    #[test]
+    fn conversation_usage_rolls_into_a_short_summary() {
+        let usage: ConversationUsage = serde_json::from_value(serde_json::json!({
+            "prompt_tokens": 11_800,
+            "completion_tokens": 640,
+            "estimated_cost_usd": 0.0312
+        }))
+        .expect("decode usage");
+        assert_eq!(usage.total_tokens(), 12_440);
+        assert_eq!(usage.summary(), "12.4k tokens · $0.03");
+
+        let local = ConversationUsage {
+            prompt_tokens: 900,
+            completion_tokens: 20,
+            estimated_cost_usd: None,
+        };
+        assert_eq!(local.summary(), "920 tokens");
+        let cheap = ConversationUsage {
+            estimated_cost_usd: Some(0.0042),
+            ..local
+        };
+        assert_eq!(cheap.summary(), "920 tokens · $0.0042");
     }
 
     #[test]
@@ -1115,5 +4379,152 @@ mod tests {
         assert!(matches!(parsed.visual_state, AgentVisualState::Thinking));
         assert!(!parsed.loose_mode);
         assert!(parsed.current_intention.is_none());
+        assert!(!parsed.autonomy_paused);
+    }
+
+    #[test]
+    fn turn_rating_round_trips_on_messages() {
+        let rated: ChatMessage = serde_json::from_value(serde_json::json!({
+            "id": "m1",
+            "conversation_id": "c1",
+            "role": "assistant",
+            "content": "Done.",
+            "created_at": "2026-03-01T09:00:00Z",
+            "processed": true,
+            "turn_id": "t1",
+            "rating": "down"
+        }))
+        .expect("decode rated message");
+        assert_eq!(rated.rating, Some(TurnRating::Down));
+        assert_eq!(
+            serde_json::to_value(&rated).expect("encode")["rating"],
+            "down"
+        );
+
+        let mut unrated = serde_json::to_value(&rated).expect("encode");
+        unrated.as_object_mut().unwrap().remove("rating");
+        let unrated: ChatMessage = serde_json::from_value(unrated).expect("decode");
+        assert_eq!(unrated.rating, None);
+    }
+
+    #[test]
+    fn config_history_decodes_and_describes_field_changes() {
+        let payload = serde_json::json!([{
+            "changed_at": "2026-03-01T09:00:00Z",
+            "source": "agent",
+            "changes": [
+                { "field": "max_tool_iterations", "old": 10, "new": 20 },
+                { "field": "llm_model", "old": "llama3", "new": "qwen2.5" },
+                { "field": "llm_api_key", "old": "[redacted]", "new": "[redacted]" },
+                { "field": "heartbeat_checklist_path", "new": "/tmp/list.md" }
+            ]
+        }]);
+
+        let history: Vec<ConfigChange> = serde_json::from_value(payload).expect("decode history");
+        assert_eq!(history[0].source, ConfigChangeSource::Agent);
+        let described: Vec<String> = history[0]
+            .changes
+            .iter()
+            .map(|change| change.describe(40))
+            .collect();
+        assert_eq!(
+            described,
+            [
+                "max_tool_iterations: 10 → 20",
+                "llm_model: llama3 → qwen2.5",
+                "llm_api_key: [redacted] → [redacted]",
+                "heartbeat_checklist_path: (unset) → /tmp/list.md",
+            ]
+        );
+
+        // Long values are cut between graphemes, never inside one.
+        let emoji = ConfigFieldChange {
+            field: "character_name".to_string(),
+            old: Value::from(1),
+            new: Value::String("Ae\u{301}👨\u{200d}👩\u{200d}👧x".to_string()),
+        };
+        assert_eq!(
+            emoji.describe(3),
+            "character_name: 1 → Ae\u{301}👨\u{200d}👩\u{200d}👧…"
+        );
+    }
+
+    #[test]
+    fn remember_request_omits_scope_for_global_entries() {
+        let global = RememberRequest {
+            content: "The user's cat is named Miso".to_string(),
+            conversation_id: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&global).expect("encode"),
+            serde_json::json!({ "content": "The user's cat is named Miso" })
+        );
+        let scoped = RememberRequest {
+            conversation_id: Some("c1".to_string()),
+            ..global
+        };
+        assert_eq!(
+            serde_json::to_value(&scoped).expect("encode")["conversation_id"],
+            "c1"
+        );
+    }
+
+    #[test]
+    fn upcoming_items_decode_kind_and_optional_fields() {
+        let payload = serde_json::json!([
+            {
+                "kind": "scheduled_action",
+                "id": "job-1",
+                "summary": "Check the feed",
+                "due_at": "2026-03-01T09:00:00Z",
+                "detail": "every 60 min"
+            },
+            { "kind": "intention", "id": "int-7", "summary": "Finish the essay" }
+        ]);
+
+        let items: Vec<UpcomingItem> = serde_json::from_value(payload).expect("decode upcoming");
+        assert_eq!(items[0].kind, UpcomingKind::ScheduledAction);
+        assert_eq!(items[0].kind.label(), "scheduled");
+        assert!(items[0].due_at.is_some());
+        assert_eq!(items[1].kind, UpcomingKind::Intention);
+        assert!(items[1].due_at.is_none());
+        assert!(items[1].detail.is_none());
+    }
+
+    #[test]
+    fn turn_timing_decodes_and_accounts_for_the_total() {
+        let payload = serde_json::json!({
+            "turn_id": "t1",
+            "prompt_text": "hi",
+            "system_prompt_text": null,
+            "timing": {
+                "total_ms": 5230,
+                "phases": [
+                    { "kind": "context_build", "label": "context", "duration_ms": 120 },
+                    { "kind": "llm_call", "label": "llm call 1", "duration_ms": 1800 },
+                    { "kind": "tool_call", "label": "web_search", "duration_ms": 2900, "tool_call_id": "call_1" },
+                    { "kind": "tool_call", "label": "read_file", "duration_ms": 40, "tool_call_id": "call_2" },
+                    { "kind": "llm_call", "label": "llm call 2", "duration_ms": 350 },
+                    { "kind": "compaction", "label": "compaction", "duration_ms": 0 }
+                ]
+            }
+        });
+
+        let response: ChatTurnPromptResponse =
+            serde_json::from_value(payload).expect("decode prompt with timing");
+        let timing = response.timing.expect("timing present");
+        assert_eq!(timing.phases[5].kind, TimingPhaseKind::Other);
+        assert_eq!(timing.total_for(TimingPhaseKind::LlmCall), 2150);
+        assert_eq!(timing.total_for(TimingPhaseKind::ToolCall), 2940);
+        assert_eq!(timing.unaccounted_ms(), 20);
+        assert_eq!(timing.slowest_phase(), Some(2));
+
+        let older: ChatTurnPromptResponse = serde_json::from_value(serde_json::json!({
+            "turn_id": "t0",
+            "prompt_text": "hi",
+            "system_prompt_text": null
+        }))
+        .expect("decode prompt without timing");
+        assert!(older.timing.is_none());
     }
 }
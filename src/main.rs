@@ -22,7 +22,12 @@ pub use ponderer_backend::config;
 
 use api::ApiClient;
 use config::AgentConfig;
-use ui::app::AgentApp;
+use ui::app::{brand_name, AgentApp};
+use ui::prefs::{UiPrefs, WindowPlacement};
+
+const DEFAULT_WINDOW_SIZE: egui::Vec2 = egui::vec2(600.0, 800.0);
+/// How much of a window must lie on a monitor for the user to still grab it.
+const MIN_VISIBLE_WINDOW_STRIP: egui::Vec2 = egui::vec2(48.0, 24.0);
 
 fn main() {
     if let Some(arg) = std::env::args().nth(1) {
@@ -76,10 +81,23 @@ fn run_desktop_mode() -> Result<()> {
         );
     }
 
+    // winit only enumerates monitors once the event loop runs, so the restored
+    // position is checked against the saved monitor size here and against the
+    // real display on the first frame (see `AgentApp::check_window_placement`).
+    // The character name is only known once the app loads the card; the first
+    // frame retitles the window (see `AgentApp::sync_window_title`).
+    let ui_prefs = UiPrefs::load();
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size(DEFAULT_WINDOW_SIZE)
+        .with_title(brand_name(&ui_prefs.agent_name, ""));
+    if let Some(placement) = ui_prefs.window {
+        let bounds = restored_window_bounds(&placement);
+        viewport = viewport
+            .with_position(bounds.min)
+            .with_inner_size(bounds.size());
+    }
     let native_options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([600.0, 800.0])
-            .with_title("Ponderer"),
+        viewport,
         ..Default::default()
     };
 
@@ -107,6 +125,48 @@ fn run_desktop_mode() -> Result<()> {
     Ok(())
 }
 
+/// Returns window bounds that are reachable on one of `monitors` (primary
+/// first). A window still partly on a monitor is pulled fully onto the one it
+/// overlaps most; one that is not is centred on the primary display. With no
+/// monitor information the saved bounds are returned unchanged.
+fn safe_window_bounds(saved: egui::Rect, monitors: &[egui::Rect]) -> egui::Rect {
+    let Some(primary) = monitors.first() else {
+        return saved;
+    };
+
+    let best_visible = monitors
+        .iter()
+        .filter_map(|monitor| {
+            let overlap = monitor.intersect(saved);
+            (overlap.width() >= MIN_VISIBLE_WINDOW_STRIP.x
+                && overlap.height() >= MIN_VISIBLE_WINDOW_STRIP.y)
+                .then(|| (overlap.area(), monitor))
+        })
+        .max_by(|(left, _), (right, _)| left.total_cmp(right));
+
+    match best_visible {
+        Some((_, monitor)) => {
+            let size = saved.size().min(monitor.size());
+            let min = saved.min.clamp(monitor.min, monitor.max - size);
+            egui::Rect::from_min_size(min, size)
+        }
+        None => egui::Rect::from_center_size(primary.center(), saved.size().min(primary.size())),
+    }
+}
+
+/// Bounds for a saved window before any monitor is known: the saved monitor
+/// size stands in for the primary display at the origin, so an off-screen
+/// position is centred on it.
+fn restored_window_bounds(placement: &WindowPlacement) -> egui::Rect {
+    let saved = egui::Rect::from_min_size(placement.position.into(), placement.inner_size.into());
+    let primary: Vec<egui::Rect> = placement
+        .monitor_size
+        .map(|size| egui::Rect::from_min_size(egui::Pos2::ZERO, size.into()))
+        .into_iter()
+        .collect();
+    safe_window_bounds(saved, &primary)
+}
+
 fn run_backend_only() -> Result<()> {
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -436,11 +496,8 @@ fn api_is_healthy_with_timeout(client: &ApiClient, timeout: Duration) -> bool {
             return false;
         }
     };
-    runtime.block_on(async {
-        tokio::time::timeout(timeout, client.health())
-            .await
-            .is_ok_and(|result| result.is_ok())
-    })
+    let probe = client.clone().with_request_timeout(timeout);
+    runtime.block_on(probe.health()).is_ok()
 }
 
 fn backend_discovery_path() -> PathBuf {
@@ -700,6 +757,72 @@ mod tests {
         }
     }
 
+    #[test]
+    fn window_bounds_stay_on_a_connected_monitor() {
+        let rect = |x: f32, y: f32, w: f32, h: f32| {
+            egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(w, h))
+        };
+        let primary = rect(0.0, 0.0, 1920.0, 1080.0);
+        let right = rect(1920.0, 0.0, 1280.0, 1024.0);
+
+        let on_secondary = rect(2100.0, 100.0, 600.0, 800.0);
+        assert_eq!(
+            safe_window_bounds(on_secondary, &[primary, right]),
+            on_secondary
+        );
+
+        // Hanging off the right edge of the secondary display.
+        assert_eq!(
+            safe_window_bounds(rect(2900.0, 100.0, 600.0, 800.0), &[primary, right]),
+            rect(2600.0, 100.0, 600.0, 800.0)
+        );
+
+        // Secondary display unplugged: recentre on the primary.
+        assert_eq!(
+            safe_window_bounds(on_secondary, &[primary]),
+            rect(660.0, 140.0, 600.0, 800.0)
+        );
+
+        // Larger than the only monitor: shrink to fit.
+        assert_eq!(
+            safe_window_bounds(rect(-5000.0, -5000.0, 2500.0, 1400.0), &[primary]),
+            primary
+        );
+
+        // A sliver of title bar is not enough to grab.
+        assert_eq!(
+            safe_window_bounds(rect(1900.0, 1070.0, 600.0, 800.0), &[primary]),
+            rect(660.0, 140.0, 600.0, 800.0)
+        );
+
+        assert_eq!(safe_window_bounds(on_secondary, &[]), on_secondary);
+    }
+
+    #[test]
+    fn restored_window_bounds_centre_an_off_screen_position() {
+        let placement = |position: [f32; 2], monitor_size: Option<[f32; 2]>| WindowPlacement {
+            position,
+            inner_size: [600.0, 800.0],
+            monitor_size,
+        };
+        let rect =
+            |x: f32, y: f32| egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(600.0, 800.0));
+
+        // Saved on an unplugged secondary with the same resolution as the primary.
+        assert_eq!(
+            restored_window_bounds(&placement([2100.0, 100.0], Some([1920.0, 1080.0]))),
+            rect(660.0, 140.0)
+        );
+        assert_eq!(
+            restored_window_bounds(&placement([100.0, 50.0], Some([1920.0, 1080.0]))),
+            rect(100.0, 50.0)
+        );
+        assert_eq!(
+            restored_window_bounds(&placement([2100.0, 100.0], None)),
+            rect(2100.0, 100.0)
+        );
+    }
+
     #[test]
     fn health_probe_does_not_require_an_ambient_tokio_reactor() {
         let listener = TcpListener::bind("127.0.0.1:0").unwrap();
@@ -1,14 +1,47 @@
 use eframe::egui;
 use flume::Receiver;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use unicode_segmentation::UnicodeSegmentation;
 
 use super::avatar::AvatarSet;
 use super::character::CharacterPanel;
-use super::settings::{ScheduledJobAction, SettingsPanel};
+use super::chat::{
+    parse_chat_payload, tool_argument_key, ChatPaneAction, ChatScrollState, ChatSpeakerNames,
+    CompactionMarker, FailedChatSend,
+};
+use super::command_palette::{CommandPalette, PaletteAction};
+use super::conversation_archive::{
+    ArchiveAction, ArchivedConversationsWindow, ARCHIVED_LIST_LIMIT,
+};
+use super::conversation_groups::{grouped_conversations, ConversationOrganizer};
+use super::conversation_summary::{ConversationSummaryWindow, RecapOutcome, SummarizeRequest};
+use super::debug_dump::{debug_dump_dir, ConnectionSnapshot, DebugDump};
+use super::diagnostics::{collect_checks, DiagnosticsWindow};
+use super::file_manager;
+use super::json_tree::{parse_structured_json, render_json_tree};
+use super::model_compare::ModelCompareWindow;
+use super::mood::MoodReaction;
+use super::prefs::{UiLimits, UiPrefs, WindowPlacement, LIVE_STREAM_WINDOW_RANGE};
+use super::quick_ask::{
+    read_clipboard_text, route_quick_ask, QuickAskAction, QuickAskHotkey, QuickAskRoute,
+    QuickAskWindow, QUICK_ASK_TITLE,
+};
+use super::settings::{
+    DisplaySettings, ScheduledJobAction, SettingsPanel, StorageAction, ToolSettingsAction,
+    CORE_TAB_BEHAVIOR,
+};
+use super::sprite::{
+    resolve_fallback, IdleHeartbeat, IdlePose, SpriteFallback, IDLE_BEAT_INTERVAL,
+};
 use super::token_monitor::TokenMonitorState;
 use crate::api::{
-    AgentVisualState, ApiClient, ChatConversation, ChatMessage, ChatTurnPhase, FrontendEvent,
-    OrientationSummary, RuntimeIntentionSummary, UpdateScheduledJobRequest,
-    DEFAULT_CHAT_CONVERSATION_ID,
+    is_auth_error, is_retryable_error, ActionExplanation, AgentRuntimeStatus, AgentVisualState,
+    ApiClient, ApiError, ChatConversation, ChatMessage, ChatTurnPhase, ChatTurnToolCall,
+    CompareModelsRequest, ConfigFlags, ConfigProposal, EventSink, FrontendEvent,
+    OrientationSummary, RuntimeIntentionSummary, TimingPhaseKind, ToolBatch, ToolCallDecision,
+    ToolMode, TurnRating, TurnTiming, UpcomingItem, UpdateScheduledJobRequest, WorkingMemoryEntry,
+    DEFAULT_CHAT_CONVERSATION_ID, EVENT_CHANNEL_CAPACITY,
 };
 use crate::config::AgentConfig;
 
@@ -17,18 +50,30 @@ const MAX_LIVE_TOOL_PROGRESS_LINES: usize = 200;
 pub struct AgentApp {
     events: Vec<FrontendEvent>,
     event_rx: Receiver<FrontendEvent>,
+    /// Background 2-second polls post here; `update()` only applies the results.
+    poll_tx: flume::Sender<PolledState>,
+    poll_rx: Receiver<PolledState>,
+    poll_tracker: PollTracker,
+    /// Set while a database backup or restore runs; at most one at a time.
+    database_transfer: Option<Receiver<DatabaseTransferOutcome>>,
     api_client: ApiClient,
     current_state: AgentVisualState,
     user_input: String,
     runtime: tokio::runtime::Runtime,
     settings_panel: SettingsPanel,
     character_panel: CharacterPanel,
+    /// Last config loaded from or saved to the backend; the panels hold edit buffers.
+    applied_config: AgentConfig,
     avatars: Option<AvatarSet>,
     avatars_loaded: bool,
     conversations: Vec<ChatConversation>,
     active_conversation_id: String,
     chat_history: Vec<ChatMessage>,
     chat_media_cache: super::chat::ChatMediaCache,
+    /// Durable tool records per turn id, loaded on demand from the chat pane.
+    turn_tool_calls: HashMap<String, Vec<ChatTurnToolCall>>,
+    /// Scroll position / unread tracking for each conversation's chat pane.
+    chat_scroll_states: HashMap<String, ChatScrollState>,
     live_tool_progress: Vec<LiveToolProgress>,
     streaming_chat_preview: Option<StreamingChatPreview>,
     prompt_inspector: Option<PromptInspectorWindow>,
@@ -36,10 +81,26 @@ pub struct AgentApp {
     show_activity_panel: bool,
     /// Tool approval requests waiting for the user's response (tool_name, reason).
     pending_approvals: Vec<(String, String)>,
+    /// Agent config proposals waiting on the operator, oldest first.
+    pending_config_proposals: Vec<ConfigProposal>,
+    /// Paused tool batches, each with the operator's per-call allow toggles.
+    pending_tool_batches: Vec<PendingToolBatch>,
+    /// Tool questions waiting on the operator, oldest first.
+    pending_tool_inputs: Vec<PendingToolInput>,
     /// Latest orientation summary received from the backend.
     last_orientation: Option<OrientationSummary>,
     /// Last action taken by the agent (short label string).
     last_action: Option<String>,
+    last_action_id: Option<String>,
+    action_explanation: Option<ActionExplanationWindow>,
+    model_compare: ModelCompareWindow,
+    conversation_summary: ConversationSummaryWindow,
+    diagnostics: DiagnosticsWindow,
+    quick_ask: QuickAskWindow,
+    /// `None` when the platform refused the global shortcut.
+    quick_ask_hotkey: Option<QuickAskHotkey>,
+    conversation_organizer: ConversationOrganizer,
+    archived_conversations: ArchivedConversationsWindow,
     /// Last journal entry summary.
     last_journal: Option<String>,
     /// Latest live LLM token stream content (any conversation, any cycle).
@@ -52,6 +113,8 @@ pub struct AgentApp {
     current_activity: Option<String>,
     /// Whether dedicated-machine Loose autonomy is deliberately armed.
     loose_mode: bool,
+    /// Autonomous cycles paused while chat stays live.
+    autonomy_paused: bool,
     /// Current or next durable intention exposed by backend runtime status.
     current_intention: Option<RuntimeIntentionSummary>,
     show_loose_arm_confirmation: bool,
@@ -59,8 +122,107 @@ pub struct AgentApp {
     confirm_delete_conversation_id: Option<String>,
     /// Conversation pending rename: (id, draft_title).
     rename_conversation: Option<(String, String)>,
+    remember_draft: Option<RememberDraft>,
+    /// Conversation tags/note editor draft.
+    conversation_details: Option<ConversationDetailsDraft>,
+    /// When set, the conversation picker only lists conversations carrying this tag.
+    conversation_tag_filter: Option<String>,
     /// Full text to show in the Mind event detail pop-out window.
     event_detail_popup: Option<String>,
+    /// Ctrl+K quick-action palette.
+    command_palette: CommandPalette,
+    /// Working-memory entries for the Mind panel inspector; `None` until first opened.
+    working_memory: Option<Vec<WorkingMemoryEntry>>,
+    working_memory_error: Option<String>,
+    upcoming: Option<Vec<UpcomingItem>>,
+    upcoming_error: Option<String>,
+    confirm_clear_working_memory: bool,
+    /// Limit the inspector to what the active conversation's context can see.
+    working_memory_active_scope_only: bool,
+    /// Persisted desktop layout preferences (focus mode).
+    ui_prefs: UiPrefs,
+    /// Latest window placement, saved into `ui_prefs` on exit.
+    window_placement: Option<WindowPlacement>,
+    window_placement_checked: bool,
+    streaming_coalescer: StreamingCoalescer,
+    /// Drives the idle sprite's occasional breath and glance at new activity.
+    idle_heartbeat: IdleHeartbeat,
+    /// Post-reply `Happy`/`Confused` flash, when `UiPrefs::mood_reactions` is on.
+    mood_reaction: Option<MoodReaction>,
+    /// Title last sent to the viewport; `None` until the first frame sets it.
+    window_title: Option<String>,
+    /// Set while settings show the local fallback because the backend config failed to load.
+    config_load_error: Option<String>,
+    /// Limit reported by the first mid-progress `IterationLimitHit`, until dismissed.
+    iteration_limit_hint: Option<Option<u32>>,
+    /// Set when the backend rejects our token (401/403); cleared by the next successful status poll.
+    auth_failed: bool,
+    /// Error from the latest status poll, kept for debug dumps.
+    last_status_error: Option<String>,
+    auth_token_draft: String,
+    /// Sends the backend rejected, per conversation, shown inline with Retry/Discard.
+    failed_sends: HashMap<String, Vec<FailedChatSend>>,
+    /// Compactions seen this session, per conversation, for inline separators.
+    compactions: HashMap<String, Vec<CompactionMarker>>,
+    next_failed_send_id: u64,
+}
+
+struct ConversationDetailsDraft {
+    conversation_id: String,
+    tags_text: String,
+    note: String,
+    system_prompt_prefix: String,
+    /// `Some` pins the sampling seed for this conversation.
+    seed: Option<u64>,
+    /// Exempt from auto-archiving.
+    pinned: bool,
+}
+
+/// One background poll's results, fetched off the UI thread.
+struct PolledState {
+    epoch: u64,
+    status: anyhow::Result<AgentRuntimeStatus>,
+    conversations: anyhow::Result<Vec<ChatConversation>>,
+    conversation_id: String,
+    history: anyhow::Result<Vec<ChatMessage>>,
+}
+
+/// Outcome of a backup or restore run on the runtime by `start_database_transfer`.
+enum DatabaseTransferOutcome {
+    BackedUp { path: PathBuf, bytes: u64 },
+    Restored { path: PathBuf },
+    BackupFailed(String),
+    RestoreFailed(String),
+}
+
+/// Keeps background polls from overlapping and from clobbering newer data.
+/// A synchronous refresh (after sending, deleting, etc.) bumps the epoch, so a
+/// poll that started before it is discarded instead of rolling the chat back.
+#[derive(Debug, Default)]
+struct PollTracker {
+    epoch: u64,
+    in_flight: bool,
+}
+
+impl PollTracker {
+    /// Returns the epoch to tag a new poll with, or `None` if one is running.
+    fn begin(&mut self) -> Option<u64> {
+        if self.in_flight {
+            return None;
+        }
+        self.in_flight = true;
+        Some(self.epoch)
+    }
+
+    fn invalidate(&mut self) {
+        self.epoch += 1;
+    }
+
+    /// Marks the poll done and reports whether its data is still current.
+    fn finish(&mut self, epoch: u64) -> bool {
+        self.in_flight = false;
+        epoch == self.epoch
+    }
 }
 
 struct StreamingChatPreview {
@@ -68,12 +230,151 @@ struct StreamingChatPreview {
     content: String,
 }
 
+const STREAMING_COALESCE_WINDOW: std::time::Duration = std::time::Duration::from_millis(50);
+
+#[derive(Debug, Clone, PartialEq)]
+struct StreamingUpdate {
+    conversation_id: String,
+    content: String,
+    done: bool,
+}
+
+/// `ChatStreaming` events carry the whole text so far, so applying every one of
+/// them just re-wraps the same growing string. This keeps only the newest
+/// snapshot and releases at most one per window; `done` always goes through.
+struct StreamingCoalescer {
+    window: std::time::Duration,
+    pending: Option<StreamingUpdate>,
+    last_applied: Option<std::time::Instant>,
+}
+
+impl StreamingCoalescer {
+    fn new(window: std::time::Duration) -> Self {
+        Self {
+            window,
+            pending: None,
+            last_applied: None,
+        }
+    }
+
+    /// Returns the updates to apply now, oldest first.
+    fn offer(
+        &mut self,
+        conversation_id: &str,
+        content: &str,
+        done: bool,
+        now: std::time::Instant,
+    ) -> Vec<StreamingUpdate> {
+        let update = StreamingUpdate {
+            conversation_id: conversation_id.to_string(),
+            content: content.to_string(),
+            done,
+        };
+        let mut ready = Vec::new();
+        // A buffered snapshot for another conversation must land before we switch.
+        if let Some(pending) = self
+            .pending
+            .take_if(|pending| pending.conversation_id != conversation_id)
+        {
+            ready.push(pending);
+        }
+        if done {
+            // The final event supersedes anything buffered for this conversation.
+            self.pending = None;
+            ready.push(update);
+            self.last_applied = Some(now);
+        } else if self.window_elapsed(now) {
+            ready.push(update);
+            self.last_applied = Some(now);
+        } else {
+            self.pending = Some(update);
+        }
+        ready
+    }
+
+    /// Releases the buffered snapshot once its window has passed.
+    fn poll(&mut self, now: std::time::Instant) -> Option<StreamingUpdate> {
+        if self.pending.is_none() || !self.window_elapsed(now) {
+            return None;
+        }
+        self.last_applied = Some(now);
+        self.pending.take()
+    }
+
+    fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    fn window_elapsed(&self, now: std::time::Instant) -> bool {
+        self.last_applied
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.window)
+    }
+}
+
 #[derive(Clone)]
 struct LiveToolProgress {
     conversation_id: String,
     tool_name: String,
     output_preview: String,
     subtask_id: Option<String>,
+    /// (iteration, max_iterations) when the backend reports loop depth.
+    iteration_budget: Option<(u32, u32)>,
+    tool_call_id: Option<String>,
+    /// Headline argument (command, URL, path, …) from `tool_argument_key`.
+    argument_key: Option<String>,
+}
+
+/// Streaming tools resend the accumulated output under one `tool_call_id`;
+/// replace that entry in place so a build log scrolls in a single row.
+fn upsert_live_tool_progress(entries: &mut Vec<LiveToolProgress>, entry: LiveToolProgress) {
+    if let Some(tool_call_id) = entry.tool_call_id.as_deref() {
+        if let Some(existing) = entries.iter_mut().rev().find(|existing| {
+            existing.conversation_id == entry.conversation_id
+                && existing.tool_call_id.as_deref() == Some(tool_call_id)
+        }) {
+            // Only the first chunk of a streaming call has to carry its arguments.
+            let argument_key = entry.argument_key.or(existing.argument_key.take());
+            *existing = LiveToolProgress {
+                argument_key,
+                ..entry
+            };
+            return;
+        }
+    }
+    entries.push(entry);
+}
+
+/// "Remember" dialog state: editable text pinned to working memory on confirm.
+struct RememberDraft {
+    conversation_id: String,
+    text: String,
+    /// Scope the entry to `conversation_id` instead of global.
+    this_chat_only: bool,
+}
+
+/// A tool's mid-turn question and the operator's draft answer.
+struct PendingToolBatch {
+    batch: ToolBatch,
+    allowed: Vec<bool>,
+    /// Set once a poll shows the conversation awaiting approval; see `still_awaiting`.
+    seen_awaiting: bool,
+}
+
+struct PendingToolInput {
+    conversation_id: String,
+    turn_id: String,
+    tool_call_id: String,
+    tool_name: String,
+    prompt: String,
+    answer: String,
+    /// Set once a poll shows the conversation awaiting input; see `still_awaiting`.
+    seen_awaiting: bool,
+}
+
+struct ActionExplanationWindow {
+    action: String,
+    explanation: Option<ActionExplanation>,
+    error: Option<String>,
 }
 
 struct PromptInspectorWindow {
@@ -81,21 +382,27 @@ struct PromptInspectorWindow {
     turn_id: String,
     prompt_text: String,
     system_prompt_text: String,
+    seed: Option<u64>,
+    timing: Option<TurnTiming>,
     show_system_prompt: bool,
     highlight_sections: bool,
     error: Option<String>,
+    /// Feedback for the last "Copy as curl" attempt.
+    curl_status: Option<String>,
 }
 
 impl AgentApp {
     pub fn new(api_client: ApiClient, fallback_config: AgentConfig) -> Self {
         let runtime = tokio::runtime::Runtime::new().expect("UI tokio runtime");
-        let (event_tx, event_rx) = flume::unbounded();
+        let (event_tx, event_rx) = EventSink::bounded(EVENT_CHANNEL_CAPACITY);
+        let (poll_tx, poll_rx) = flume::unbounded();
 
         let event_client = api_client.clone();
         runtime.spawn(async move {
             event_client.stream_events_forever(event_tx).await;
         });
 
+        let mut config_load_error = None;
         let startup_config = match runtime.block_on(api_client.get_config()) {
             Ok(config) => config,
             Err(error) => {
@@ -103,6 +410,7 @@ impl AgentApp {
                     "Failed to load config from backend ({}); using local fallback",
                     error
                 );
+                config_load_error = Some(error.to_string());
                 fallback_config
             }
         };
@@ -118,49 +426,133 @@ impl AgentApp {
             }
         };
 
+        let ui_prefs = UiPrefs::load();
         let mut settings_panel = SettingsPanel::new(startup_config.clone());
         settings_panel.set_plugin_manifests(plugin_manifests);
+        settings_panel.set_display_settings(DisplaySettings {
+            limits: ui_prefs.limits,
+            plain_labels: ui_prefs.plain_labels,
+            chat_names: ui_prefs.chat_names,
+            operator_name: ui_prefs.operator_name.clone(),
+            long_reply_chars: ui_prefs.long_reply_chars,
+            prompt_token_budget: ui_prefs.prompt_token_budget,
+            idle_animation: ui_prefs.idle_animation,
+            mood_reactions: ui_prefs.mood_reactions,
+            agent_name: ui_prefs.agent_name.clone(),
+            sprite_fallback: ui_prefs.sprite_fallback.clone(),
+        });
+
+        let quick_ask_hotkey = match QuickAskHotkey::register() {
+            Ok(hotkey) => Some(hotkey),
+            Err(error) => {
+                tracing::warn!("Quick ask shortcut not available: {:#}", error);
+                None
+            }
+        };
 
         let mut app = Self {
             events: Vec::new(),
             event_rx,
+            poll_tx,
+            poll_rx,
+            database_transfer: None,
+            poll_tracker: PollTracker::default(),
             api_client,
             current_state: AgentVisualState::Idle,
             user_input: String::new(),
             runtime,
             settings_panel,
-            character_panel: CharacterPanel::new(startup_config),
+            character_panel: CharacterPanel::new(startup_config.clone()),
+            applied_config: startup_config,
             avatars: None,
             avatars_loaded: false,
             conversations: Vec::new(),
             active_conversation_id: DEFAULT_CHAT_CONVERSATION_ID.to_string(),
             chat_history: Vec::new(),
             chat_media_cache: super::chat::ChatMediaCache::new(),
+            turn_tool_calls: HashMap::new(),
+            chat_scroll_states: HashMap::new(),
             live_tool_progress: Vec::new(),
             streaming_chat_preview: None,
             prompt_inspector: None,
             last_chat_refresh: std::time::Instant::now(),
             show_activity_panel: true,
             pending_approvals: Vec::new(),
+            pending_config_proposals: Vec::new(),
+            pending_tool_batches: Vec::new(),
+            pending_tool_inputs: Vec::new(),
             last_orientation: None,
             last_action: None,
+            last_action_id: None,
+            action_explanation: None,
+            model_compare: ModelCompareWindow::default(),
+            conversation_summary: ConversationSummaryWindow::default(),
+            diagnostics: DiagnosticsWindow::default(),
+            quick_ask: QuickAskWindow::default(),
+            quick_ask_hotkey,
+            conversation_organizer: ConversationOrganizer::default(),
+            archived_conversations: ArchivedConversationsWindow::default(),
             last_journal: None,
             live_stream_text: None,
             token_monitor: TokenMonitorState::new(),
             visual_state_since: None,
             current_activity: None,
             loose_mode: false,
+            autonomy_paused: false,
             current_intention: None,
             show_loose_arm_confirmation: false,
             confirm_delete_conversation_id: None,
             rename_conversation: None,
+            remember_draft: None,
+            conversation_details: None,
+            conversation_tag_filter: None,
             event_detail_popup: None,
+            command_palette: CommandPalette::default(),
+            working_memory: None,
+            working_memory_error: None,
+            upcoming: None,
+            upcoming_error: None,
+            confirm_clear_working_memory: false,
+            working_memory_active_scope_only: false,
+            ui_prefs,
+            window_placement: None,
+            window_placement_checked: false,
+            streaming_coalescer: StreamingCoalescer::new(STREAMING_COALESCE_WINDOW),
+            idle_heartbeat: IdleHeartbeat::new(IDLE_BEAT_INTERVAL, std::time::Instant::now()),
+            mood_reaction: None,
+            window_title: None,
+            config_load_error,
+            iteration_limit_hint: None,
+            auth_failed: false,
+            last_status_error: None,
+            auth_token_draft: String::new(),
+            failed_sends: HashMap::new(),
+            compactions: HashMap::new(),
+            next_failed_send_id: 0,
         };
 
+        if let Some(error) = app.config_load_error.clone() {
+            app.push_ui_error(format!(
+                "Failed to load config from backend ({}); settings show a local fallback",
+                error
+            ));
+        }
+
         app.refresh_status();
         app.refresh_conversations();
         app.refresh_chat_history();
         app.refresh_scheduled_jobs();
+        app.refresh_config_proposals();
+
+        // Fire-and-forget: a greeting, if any, arrives as a normal chat message.
+        let session_client = app.api_client.clone();
+        app.runtime.spawn(async move {
+            match session_client.notify_session_start().await {
+                Ok(true) => tracing::info!("Backend queued a session greeting"),
+                Ok(false) => {}
+                Err(error) => tracing::debug!("Session start notification failed: {}", error),
+            }
+        });
         app
     }
 
@@ -168,28 +560,96 @@ impl AgentApp {
         self.events.push(FrontendEvent::Error(message.into()));
     }
 
+    /// Writes a `DebugDump` of the live UI state and reveals the file.
+    fn save_debug_dump(&mut self) {
+        let connection = ConnectionSnapshot {
+            backend_url: self.api_client.base_url().to_string(),
+            auth_failed: self.auth_failed,
+            last_status_error: self.last_status_error.clone(),
+        };
+        let result = DebugDump::new(
+            connection,
+            self.current_state.clone(),
+            &self.events,
+            &self.applied_config,
+        )
+        .and_then(|mut dump| {
+            dump.current_activity = self.current_activity.clone();
+            dump.last_orientation = self.last_orientation.clone();
+            dump.last_action = self.last_action.clone();
+            dump.last_journal = self.last_journal.clone();
+            dump.write_to(&debug_dump_dir())
+        });
+        match result {
+            Ok(path) => {
+                tracing::info!("Saved debug dump to {}", path.display());
+                if let Err(error) = file_manager::reveal(&path, true) {
+                    tracing::warn!("Failed to reveal {}: {}", path.display(), error);
+                }
+                self.events.push(FrontendEvent::Observation(format!(
+                    "Saved debug dump to {}",
+                    path.display()
+                )));
+            }
+            Err(error) => {
+                tracing::error!("Failed to save debug dump: {:#}", error);
+                self.push_ui_error(format!("Failed to save debug dump: {:#}", error));
+            }
+        }
+    }
+
     fn refresh_status(&mut self) {
-        match self.runtime.block_on(self.api_client.get_agent_status()) {
+        let result = self.block_on_api(self.api_client.get_agent_status());
+        self.apply_status(result);
+    }
+
+    fn apply_status(&mut self, result: anyhow::Result<AgentRuntimeStatus>) {
+        match result {
             Ok(status) => {
                 self.current_state = status.visual_state;
                 self.visual_state_since = status.visual_state_since;
                 self.current_activity = status.current_activity;
                 self.loose_mode = status.loose_mode;
                 self.current_intention = status.current_intention;
+                self.autonomy_paused = status.autonomy_paused;
+                self.auth_failed = false;
+                self.last_status_error = None;
             }
             Err(error) => {
+                if is_auth_error(&error) {
+                    self.auth_failed = true;
+                }
+                self.last_status_error = Some(format!("{:#}", error));
                 tracing::warn!("Failed to refresh backend status: {}", error);
             }
         }
     }
 
     fn refresh_conversations(&mut self) {
-        match self
-            .runtime
-            .block_on(self.api_client.list_conversations(100))
-        {
+        self.poll_tracker.invalidate();
+        let result = self.block_on_api(self.api_client.list_conversations(100));
+        self.apply_conversations(result);
+    }
+
+    fn apply_conversations(&mut self, result: anyhow::Result<Vec<ChatConversation>>) {
+        match result {
             Ok(conversations) => {
                 self.conversations = conversations;
+                let conversations = &self.conversations;
+                self.pending_tool_inputs.retain_mut(|pending| {
+                    let state = conversations
+                        .iter()
+                        .find(|c| c.id == pending.conversation_id)
+                        .map(|c| &c.runtime_state);
+                    still_awaiting(&mut pending.seen_awaiting, state)
+                });
+                self.pending_tool_batches.retain_mut(|pending| {
+                    let state = conversations
+                        .iter()
+                        .find(|c| c.id == pending.batch.conversation_id)
+                        .map(|c| &c.runtime_state);
+                    still_awaiting(&mut pending.seen_awaiting, state)
+                });
                 if self
                     .conversations
                     .iter()
@@ -209,15 +669,42 @@ impl AgentApp {
         }
     }
 
+    /// Runs an API call on the UI thread. reqwest bounds each request; this also
+    /// bounds calls that chain several requests, so a wedged backend surfaces as
+    /// `ApiError::Timeout` instead of a frozen window.
+    fn block_on_api<T>(
+        &self,
+        call: impl std::future::Future<Output = anyhow::Result<T>>,
+    ) -> anyhow::Result<T> {
+        block_on_bounded(&self.runtime, self.api_client.request_timeout() * 2, call)
+    }
+
     fn refresh_chat_history(&mut self) {
+        self.poll_tracker.invalidate();
         let conversation_id = self.active_conversation_id.clone();
-        match self
-            .runtime
-            .block_on(self.api_client.list_messages(&conversation_id, 200))
-        {
+        let result = self.block_on_api(self.api_client.list_messages(&conversation_id, 200));
+        self.apply_chat_history(&conversation_id, result);
+    }
+
+    fn apply_chat_history(
+        &mut self,
+        conversation_id: &str,
+        result: anyhow::Result<Vec<ChatMessage>>,
+    ) {
+        if conversation_id != self.active_conversation_id {
+            // The user switched chats while this was in flight.
+            return;
+        }
+        match result {
             Ok(history) => {
                 self.chat_history = history;
             }
+            Err(error) if matches!(ApiError::of(&error), Some(ApiError::NotFound { .. })) => {
+                // Deleted from another client: fall back to a conversation that exists.
+                tracing::info!("Conversation {} no longer exists", conversation_id);
+                self.chat_history.clear();
+                self.refresh_conversations();
+            }
             Err(error) => {
                 tracing::warn!(
                     "Failed to refresh chat history for {}: {}",
@@ -229,11 +716,111 @@ impl AgentApp {
         }
     }
 
+    /// Starts the periodic status/conversations/history poll on the runtime.
+    /// Skipped while the previous poll is still outstanding so a slow backend
+    /// cannot pile up requests.
+    fn start_background_poll(&mut self, ctx: &egui::Context) {
+        let Some(epoch) = self.poll_tracker.begin() else {
+            return;
+        };
+        let client = self.api_client.clone();
+        let poll_tx = self.poll_tx.clone();
+        let conversation_id = self.active_conversation_id.clone();
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let (status, conversations, history) = tokio::join!(
+                client.get_agent_status(),
+                client.list_conversations(100),
+                client.list_messages(&conversation_id, 200),
+            );
+            let _ = poll_tx.send(PolledState {
+                epoch,
+                status,
+                conversations,
+                conversation_id,
+                history,
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    /// Comparisons run two full turns, so they go through the runtime rather
+    /// than `block_on_api`.
+    fn start_model_comparison(&mut self, request: CompareModelsRequest, ctx: &egui::Context) {
+        let (result_tx, result_rx) = flume::bounded(1);
+        let client = self.api_client.clone();
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let result = client
+                .compare_models(&request)
+                .await
+                .map_err(|error| format!("Comparison failed: {:#}", error));
+            let _ = result_tx.send(result);
+            ctx.request_repaint();
+        });
+        self.model_compare.start(result_rx);
+    }
+
+    fn open_conversation_summary(&mut self) {
+        let title = self
+            .conversations
+            .iter()
+            .find(|c| c.id == self.active_conversation_id)
+            .map(|c| c.title.clone())
+            .unwrap_or_else(|| "Default chat".to_string());
+        self.conversation_summary
+            .open_for(&self.active_conversation_id, &title);
+    }
+
+    fn start_conversation_summary(&mut self, request: SummarizeRequest, ctx: &egui::Context) {
+        let (result_tx, result_rx) = flume::bounded(1);
+        let client = self.api_client.clone();
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let result = client
+                .summarize_conversation(&request.conversation_id, request.save_to_journal)
+                .await
+                .map_err(|error| format!("Summary failed: {:#}", error));
+            let _ = result_tx.send(RecapOutcome {
+                conversation_id: request.conversation_id,
+                result,
+            });
+            ctx.request_repaint();
+        });
+        self.conversation_summary.start(result_rx);
+    }
+
+    fn start_diagnostics(&mut self, ctx: &egui::Context) {
+        if self.diagnostics.is_running() {
+            return;
+        }
+        let (result_tx, result_rx) = flume::bounded(1);
+        let client = self.api_client.clone();
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let health = client.health().await;
+            let server_checks = if health.is_ok() {
+                client.diagnostics().await
+            } else {
+                Ok(Vec::new())
+            };
+            let _ = result_tx.send(collect_checks(&health, server_checks));
+            ctx.request_repaint();
+        });
+        self.diagnostics.start(result_rx);
+    }
+
+    fn apply_polled_state(&mut self, polled: PolledState) {
+        let fresh = self.poll_tracker.finish(polled.epoch);
+        self.apply_status(polled.status);
+        if fresh {
+            self.apply_conversations(polled.conversations);
+            self.apply_chat_history(&polled.conversation_id, polled.history);
+        }
+    }
+
     fn refresh_scheduled_jobs(&mut self) {
-        match self
-            .runtime
-            .block_on(self.api_client.list_scheduled_jobs(200))
-        {
+        match self.block_on_api(self.api_client.list_scheduled_jobs(200)) {
             Ok(jobs) => {
                 self.settings_panel.set_scheduled_jobs(jobs);
                 self.settings_panel.set_scheduled_jobs_error(None);
@@ -246,6 +833,326 @@ impl AgentApp {
         }
     }
 
+    fn refresh_tools(&mut self) {
+        match self.block_on_api(self.api_client.list_tools()) {
+            Ok(tools) => {
+                self.settings_panel.set_tools(tools);
+                self.settings_panel.set_tools_error(None);
+            }
+            Err(error) => {
+                tracing::warn!("Failed to refresh tools: {}", error);
+                self.settings_panel
+                    .set_tools_error(Some(format!("Failed to load tools: {}", error)));
+            }
+        }
+    }
+
+    fn refresh_working_memory(&mut self) {
+        match self.block_on_api(self.api_client.list_working_memory()) {
+            Ok(entries) => {
+                self.working_memory = Some(entries);
+                self.working_memory_error = None;
+            }
+            Err(error) => {
+                tracing::warn!("Failed to load working memory: {}", error);
+                self.working_memory = Some(Vec::new());
+                self.working_memory_error = Some(format!("Failed to load: {}", error));
+            }
+        }
+    }
+
+    fn refresh_upcoming(&mut self) {
+        match self.block_on_api(self.api_client.list_intentions()) {
+            Ok(items) => {
+                self.upcoming = Some(items);
+                self.upcoming_error = None;
+            }
+            Err(error) => {
+                tracing::warn!("Failed to load upcoming intentions: {}", error);
+                self.upcoming = Some(Vec::new());
+                self.upcoming_error = Some(format!("Failed to load: {}", error));
+            }
+        }
+    }
+
+    fn decide_tool_batch(&mut self, index: usize) {
+        let PendingToolBatch { batch, allowed, .. } = &self.pending_tool_batches[index];
+        let decisions: Vec<ToolCallDecision> = batch
+            .calls
+            .iter()
+            .zip(allowed)
+            .map(|(call, allow)| ToolCallDecision {
+                tool_call_id: call.tool_call_id.clone(),
+                approve: *allow,
+            })
+            .collect();
+        match self.block_on_api(
+            self.api_client
+                .decide_tool_batch(&batch.turn_id, &decisions),
+        ) {
+            Ok(()) => {
+                self.pending_tool_batches.remove(index);
+            }
+            Err(error) if is_stale_operator_request(&error) => {
+                // Decided from another client, stopped, or the turn is gone.
+                tracing::info!("Tool batch is no longer waiting: {:#}", error);
+                self.pending_tool_batches.remove(index);
+            }
+            Err(error) => {
+                tracing::error!("Failed to send tool batch decision: {:#}", error);
+                self.push_ui_error(format!("Failed to send tool decisions: {:#}", error));
+            }
+        }
+    }
+
+    fn answer_tool_input(&mut self, index: usize) {
+        let pending = &self.pending_tool_inputs[index];
+        match self.block_on_api(self.api_client.provide_tool_input(
+            &pending.turn_id,
+            &pending.tool_call_id,
+            pending.answer.trim(),
+        )) {
+            Ok(()) => {
+                self.pending_tool_inputs.remove(index);
+            }
+            Err(error) if is_stale_operator_request(&error) => {
+                // Answered from another client, cancelled, or timed out.
+                tracing::info!("Tool question is no longer open: {:#}", error);
+                self.pending_tool_inputs.remove(index);
+            }
+            Err(error) => {
+                tracing::error!("Failed to answer tool input: {:#}", error);
+                self.push_ui_error(format!("Failed to send answer: {:#}", error));
+            }
+        }
+    }
+
+    fn delete_working_memory_entry(&mut self, key: &str, scope: Option<&str>) {
+        match self.block_on_api(self.api_client.delete_working_memory_entry(key, scope)) {
+            Ok(()) => self.refresh_working_memory(),
+            Err(error) => {
+                self.push_ui_error(format!("Failed to forget '{}': {}", key, error));
+            }
+        }
+    }
+
+    fn clear_working_memory(&mut self) {
+        match self.block_on_api(self.api_client.clear_working_memory()) {
+            Ok(cleared) => {
+                tracing::info!("Cleared {} working-memory entries", cleared);
+                self.refresh_working_memory();
+            }
+            Err(error) => self.push_ui_error(format!("Failed to clear working memory: {}", error)),
+        }
+    }
+
+    /// Drops every cached view of agent state after a factory reset, then
+    /// reloads the (now empty) conversation list from the backend.
+    fn clear_local_agent_state(&mut self) {
+        self.events.clear();
+        self.chat_history.clear();
+        self.turn_tool_calls.clear();
+        self.failed_sends.clear();
+        self.compactions.clear();
+        self.live_tool_progress.clear();
+        self.chat_scroll_states.clear();
+        self.streaming_chat_preview = None;
+        self.working_memory = None;
+        self.last_journal = None;
+        self.last_action = None;
+        self.last_action_id = None;
+        self.refresh_conversations();
+        self.refresh_chat_history();
+    }
+
+    /// Backups and restores can run for minutes (`DATABASE_TRANSFER_TIMEOUT`),
+    /// so they go through the runtime; `poll_database_transfer` applies the result.
+    fn start_database_transfer(&mut self, action: StorageAction, ctx: &egui::Context) {
+        if self.database_transfer.is_some() {
+            self.settings_panel
+                .set_storage_status(Some("A backup or restore is already running".to_string()));
+            return;
+        }
+        let status = match &action {
+            StorageAction::BackupTo(path) => format!("Backing up to {}…", path.display()),
+            StorageAction::RestoreFrom(path) => format!("Restoring from {}…", path.display()),
+            _ => return,
+        };
+        let (result_tx, result_rx) = flume::bounded(1);
+        let client = self.api_client.clone();
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let outcome = match action {
+                StorageAction::BackupTo(path) => match client.export_database(&path).await {
+                    Ok(bytes) => DatabaseTransferOutcome::BackedUp { path, bytes },
+                    Err(error) => DatabaseTransferOutcome::BackupFailed(format!("{:#}", error)),
+                },
+                StorageAction::RestoreFrom(path) => match client.restore_database(&path).await {
+                    Ok(()) => DatabaseTransferOutcome::Restored { path },
+                    Err(error) => DatabaseTransferOutcome::RestoreFailed(format!("{:#}", error)),
+                },
+                _ => return,
+            };
+            let _ = result_tx.send(outcome);
+            ctx.request_repaint();
+        });
+        self.database_transfer = Some(result_rx);
+        self.settings_panel.set_storage_status(Some(status));
+    }
+
+    fn poll_database_transfer(&mut self) {
+        let Some(outcome) = self
+            .database_transfer
+            .as_ref()
+            .and_then(|rx| rx.try_recv().ok())
+        else {
+            return;
+        };
+        self.database_transfer = None;
+        let status = match outcome {
+            DatabaseTransferOutcome::BackedUp { path, bytes } => format!(
+                "Backed up {} KB to {}",
+                bytes.div_ceil(1024),
+                path.display()
+            ),
+            DatabaseTransferOutcome::Restored { path } => {
+                self.refresh_conversations();
+                self.refresh_chat_history();
+                self.working_memory = None;
+                format!("Restored from {}", path.display())
+            }
+            DatabaseTransferOutcome::BackupFailed(error) => {
+                self.push_ui_error(format!("Database backup failed: {}", error));
+                format!("Backup failed: {}", error)
+            }
+            DatabaseTransferOutcome::RestoreFailed(error) => {
+                self.push_ui_error(format!("Database restore failed: {}", error));
+                format!("Restore failed: {}", error)
+            }
+        };
+        self.settings_panel.set_storage_status(Some(status));
+    }
+
+    fn apply_storage_actions(&mut self, actions: Vec<StorageAction>, ctx: &egui::Context) {
+        for action in actions {
+            match action {
+                StorageAction::BackupTo(_) | StorageAction::RestoreFrom(_) => {
+                    self.start_database_transfer(action, ctx);
+                }
+                StorageAction::ResetAgent => {
+                    let status = match self.block_on_api(self.api_client.reset_agent()) {
+                        Ok(()) => {
+                            self.clear_local_agent_state();
+                            "Agent reset".to_string()
+                        }
+                        Err(error) => {
+                            self.push_ui_error(format!("Agent reset failed: {:#}", error));
+                            format!("Reset failed: {}", error)
+                        }
+                    };
+                    self.settings_panel.set_storage_status(Some(status));
+                }
+                StorageAction::SetRetention(policy) => {
+                    match self.block_on_api(self.api_client.set_retention(&policy)) {
+                        Ok(status) => self.settings_panel.set_retention(Some(status)),
+                        Err(error) => {
+                            self.push_ui_error(format!(
+                                "Failed to save retention policy: {:#}",
+                                error
+                            ));
+                        }
+                    }
+                }
+                StorageAction::PruneNow => {
+                    let status = match self.block_on_api(self.api_client.prune_now()) {
+                        Ok(report) => {
+                            self.refresh_conversations();
+                            self.refresh_retention();
+                            format!("Pruned: {}", report)
+                        }
+                        Err(error) => {
+                            self.push_ui_error(format!("Pruning failed: {:#}", error));
+                            format!("Pruning failed: {}", error)
+                        }
+                    };
+                    self.settings_panel.set_storage_status(Some(status));
+                }
+            }
+        }
+    }
+
+    fn apply_tool_actions(&mut self, actions: Vec<ToolSettingsAction>) {
+        let mut should_refresh = false;
+        let mut toggled = false;
+        // The tool PUTs persist into AgentConfig, so the panel's copy goes stale.
+        let config_before = actions
+            .iter()
+            .any(|action| matches!(action, ToolSettingsAction::SetEnabled { .. }))
+            .then(|| self.block_on_api(self.api_client.get_config()).ok())
+            .flatten();
+
+        for action in actions {
+            match action {
+                ToolSettingsAction::Refresh => {
+                    should_refresh = true;
+                }
+                ToolSettingsAction::SetEnabled { tool_name, enabled } => {
+                    match self.block_on_api(self.api_client.set_tool_enabled(&tool_name, enabled)) {
+                        Ok(_) => {
+                            should_refresh = true;
+                            toggled = true;
+                        }
+                        Err(error) => {
+                            self.settings_panel.set_tools_error(Some(format!(
+                                "Failed to update tool '{}': {}",
+                                tool_name, error
+                            )));
+                            self.push_ui_error(format!(
+                                "Failed to update tool '{}': {}",
+                                tool_name, error
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        if toggled {
+            self.resync_config_after_tool_toggles(config_before);
+        }
+        if should_refresh {
+            self.refresh_tools();
+        }
+    }
+
+    fn resync_config_after_tool_toggles(&mut self, config_before: Option<AgentConfig>) {
+        let result = self
+            .block_on_api(self.api_client.get_config())
+            .and_then(|config_after| match config_before {
+                Some(config_before) => self
+                    .settings_panel
+                    .adopt_backend_changes(&config_before, &config_after)
+                    .map(|()| config_after),
+                None => {
+                    self.settings_panel.sync_from_config(config_after.clone());
+                    Ok(config_after)
+                }
+            });
+        match result {
+            Ok(config_after) => {
+                self.character_panel.config = config_after.clone();
+                self.applied_config = config_after;
+            }
+            Err(error) => {
+                tracing::warn!("Config re-sync after tool toggles failed: {:#}", error);
+                self.push_ui_error(format!(
+                    "Tool changes saved, but reloading the config failed: {}",
+                    error
+                ));
+            }
+        }
+    }
+
     fn apply_scheduled_job_actions(&mut self, actions: Vec<ScheduledJobAction>) {
         let mut should_refresh = false;
 
@@ -259,7 +1166,7 @@ impl AgentApp {
                     prompt,
                     interval_minutes,
                     enabled,
-                } => match self.runtime.block_on(self.api_client.create_scheduled_job(
+                } => match self.block_on_api(self.api_client.create_scheduled_job(
                     &name,
                     &prompt,
                     interval_minutes,
@@ -270,10 +1177,9 @@ impl AgentApp {
                                 enabled: Some(false),
                                 ..Default::default()
                             };
-                            if let Err(error) = self
-                                .runtime
-                                .block_on(self.api_client.update_scheduled_job(&job.id, &request))
-                            {
+                            if let Err(error) = self.block_on_api(
+                                self.api_client.update_scheduled_job(&job.id, &request),
+                            ) {
                                 self.settings_panel.set_scheduled_jobs_error(Some(format!(
                                     "Created schedule '{}' but failed to disable it: {}",
                                     name, error
@@ -310,9 +1216,7 @@ impl AgentApp {
                         interval_minutes: Some(interval_minutes),
                         enabled: Some(enabled),
                     };
-                    match self
-                        .runtime
-                        .block_on(self.api_client.update_scheduled_job(&job_id, &request))
+                    match self.block_on_api(self.api_client.update_scheduled_job(&job_id, &request))
                     {
                         Ok(_) => {
                             should_refresh = true;
@@ -330,10 +1234,7 @@ impl AgentApp {
                     }
                 }
                 ScheduledJobAction::Delete { job_id } => {
-                    match self
-                        .runtime
-                        .block_on(self.api_client.delete_scheduled_job(&job_id))
-                    {
+                    match self.block_on_api(self.api_client.delete_scheduled_job(&job_id)) {
                         Ok(()) => {
                             should_refresh = true;
                         }
@@ -360,38 +1261,556 @@ impl AgentApp {
     fn send_chat_message(&mut self, content: &str) {
         let active_conversation = self.active_conversation_id.clone();
         self.clear_live_tool_progress(&active_conversation);
+        self.send_message_to(&active_conversation, content);
+    }
 
-        match self
-            .runtime
-            .block_on(self.api_client.send_message(&active_conversation, content))
-        {
+    /// Sends `content`; on failure the text is kept as an inline failed message
+    /// instead of only being logged. Returns whether the backend accepted it.
+    fn send_message_to(&mut self, active_conversation: &str, content: &str) -> bool {
+        match self.block_on_api(self.api_client.send_message(active_conversation, content)) {
             Ok(_message_id) => {
                 tracing::info!("Sent chat message to backend: {}", content);
                 self.token_monitor.on_human_interaction();
                 self.refresh_conversations();
                 self.refresh_chat_history();
+                true
             }
             Err(error) => {
                 tracing::error!("Failed to send chat message: {}", error);
                 self.push_ui_error(format!("Failed to send message: {}", error));
+                let failed = FailedChatSend {
+                    id: self.next_failed_send_id,
+                    content: content.to_string(),
+                    error: format!("{:#}", error),
+                    retryable: is_retryable_error(&error),
+                };
+                self.next_failed_send_id += 1;
+                self.failed_sends
+                    .entry(active_conversation.to_string())
+                    .or_default()
+                    .push(failed);
+                false
             }
         }
     }
 
-    fn open_prompt_inspector_for_turn(&mut self, turn_id: &str) {
-        match self
-            .runtime
-            .block_on(self.api_client.get_turn_prompt(turn_id))
+    fn open_quick_ask(&mut self) {
+        self.quick_ask
+            .open_with(read_clipboard_text().unwrap_or_default());
+    }
+
+    /// Posts to the Quick Ask conversation (creating it on first use) without
+    /// touching the main window's active conversation.
+    fn send_quick_ask(&mut self, message: &str) {
+        let stored = self.ui_prefs.quick_ask_conversation_id.clone();
+        let existing = match route_quick_ask(stored.as_deref(), &self.conversations) {
+            QuickAskRoute::Existing(id) => Some(id),
+            QuickAskRoute::Unlisted(id) => match self.find_unlisted_quick_ask(&id) {
+                Ok(found) => found.then_some(id),
+                Err(error) => {
+                    tracing::error!("Failed to look up quick ask conversation: {}", error);
+                    self.quick_ask
+                        .failed(format!("Failed to find the Quick Ask chat: {:#}", error));
+                    return;
+                }
+            },
+            QuickAskRoute::Create => None,
+        };
+        let conversation_id = match existing {
+            Some(id) => id,
+            None => {
+                match self.block_on_api(self.api_client.create_conversation(Some(QUICK_ASK_TITLE)))
+                {
+                    Ok(conversation) => {
+                        self.ui_prefs.quick_ask_conversation_id = Some(conversation.id.clone());
+                        if let Err(error) = self.ui_prefs.save() {
+                            tracing::warn!("Failed to persist quick ask conversation: {}", error);
+                        }
+                        conversation.id
+                    }
+                    Err(error) => {
+                        tracing::error!("Failed to create quick ask conversation: {}", error);
+                        self.quick_ask
+                            .failed(format!("Failed to create conversation: {:#}", error));
+                        return;
+                    }
+                }
+            }
+        };
+        if self.send_message_to(&conversation_id, message) {
+            self.quick_ask.sent(&conversation_id);
+        } else {
+            self.quick_ask
+                .failed("Send failed; the message is kept in the Quick Ask chat.".to_string());
+        }
+    }
+
+    /// The stored quick-ask conversation is missing from the main list, which is
+    /// capped and hides archived chats. Un-archives it if the retention pass
+    /// archived it; `false` means it was deleted.
+    fn find_unlisted_quick_ask(&mut self, conversation_id: &str) -> anyhow::Result<bool> {
+        let conversation =
+            match self.block_on_api(self.api_client.get_conversation(conversation_id)) {
+                Ok(conversation) => conversation,
+                Err(error) if matches!(ApiError::of(&error), Some(ApiError::NotFound { .. })) => {
+                    return Ok(false);
+                }
+                Err(error) => return Err(error),
+            };
+        if conversation.archived_at.is_some() {
+            self.block_on_api(
+                self.api_client
+                    .set_conversation_archived(conversation_id, false),
+            )?;
+            self.refresh_conversations();
+        }
+        Ok(true)
+    }
+
+    fn retry_failed_send(&mut self, failed_id: u64) {
+        let conversation_id = self.active_conversation_id.clone();
+        if let Some(failed) = take_failed_send(&mut self.failed_sends, &conversation_id, failed_id)
         {
+            self.send_message_to(&conversation_id, &failed.content);
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        match self.block_on_api(self.api_client.toggle_pause()) {
+            Ok(paused) => {
+                self.current_state = if paused {
+                    AgentVisualState::Paused
+                } else {
+                    AgentVisualState::Idle
+                };
+            }
+            Err(error) => {
+                tracing::error!("Failed to toggle pause: {}", error);
+                self.push_ui_error(format!("Failed to toggle pause: {}", error));
+            }
+        }
+    }
+
+    fn toggle_autonomy_pause(&mut self) {
+        match self.block_on_api(self.api_client.set_autonomy_paused(!self.autonomy_paused)) {
+            Ok(paused) => self.autonomy_paused = paused,
+            Err(error) => {
+                tracing::error!("Failed to toggle autonomy pause: {}", error);
+                self.push_ui_error(format!("Failed to toggle autonomy pause: {}", error));
+            }
+        }
+    }
+
+    fn trigger_cycle(&mut self) {
+        match self.block_on_api(self.api_client.trigger_cycle()) {
+            Ok(true) => {
+                self.current_state = AgentVisualState::Thinking;
+            }
+            Ok(false) => {
+                self.events.push(FrontendEvent::Observation(
+                    "A cycle is already running; Think now ignored".to_string(),
+                ));
+            }
+            Err(error) => {
+                tracing::error!("Failed to trigger cycle: {}", error);
+                self.push_ui_error(format!("Failed to trigger cycle: {}", error));
+            }
+        }
+    }
+
+    fn stop_active_turn(&mut self) {
+        match self.block_on_api(self.api_client.stop_agent_turn()) {
+            Ok(_) => {
+                let active = self.active_conversation_id.clone();
+                self.streaming_chat_preview = None;
+                self.clear_live_tool_progress(&active);
+                // A stopped turn no longer waits on the operator.
+                self.pending_tool_batches
+                    .retain(|pending| pending.batch.conversation_id != active);
+                self.pending_tool_inputs
+                    .retain(|pending| pending.conversation_id != active);
+                self.refresh_conversations();
+                self.refresh_chat_history();
+                self.current_state = AgentVisualState::Idle;
+            }
+            Err(error) => {
+                tracing::error!("Failed to stop active turn: {}", error);
+                self.push_ui_error(format!("Failed to stop active turn: {}", error));
+            }
+        }
+    }
+
+    fn open_settings(&mut self) {
+        self.settings_panel.open();
+        self.refresh_scheduled_jobs();
+        self.refresh_tools();
+        self.refresh_storage_info();
+        self.refresh_retention();
+        self.refresh_config_flags();
+        self.refresh_config_history();
+    }
+
+    fn refresh_config_flags(&mut self) {
+        match self.block_on_api(self.api_client.get_config_flags()) {
+            Ok(flags) => self.settings_panel.set_config_flags(Some(flags)),
+            Err(error) => {
+                tracing::warn!("Failed to load config flags: {}", error);
+                self.settings_panel.set_config_flags(None);
+            }
+        }
+    }
+
+    fn apply_config_flags(&mut self, flags: ConfigFlags) {
+        match self.block_on_api(self.api_client.set_config_flags(&flags)) {
+            Ok(saved) => self.settings_panel.set_config_flags(Some(saved)),
+            Err(error) => {
+                tracing::error!("Failed to save config flags: {:#}", error);
+                self.push_ui_error(format!("Failed to save config flags: {:#}", error));
+            }
+        }
+    }
+
+    fn refresh_config_proposals(&mut self) {
+        match self.block_on_api(self.api_client.list_config_proposals()) {
+            Ok(proposals) => self.pending_config_proposals = proposals,
+            Err(error) => tracing::warn!("Failed to load config proposals: {}", error),
+        }
+    }
+
+    fn resolve_config_proposal(&mut self, id: &str, approve: bool) {
+        match self.block_on_api(self.api_client.resolve_config_proposal(id, approve)) {
+            Ok(()) => {
+                self.pending_config_proposals
+                    .retain(|proposal| proposal.id != id);
+                if approve {
+                    // The backend applied it; pull the new config into the panels.
+                    self.retry_config_load();
+                    self.refresh_config_history();
+                }
+            }
+            Err(error) => {
+                tracing::error!("Failed to resolve config proposal {}: {:#}", id, error);
+                self.push_ui_error(format!("Failed to resolve config proposal: {:#}", error));
+            }
+        }
+    }
+
+    fn refresh_config_history(&mut self) {
+        match self.block_on_api(self.api_client.config_history()) {
+            Ok(history) => self.settings_panel.set_config_history(Some(history)),
+            Err(error) => {
+                tracing::warn!("Failed to load config history: {}", error);
+                self.settings_panel.set_config_history(None);
+            }
+        }
+    }
+
+    fn refresh_retention(&mut self) {
+        match self.block_on_api(self.api_client.get_retention()) {
+            Ok(status) => self.settings_panel.set_retention(Some(status)),
+            Err(error) => {
+                tracing::warn!("Failed to load retention policy: {}", error);
+                self.settings_panel.set_retention(None);
+            }
+        }
+    }
+
+    fn refresh_storage_info(&mut self) {
+        match self.block_on_api(self.api_client.get_storage_info()) {
+            Ok(info) => self.settings_panel.set_storage_info(Some(info)),
+            Err(error) => {
+                tracing::warn!("Failed to load storage info: {}", error);
+                self.settings_panel.set_storage_info(None);
+            }
+        }
+    }
+
+    fn begin_rename_active_conversation(&mut self) {
+        let current_title = self
+            .conversations
+            .iter()
+            .find(|c| c.id == self.active_conversation_id)
+            .map(|c| c.title.clone())
+            .unwrap_or_default();
+        self.rename_conversation = Some((self.active_conversation_id.clone(), current_title));
+    }
+
+    fn run_palette_action(&mut self, action: PaletteAction) {
+        match action {
+            PaletteAction::NewChat => self.create_new_conversation(),
+            PaletteAction::ImportChat => self.import_conversation_from_file(),
+            PaletteAction::OrganizeChats => self.conversation_organizer.open = true,
+            PaletteAction::ArchivedChats => self.open_archived_conversations(),
+            PaletteAction::SummarizeChat => self.open_conversation_summary(),
+            PaletteAction::RenameChat => self.begin_rename_active_conversation(),
+            PaletteAction::DeleteChat => {
+                self.confirm_delete_conversation_id = Some(self.active_conversation_id.clone());
+            }
+            PaletteAction::TogglePause => self.toggle_pause(),
+            PaletteAction::ToggleAutonomyPause => self.toggle_autonomy_pause(),
+            PaletteAction::StopTurn => self.stop_active_turn(),
+            PaletteAction::ThinkNow => self.trigger_cycle(),
+            PaletteAction::OpenSettings => self.open_settings(),
+            PaletteAction::OpenCharacter => self.character_panel.show = true,
+            PaletteAction::ToggleActivityPanel => {
+                self.show_activity_panel = !self.show_activity_panel;
+            }
+            PaletteAction::ToggleFocusMode => self.toggle_focus_mode(),
+            PaletteAction::QuickAsk => self.open_quick_ask(),
+            PaletteAction::DebugDump => self.save_debug_dump(),
+            PaletteAction::CompareModels => {
+                let configured_model = self.settings_panel.config.llm_model.clone();
+                self.model_compare.open(&configured_model);
+            }
+        }
+    }
+
+    fn toggle_focus_mode(&mut self) {
+        self.ui_prefs.focus_mode = !self.ui_prefs.focus_mode;
+        if let Err(error) = self.ui_prefs.save() {
+            tracing::warn!("Failed to persist UI prefs: {}", error);
+        }
+    }
+
+    fn apply_display_settings(&mut self, mut display: DisplaySettings) {
+        // The Display tab keeps a row per state; only real overrides are stored.
+        display
+            .sprite_fallback
+            .retain(|_, glyph| !glyph.trim().is_empty());
+        if self.ui_prefs.limits == display.limits
+            && self.ui_prefs.plain_labels == display.plain_labels
+            && self.ui_prefs.chat_names == display.chat_names
+            && self.ui_prefs.operator_name == display.operator_name
+            && self.ui_prefs.long_reply_chars == display.long_reply_chars
+            && self.ui_prefs.prompt_token_budget == display.prompt_token_budget
+            && self.ui_prefs.idle_animation == display.idle_animation
+            && self.ui_prefs.mood_reactions == display.mood_reactions
+            && self.ui_prefs.agent_name == display.agent_name
+            && self.ui_prefs.sprite_fallback == display.sprite_fallback
+        {
+            return;
+        }
+        self.ui_prefs.limits = display.limits;
+        self.ui_prefs.plain_labels = display.plain_labels;
+        self.ui_prefs.chat_names = display.chat_names;
+        self.ui_prefs.operator_name = display.operator_name;
+        self.ui_prefs.long_reply_chars = display.long_reply_chars;
+        self.ui_prefs.prompt_token_budget = display.prompt_token_budget;
+        self.ui_prefs.idle_animation = display.idle_animation;
+        self.ui_prefs.mood_reactions = display.mood_reactions;
+        self.ui_prefs.agent_name = display.agent_name;
+        self.ui_prefs.sprite_fallback = display.sprite_fallback;
+        if let Err(error) = self.ui_prefs.save() {
+            tracing::warn!("Failed to persist UI prefs: {}", error);
+        }
+    }
+
+    /// Runs once the viewport reports its monitor. If the display layout changed
+    /// since the placement was saved, moves an unreachable window back on screen.
+    fn check_window_placement(&mut self, ctx: &egui::Context) {
+        let (outer_rect, inner_rect, monitor_size) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (
+                viewport.outer_rect,
+                viewport.inner_rect,
+                viewport.monitor_size,
+            )
+        });
+        let (Some(outer_rect), Some(inner_rect)) = (outer_rect, inner_rect) else {
+            return;
+        };
+        self.window_placement = Some(WindowPlacement {
+            position: outer_rect.min.into(),
+            inner_size: inner_rect.size().into(),
+            monitor_size: monitor_size.map(Into::into),
+        });
+
+        if self.window_placement_checked {
+            return;
+        }
+        let Some(monitor_size) = monitor_size else {
+            return;
+        };
+        self.window_placement_checked = true;
+        if self.ui_prefs.window.is_none() {
+            return;
+        }
+
+        // egui only reports the size of the window's current monitor, so treat
+        // it as the primary display at the origin, as `center_on_screen` does.
+        let primary = egui::Rect::from_min_size(egui::Pos2::ZERO, monitor_size);
+        let safe = crate::safe_window_bounds(outer_rect, &[primary]);
+        if safe == outer_rect {
+            return;
+        }
+        tracing::info!(
+            "Restored window at {:?} is off-screen; moving it to {:?}",
+            outer_rect,
+            safe
+        );
+        ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(safe.min));
+        if safe.size() != outer_rect.size() {
+            let decorations = outer_rect.size() - inner_rect.size();
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(safe.size() - decorations));
+        }
+    }
+
+    fn continue_chat_message(&mut self, message_id: &str) {
+        let active_conversation = self.active_conversation_id.clone();
+        self.clear_live_tool_progress(&active_conversation);
+
+        match self.block_on_api(
+            self.api_client
+                .continue_message(&active_conversation, message_id),
+        ) {
+            Ok(_message_id) => {
+                self.token_monitor.on_human_interaction();
+                self.refresh_conversations();
+                self.refresh_chat_history();
+            }
+            Err(error) => {
+                tracing::error!("Failed to continue message {}: {}", message_id, error);
+                self.push_ui_error(format!("Failed to continue message: {}", error));
+            }
+        }
+    }
+
+    fn brand_name(&self) -> String {
+        brand_name(
+            &self.ui_prefs.agent_name,
+            &self.applied_config.character_name,
+        )
+    }
+
+    /// Keeps the OS window title on the current brand name, e.g. after the
+    /// character card changes.
+    fn sync_window_title(&mut self, ctx: &egui::Context) {
+        let title = self.brand_name();
+        if self.window_title.as_deref() != Some(title.as_str()) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.window_title = Some(title);
+        }
+    }
+
+    /// The backend's visual state, unless a conversation is blocked on the
+    /// operator. The active conversation's phase wins; otherwise any conversation
+    /// awaiting approval shows the waiting state.
+    fn displayed_visual_state(&self) -> AgentVisualState {
+        let active_override = self
+            .conversations
+            .iter()
+            .find(|c| c.id == self.active_conversation_id)
+            .and_then(|c| c.runtime_state.visual_state_override());
+        active_override
+            .or_else(|| {
+                self.conversations
+                    .iter()
+                    .find_map(|c| c.runtime_state.visual_state_override())
+            })
+            .unwrap_or_else(|| match &self.mood_reaction {
+                Some(reaction)
+                    if self.current_state == AgentVisualState::Idle
+                        && reaction.is_active(std::time::Instant::now()) =>
+                {
+                    reaction.state.clone()
+                }
+                _ => self.current_state.clone(),
+            })
+    }
+
+    /// Forks the active conversation at `message_id` and switches to the branch.
+    fn branch_conversation_at(&mut self, message_id: &str) {
+        let source = self.active_conversation_id.clone();
+        match self.block_on_api(self.api_client.fork_conversation(&source, message_id)) {
+            Ok(branch) => {
+                self.active_conversation_id = branch.id;
+                self.user_input.clear();
+                self.streaming_chat_preview = None;
+                self.refresh_conversations();
+                self.refresh_chat_history();
+            }
+            Err(error) => {
+                tracing::error!("Failed to branch conversation {}: {:#}", source, error);
+                self.push_ui_error(format!("Failed to branch conversation: {:#}", error));
+            }
+        }
+    }
+
+    fn rate_turn(&mut self, turn_id: &str, rating: Option<TurnRating>) {
+        match self.block_on_api(self.api_client.rate_turn(turn_id, rating)) {
+            Ok(()) => {
+                // Reflect it now rather than waiting for the next history poll.
+                for message in self
+                    .chat_history
+                    .iter_mut()
+                    .filter(|message| message.turn_id.as_deref() == Some(turn_id))
+                {
+                    message.rating = rating;
+                }
+            }
+            Err(error) => {
+                tracing::error!("Failed to rate turn {}: {:#}", turn_id, error);
+                self.push_ui_error(format!("Failed to save rating: {:#}", error));
+            }
+        }
+    }
+
+    fn remember_text(&mut self, text: &str, scope: Option<&str>) {
+        match self.block_on_api(self.api_client.remember(text, scope)) {
+            Ok(entry) => {
+                tracing::info!("Pinned working-memory entry {}", entry.key);
+                self.working_memory = None;
+            }
+            Err(error) => {
+                tracing::error!("Failed to pin message to working memory: {:#}", error);
+                self.push_ui_error(format!("Failed to remember message: {:#}", error));
+            }
+        }
+    }
+
+    fn load_turn_tool_calls(&mut self, turn_id: &str) {
+        let records = match self.block_on_api(self.api_client.get_turn_tools(turn_id)) {
+            Ok(records) => records,
+            Err(error) => {
+                tracing::warn!("Failed to fetch tool calls for turn {}: {}", turn_id, error);
+                self.push_ui_error(format!("Failed to load turn tool history: {}", error));
+                Vec::new()
+            }
+        };
+        // Cache failures as empty too so an open header doesn't refetch every frame.
+        self.turn_tool_calls.insert(turn_id.to_string(), records);
+    }
+
+    fn open_action_explanation(&mut self, action_id: &str, action: &str) {
+        let result = self.block_on_api(self.api_client.explain_action(action_id));
+        self.action_explanation = Some(match result {
+            Ok(explanation) => ActionExplanationWindow {
+                action: action.to_string(),
+                explanation: Some(explanation),
+                error: None,
+            },
+            Err(error) => ActionExplanationWindow {
+                action: action.to_string(),
+                explanation: None,
+                error: Some(format!("{:#}", error)),
+            },
+        });
+    }
+
+    fn open_prompt_inspector_for_turn(&mut self, turn_id: &str) {
+        match self.block_on_api(self.api_client.get_turn_prompt(turn_id)) {
             Ok(prompt) => {
                 self.prompt_inspector = Some(PromptInspectorWindow {
                     open: true,
                     turn_id: prompt.turn_id,
                     prompt_text: prompt.prompt_text,
                     system_prompt_text: prompt.system_prompt_text.unwrap_or_default(),
+                    seed: prompt.seed,
+                    timing: prompt.timing,
                     show_system_prompt: false,
                     highlight_sections: false,
                     error: None,
+                    curl_status: None,
                 });
             }
             Err(error) => {
@@ -401,9 +1820,12 @@ impl AgentApp {
                     turn_id: turn_id.to_string(),
                     prompt_text: String::new(),
                     system_prompt_text: String::new(),
+                    seed: None,
+                    timing: None,
                     show_system_prompt: false,
                     highlight_sections: false,
                     error: Some(error.to_string()),
+                    curl_status: None,
                 });
                 self.push_ui_error(format!("Failed to load turn prompt: {}", error));
             }
@@ -411,10 +1833,7 @@ impl AgentApp {
     }
 
     fn create_new_conversation(&mut self) {
-        match self
-            .runtime
-            .block_on(self.api_client.create_conversation(None))
-        {
+        match self.block_on_api(self.api_client.create_conversation(None)) {
             Ok(conversation) => {
                 self.active_conversation_id = conversation.id;
                 self.user_input.clear();
@@ -429,11 +1848,43 @@ impl AgentApp {
         }
     }
 
+    /// Picks an exported chat (OpenAI-style JSON) and opens it as a new conversation.
+    fn import_conversation_from_file(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Conversation JSON", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+        let json = match std::fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(error) => {
+                self.push_ui_error(format!("Failed to read {}: {}", path.display(), error));
+                return;
+            }
+        };
+        match self.block_on_api(self.api_client.import_conversation(&json)) {
+            Ok(imported) => {
+                if imported.skipped > 0 {
+                    self.events.push(FrontendEvent::Observation(format!(
+                        "Imported {} messages; skipped {} with unsupported roles or no text",
+                        imported.imported, imported.skipped
+                    )));
+                }
+                self.active_conversation_id = imported.conversation.id;
+                self.streaming_chat_preview = None;
+                self.refresh_conversations();
+                self.refresh_chat_history();
+            }
+            Err(error) => {
+                tracing::error!("Failed to import conversation: {:#}", error);
+                self.push_ui_error(format!("Failed to import conversation: {:#}", error));
+            }
+        }
+    }
+
     fn delete_conversation(&mut self, conversation_id: &str) {
-        match self
-            .runtime
-            .block_on(self.api_client.delete_conversation(conversation_id))
-        {
+        match self.block_on_api(self.api_client.delete_conversation(conversation_id)) {
             Ok(()) => {
                 // If we deleted the active conversation, switch to a different one.
                 if self.active_conversation_id == conversation_id {
@@ -460,8 +1911,51 @@ impl AgentApp {
         }
     }
 
+    fn open_archived_conversations(&mut self) {
+        self.archived_conversations.open = true;
+        self.refresh_archived_conversations();
+    }
+
+    fn refresh_archived_conversations(&mut self) {
+        let result = self.block_on_api(
+            self.api_client
+                .list_archived_conversations(ARCHIVED_LIST_LIMIT),
+        );
+        if let Err(error) = &result {
+            tracing::warn!("Failed to load archived conversations: {:#}", error);
+        }
+        self.archived_conversations
+            .set_list(result.map_err(|error| format!("Failed to load: {:#}", error)));
+    }
+
+    fn restore_conversation(&mut self, conversation_id: &str, open: bool) {
+        match self.block_on_api(
+            self.api_client
+                .set_conversation_archived(conversation_id, false),
+        ) {
+            Ok(_) => {
+                self.archived_conversations.restored(conversation_id);
+                self.refresh_conversations();
+                if open {
+                    self.active_conversation_id = conversation_id.to_string();
+                    self.user_input.clear();
+                    self.streaming_chat_preview = None;
+                    self.refresh_chat_history();
+                }
+            }
+            Err(error) => {
+                tracing::error!(
+                    "Failed to restore conversation {}: {:#}",
+                    conversation_id,
+                    error
+                );
+                self.push_ui_error(format!("Failed to restore conversation: {:#}", error));
+            }
+        }
+    }
+
     fn rename_conversation(&mut self, conversation_id: &str, title: &str) {
-        match self.runtime.block_on(
+        match self.block_on_api(
             self.api_client
                 .update_conversation_title(conversation_id, title),
         ) {
@@ -475,16 +1969,87 @@ impl AgentApp {
         }
     }
 
+    fn set_active_tool_mode(&mut self, tool_mode: ToolMode) {
+        let conversation_id = self.active_conversation_id.clone();
+        match self.block_on_api(
+            self.api_client
+                .set_conversation_tool_mode(&conversation_id, tool_mode),
+        ) {
+            Ok(_) => {
+                self.refresh_conversations();
+            }
+            Err(error) => {
+                tracing::error!("Failed to set tool mode: {}", error);
+                self.push_ui_error(format!("Failed to set tool mode: {}", error));
+            }
+        }
+    }
+
+    fn save_conversation_details(&mut self, draft: &ConversationDetailsDraft) {
+        let tags = parse_tag_list(&draft.tags_text);
+        let note = draft.note.trim();
+        let prefix = draft.system_prompt_prefix.trim();
+        match self.block_on_api(self.api_client.set_conversation_metadata(
+            &draft.conversation_id,
+            &tags,
+            (!note.is_empty()).then_some(note),
+            (!prefix.is_empty()).then_some(prefix),
+            draft.seed,
+            draft.pinned,
+        )) {
+            Ok(_) => {
+                self.refresh_conversations();
+            }
+            Err(error) => {
+                tracing::error!("Failed to save conversation details: {}", error);
+                self.push_ui_error(format!("Failed to save conversation details: {}", error));
+            }
+        }
+    }
+
+    fn apply_auth_token(&mut self) {
+        let token = std::mem::take(&mut self.auth_token_draft);
+        self.api_client.set_token(Some(token.trim().to_string()));
+        self.refresh_status();
+        if self.auth_failed {
+            self.push_ui_error("Backend still rejects the token");
+            return;
+        }
+        if self.config_load_error.is_some() {
+            self.retry_config_load();
+        }
+        self.refresh_conversations();
+        self.refresh_chat_history();
+    }
+
+    fn retry_config_load(&mut self) {
+        match self.block_on_api(self.api_client.get_config()) {
+            Ok(config) => {
+                self.settings_panel.sync_from_config(config.clone());
+                self.character_panel.config = config.clone();
+                self.applied_config = config;
+                self.avatars = None;
+                self.avatars_loaded = false;
+                self.config_load_error = None;
+                tracing::info!("Reloaded config from backend");
+            }
+            Err(error) => {
+                tracing::warn!("Config reload failed: {}", error);
+                self.push_ui_error(format!("Config reload failed: {}", error));
+                self.config_load_error = Some(error.to_string());
+            }
+        }
+    }
+
     fn persist_config(&mut self, config: AgentConfig) {
-        match self
-            .runtime
-            .block_on(self.api_client.update_config(&config))
-        {
+        match self.block_on_api(self.api_client.update_config(&config)) {
             Ok(saved) => {
                 self.settings_panel.sync_from_config(saved.clone());
                 self.character_panel.config = saved.clone();
+                self.applied_config = saved;
                 self.avatars = None;
                 self.avatars_loaded = false;
+                self.refresh_config_history();
                 tracing::info!("Config saved through backend API");
             }
             Err(error) => {
@@ -494,13 +2059,54 @@ impl AgentApp {
         }
     }
 
-    fn push_live_tool_progress(&mut self, conversation_id: &str, tool_name: &str, output: &str) {
-        self.live_tool_progress.push(LiveToolProgress {
-            conversation_id: conversation_id.to_string(),
-            tool_name: tool_name.to_string(),
-            output_preview: output.to_string(),
-            subtask_id: parse_subtask_id(output),
-        });
+    fn apply_chat_streaming(&mut self, conversation_id: &str, content: &str, done: bool) {
+        self.quick_ask.on_stream(conversation_id, content, done);
+        // Capture global live stream regardless of which conversation is active.
+        if done {
+            self.live_stream_text = None;
+            if self.ui_prefs.mood_reactions {
+                let reply = if content.trim().is_empty() {
+                    self.streaming_chat_preview
+                        .as_ref()
+                        .filter(|preview| preview.conversation_id == conversation_id)
+                        .map(|preview| preview.content.as_str())
+                        .unwrap_or_default()
+                } else {
+                    content
+                };
+                self.mood_reaction = MoodReaction::for_reply(reply, std::time::Instant::now());
+            }
+            // Revert Writing back to Thinking so the backend StateChanged that
+            // follows can take over normally.
+            if matches!(self.current_state, AgentVisualState::Writing) {
+                self.current_state = AgentVisualState::Thinking;
+            }
+        } else if !content.trim().is_empty() {
+            self.live_stream_text = Some(content.to_string());
+            // Show Writing while tokens are actively streaming to the user.
+            if matches!(self.current_state, AgentVisualState::Thinking) {
+                self.current_state = AgentVisualState::Writing;
+            }
+        }
+        // Per-conversation streaming preview for the chat pane.
+        if done && content.trim().is_empty() {
+            if self
+                .streaming_chat_preview
+                .as_ref()
+                .is_some_and(|preview| preview.conversation_id == conversation_id)
+            {
+                self.streaming_chat_preview = None;
+            }
+        } else {
+            self.streaming_chat_preview = Some(StreamingChatPreview {
+                conversation_id: conversation_id.to_string(),
+                content: content.to_string(),
+            });
+        }
+    }
+
+    fn push_live_tool_progress(&mut self, entry: LiveToolProgress) {
+        upsert_live_tool_progress(&mut self.live_tool_progress, entry);
         if self.live_tool_progress.len() > MAX_LIVE_TOOL_PROGRESS_LINES {
             let overflow = self.live_tool_progress.len() - MAX_LIVE_TOOL_PROGRESS_LINES;
             self.live_tool_progress.drain(0..overflow);
@@ -529,6 +2135,93 @@ impl AgentApp {
     }
 }
 
+fn block_on_bounded<T>(
+    runtime: &tokio::runtime::Runtime,
+    bound: std::time::Duration,
+    call: impl std::future::Future<Output = anyhow::Result<T>>,
+) -> anyhow::Result<T> {
+    runtime.block_on(async {
+        tokio::time::timeout(bound, call)
+            .await
+            .unwrap_or_else(|_| Err(ApiError::Timeout.into()))
+    })
+}
+
+/// Whether a card waiting on the operator should stay up after a poll. A card
+/// can arrive before the poll that reports `AwaitingApproval`, so it is only
+/// dropped once the conversation has been seen awaiting and then leaves that
+/// state. Conversations missing from the poll (e.g. archived) keep their cards.
+fn still_awaiting(seen_awaiting: &mut bool, state: Option<&ChatTurnPhase>) -> bool {
+    match state {
+        Some(ChatTurnPhase::AwaitingApproval) => {
+            *seen_awaiting = true;
+            true
+        }
+        Some(_) => !*seen_awaiting,
+        None => true,
+    }
+}
+
+/// 404 (turn or call gone) and 409 (already answered or no longer waiting)
+/// mean the operator's card is stale rather than that the send failed.
+fn is_stale_operator_request(error: &anyhow::Error) -> bool {
+    match ApiError::of(error) {
+        Some(ApiError::NotFound { .. }) => true,
+        Some(ApiError::Server { status, .. }) => status == 409,
+        _ => false,
+    }
+}
+
+/// Removes a failed send so a retry cannot duplicate it; a retry that fails again
+/// re-queues the text under a new id.
+fn take_failed_send(
+    failed_sends: &mut HashMap<String, Vec<FailedChatSend>>,
+    conversation_id: &str,
+    failed_id: u64,
+) -> Option<FailedChatSend> {
+    let entries = failed_sends.get_mut(conversation_id)?;
+    let index = entries.iter().position(|failed| failed.id == failed_id)?;
+    let failed = entries.remove(index);
+    if entries.is_empty() {
+        failed_sends.remove(conversation_id);
+    }
+    Some(failed)
+}
+
+/// Warn once the loop has used 80% or more of its iteration budget.
+fn iteration_budget_near_limit(iteration: u32, max_iterations: u32) -> bool {
+    max_iterations > 0 && u64::from(iteration) * 5 >= u64::from(max_iterations) * 4
+}
+
+/// Comma-separated tag input → trimmed, de-duplicated (case-insensitive) tags.
+fn parse_tag_list(raw: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in raw.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        if !tags
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(tag))
+        {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Mirrors the backend context builder: global entries plus the conversation's own.
+fn working_memory_visible_in(entry: &WorkingMemoryEntry, conversation_id: &str) -> bool {
+    entry
+        .conversation_id
+        .as_deref()
+        .is_none_or(|scope| scope == conversation_id)
+}
+
+fn conversation_has_tag(conversation: &ChatConversation, tag: &str) -> bool {
+    conversation
+        .tags
+        .iter()
+        .any(|existing| existing.eq_ignore_ascii_case(tag))
+}
+
 fn conversation_display_label(conversation: &ChatConversation) -> String {
     let base = if conversation.message_count == 0 {
         conversation.title.clone()
@@ -552,17 +2245,49 @@ fn conversation_display_label(conversation: &ChatConversation) -> String {
 }
 
 impl eframe::App for AgentApp {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if self.window_placement.is_none() || self.window_placement == self.ui_prefs.window {
+            return;
+        }
+        self.ui_prefs.window = self.window_placement;
+        if let Err(error) = self.ui_prefs.save() {
+            tracing::warn!("Failed to persist window placement: {}", error);
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.check_window_placement(ctx);
+        self.sync_window_title(ctx);
+        let plain = self.ui_prefs.plain_labels;
         if !self.avatars_loaded {
             let config = self.settings_panel.config.clone();
             self.load_avatars(ctx, &config);
             self.avatars_loaded = true;
         }
 
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::K)) {
+            self.command_palette.toggle();
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F11)) {
+            self.toggle_focus_mode();
+        }
+        let focus_mode = self.ui_prefs.focus_mode;
+        if let Some(action) = self.command_palette.render(ctx) {
+            self.run_palette_action(action);
+        }
+        if self
+            .quick_ask_hotkey
+            .as_ref()
+            .is_some_and(QuickAskHotkey::take_pressed)
+        {
+            self.open_quick_ask();
+        }
+
+        while let Ok(polled) = self.poll_rx.try_recv() {
+            self.apply_polled_state(polled);
+        }
         if self.last_chat_refresh.elapsed() > std::time::Duration::from_secs(2) {
-            self.refresh_status();
-            self.refresh_conversations();
-            self.refresh_chat_history();
+            self.start_background_poll(ctx);
             self.last_chat_refresh = std::time::Instant::now();
         }
 
@@ -576,35 +2301,18 @@ impl eframe::App for AgentApp {
                     content,
                     done,
                 } => {
-                    // Capture global live stream regardless of which conversation is active.
-                    if *done {
-                        self.live_stream_text = None;
-                        // Revert Writing back to Thinking so the backend StateChanged that
-                        // follows can take over normally.
-                        if matches!(self.current_state, AgentVisualState::Writing) {
-                            self.current_state = AgentVisualState::Thinking;
-                        }
-                    } else if !content.trim().is_empty() {
-                        self.live_stream_text = Some(content.clone());
-                        // Show Writing while tokens are actively streaming to the user.
-                        if matches!(self.current_state, AgentVisualState::Thinking) {
-                            self.current_state = AgentVisualState::Writing;
-                        }
-                    }
-                    // Per-conversation streaming preview for the chat pane.
-                    if *done && content.trim().is_empty() {
-                        if self
-                            .streaming_chat_preview
-                            .as_ref()
-                            .is_some_and(|preview| preview.conversation_id == *conversation_id)
-                        {
-                            self.streaming_chat_preview = None;
-                        }
-                    } else {
-                        self.streaming_chat_preview = Some(StreamingChatPreview {
-                            conversation_id: conversation_id.clone(),
-                            content: content.clone(),
-                        });
+                    // Streaming events carry the full text so far, so only the newest
+                    // one per window matters; `done` always flushes through.
+                    let now = std::time::Instant::now();
+                    for update in
+                        self.streaming_coalescer
+                            .offer(conversation_id, content, *done, now)
+                    {
+                        self.apply_chat_streaming(
+                            &update.conversation_id,
+                            &update.content,
+                            update.done,
+                        );
                     }
                     continue;
                 }
@@ -652,11 +2360,32 @@ impl eframe::App for AgentApp {
                     conversation_id,
                     tool_name,
                     output_preview,
+                    iteration,
+                    max_iterations,
+                    tool_call_id,
+                    partial,
+                    arguments,
                 } => {
-                    self.push_live_tool_progress(conversation_id, tool_name, output_preview);
+                    self.push_live_tool_progress(LiveToolProgress {
+                        conversation_id: conversation_id.clone(),
+                        tool_name: tool_name.clone(),
+                        output_preview: output_preview.clone(),
+                        subtask_id: parse_subtask_id(output_preview),
+                        iteration_budget: iteration.zip(*max_iterations),
+                        tool_call_id: tool_call_id.clone(),
+                        argument_key: arguments.as_ref().and_then(tool_argument_key),
+                    });
+                    // Streamed chunks only refresh the live panel; the activity log
+                    // records the final output once.
+                    if *partial {
+                        continue;
+                    }
                 }
-                FrontendEvent::ActionTaken { action, .. } => {
+                FrontendEvent::ActionTaken {
+                    action, action_id, ..
+                } => {
                     self.last_action = Some(action.clone());
+                    self.last_action_id = action_id.clone();
                     if action.contains("operator") {
                         self.refresh_conversations();
                         self.refresh_chat_history();
@@ -666,22 +2395,116 @@ impl eframe::App for AgentApp {
                 FrontendEvent::OrientationUpdate(summary) => {
                     self.last_orientation = Some(summary.clone());
                 }
+                FrontendEvent::AgentReset => {
+                    // Another client (or the reset we just issued) wiped state; the
+                    // event itself is kept as the first line of the fresh log.
+                    self.clear_local_agent_state();
+                }
                 FrontendEvent::JournalWritten(summary) => {
                     self.last_journal = Some(summary.clone());
                 }
+                FrontendEvent::ConversationCompacted {
+                    conversation_id,
+                    summary,
+                    through_message_id,
+                } => {
+                    let after_message_id = through_message_id.clone().or_else(|| {
+                        (*conversation_id == self.active_conversation_id)
+                            .then(|| self.chat_history.last().map(|msg| msg.id.clone()))
+                            .flatten()
+                    });
+                    self.compactions
+                        .entry(conversation_id.clone())
+                        .or_default()
+                        .push(CompactionMarker {
+                            after_message_id,
+                            summary: summary.clone(),
+                        });
+                }
+                FrontendEvent::IterationLimitHit {
+                    conversation_id,
+                    max_iterations,
+                    mid_progress: true,
+                } if !self.ui_prefs.iteration_limit_hint_seen => {
+                    tracing::info!(
+                        "Turn in {} stopped at the iteration limit mid-progress",
+                        conversation_id
+                    );
+                    self.iteration_limit_hint = Some(*max_iterations);
+                }
                 FrontendEvent::ApprovalRequest { tool_name, reason } => {
                     // Deduplicate: only add if not already pending
                     if !self.pending_approvals.iter().any(|(t, _)| t == tool_name) {
                         self.pending_approvals
                             .push((tool_name.clone(), reason.clone()));
                     }
-                    // Don't push ApprovalRequest into the activity log — it gets its own popup
+                    // Don't push ApprovalRequest into the activity log — it gets its own popup
+                    continue;
+                }
+                FrontendEvent::ConfigProposed(proposal) => {
+                    if !self
+                        .pending_config_proposals
+                        .iter()
+                        .any(|pending| pending.id == proposal.id)
+                    {
+                        self.pending_config_proposals.push(proposal.clone());
+                    }
+                    continue;
+                }
+                FrontendEvent::ToolBatchPending(batch) => {
+                    self.pending_tool_batches
+                        .retain(|pending| pending.batch.turn_id != batch.turn_id);
+                    // Everything starts allowed; the operator unticks what should not run.
+                    self.pending_tool_batches.push(PendingToolBatch {
+                        batch: batch.clone(),
+                        allowed: vec![true; batch.calls.len()],
+                        seen_awaiting: false,
+                    });
+                    continue;
+                }
+                FrontendEvent::ToolInputRequested {
+                    conversation_id,
+                    turn_id,
+                    tool_call_id,
+                    tool_name,
+                    prompt,
+                } => {
+                    if !self
+                        .pending_tool_inputs
+                        .iter()
+                        .any(|pending| pending.tool_call_id == *tool_call_id)
+                    {
+                        self.pending_tool_inputs.push(PendingToolInput {
+                            conversation_id: conversation_id.clone(),
+                            turn_id: turn_id.clone(),
+                            tool_call_id: tool_call_id.clone(),
+                            tool_name: tool_name.clone(),
+                            prompt: prompt.clone(),
+                            answer: String::new(),
+                            seen_awaiting: false,
+                        });
+                    }
                     continue;
                 }
                 _ => {}
             }
+            if matches!(
+                event,
+                FrontendEvent::Observation(_)
+                    | FrontendEvent::ReasoningTrace(_)
+                    | FrontendEvent::ActionTaken { .. }
+                    | FrontendEvent::JournalWritten(_)
+            ) {
+                self.idle_heartbeat.glance(std::time::Instant::now());
+            }
             self.events.push(event);
         }
+        if let Some(update) = self.streaming_coalescer.poll(std::time::Instant::now()) {
+            self.apply_chat_streaming(&update.conversation_id, &update.content, update.done);
+        }
+        if self.streaming_coalescer.has_pending() {
+            ctx.request_repaint_after(STREAMING_COALESCE_WINDOW);
+        }
 
         // --- Approval requests ---
         // Rendered inside the activity panel (not as a floating egui::Window) so they appear
@@ -690,11 +2513,12 @@ impl eframe::App for AgentApp {
         // off-screen and remembered there by ID, never recovering.
         let mut approve_tool: Option<String> = None;
         let mut dismiss_tool: Option<String> = None;
+        let mut resolve_proposal: Option<(String, bool)> = None;
 
         egui::SidePanel::right("activity_panel")
             .resizable(true)
             .default_width(340.0)
-            .show_animated(ctx, self.show_activity_panel, |ui| {
+            .show_animated(ctx, self.show_activity_panel && !focus_mode, |ui| {
                 // Pending approvals at the very top — hard to miss.
                 for (tool_name, reason) in &self.pending_approvals {
                     ui.group(|ui| {
@@ -726,8 +2550,71 @@ impl eframe::App for AgentApp {
                     });
                     ui.add_space(4.0);
                 }
+                for proposal in &self.pending_config_proposals {
+                    ui.group(|ui| {
+                        ui.set_min_width(ui.available_width());
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 160, 50),
+                            "! Config change proposed",
+                        );
+                        if !proposal.reason.trim().is_empty() {
+                            ui.add_space(2.0);
+                            let wrapped_reason =
+                                wrap_text_for_ui_width(&proposal.reason, ui.available_width());
+                            ui.add(
+                                egui::Label::new(egui::RichText::new(wrapped_reason).small())
+                                    .wrap(),
+                            );
+                        }
+                        ui.add_space(2.0);
+                        for change in &proposal.changes {
+                            if change.is_protected() {
+                                ui.colored_label(
+                                    egui::Color32::from_rgb(220, 120, 120),
+                                    egui::RichText::new(format!(
+                                        "{}: protected, value hidden",
+                                        change.field
+                                    ))
+                                    .small()
+                                    .monospace(),
+                                );
+                            } else {
+                                ui.label(
+                                    egui::RichText::new(
+                                        change.describe(self.ui_prefs.limits.short_label_chars),
+                                    )
+                                    .small()
+                                    .monospace(),
+                                );
+                            }
+                        }
+                        let protected = proposal.touches_protected_field();
+                        ui.add_space(4.0);
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(
+                                    !protected,
+                                    egui::Button::new(
+                                        egui::RichText::new("Apply")
+                                            .color(egui::Color32::from_rgb(80, 200, 100)),
+                                    ),
+                                )
+                                .on_disabled_hover_text(
+                                    "Proposals may not change credentials or safety settings; change them in Settings yourself",
+                                )
+                                .clicked()
+                            {
+                                resolve_proposal = Some((proposal.id.clone(), true));
+                            }
+                            if ui.button("Reject").clicked() {
+                                resolve_proposal = Some((proposal.id.clone(), false));
+                            }
+                        });
+                    });
+                    ui.add_space(4.0);
+                }
 
-                ui.heading("🧠 Mind");
+                ui.heading(label_for(plain, "🧠 Mind", "Mind"));
                 ui.add_space(4.0);
 
                 // Zone 1: Current mind state snapshot.
@@ -736,10 +2623,14 @@ impl eframe::App for AgentApp {
                     if let Some(ref o) = self.last_orientation {
                         ui.horizontal_wrapped(|ui| {
                             ui.label(
-                                egui::RichText::new(format!("🧭 {}", o.disposition))
-                                    .color(egui::Color32::LIGHT_YELLOW)
-                                    .small()
-                                    .strong(),
+                                egui::RichText::new(format!(
+                                    "{} {}",
+                                    label_for(plain, "🧭", "Orientation:"),
+                                    o.disposition
+                                ))
+                                .color(egui::Color32::LIGHT_YELLOW)
+                                .small()
+                                .strong(),
                             );
                             if o.anomaly_count > 0 {
                                 ui.label(
@@ -754,20 +2645,39 @@ impl eframe::App for AgentApp {
                             }
                         });
                     }
-                    if let Some(ref action) = self.last_action {
+                    if let Some(action) = self.last_action.clone() {
                         let wrapped = wrap_text_for_ui_width(
-                            &format!("⚡ {}", truncate_str(action, 80)),
+                            &format!(
+                                "{} {}",
+                                label_for(plain, "⚡", "Action:"),
+                                truncate_str(&action, self.ui_prefs.limits.status_line_chars)
+                            ),
                             ui.available_width(),
                         );
-                        ui.label(
-                            egui::RichText::new(wrapped)
-                                .small()
-                                .color(egui::Color32::LIGHT_GREEN),
-                        );
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(
+                                egui::RichText::new(wrapped)
+                                    .small()
+                                    .color(egui::Color32::LIGHT_GREEN),
+                            );
+                            if let Some(action_id) = self.last_action_id.clone() {
+                                if ui
+                                    .small_button("Why?")
+                                    .on_hover_text("Show the reasoning behind this action")
+                                    .clicked()
+                                {
+                                    self.open_action_explanation(&action_id, &action);
+                                }
+                            }
+                        });
                     }
                     if let Some(ref journal) = self.last_journal {
                         let wrapped = wrap_text_for_ui_width(
-                            &format!("📓 {}", truncate_str(journal, 80)),
+                            &format!(
+                                "{} {}",
+                                label_for(plain, "📓", "Journal:"),
+                                truncate_str(journal, self.ui_prefs.limits.status_line_chars)
+                            ),
                             ui.available_width(),
                         );
                         ui.label(egui::RichText::new(wrapped).small().weak().italics());
@@ -776,7 +2686,11 @@ impl eframe::App for AgentApp {
                     // This makes it visible what the agent is waiting on when the GPU is idle.
                     if let Some(ref activity) = self.current_activity.clone() {
                         let wrapped = wrap_text_for_ui_width(
-                            &format!("⏳ {}", truncate_str(activity, 90)),
+                            &format!(
+                                "{} {}",
+                                label_for(plain, "⏳", "Working on:"),
+                                truncate_str(activity, self.ui_prefs.limits.activity_chars)
+                            ),
                             ui.available_width(),
                         );
                         ui.label(
@@ -788,9 +2702,9 @@ impl eframe::App for AgentApp {
                     if let Some(ref intention) = self.current_intention {
                         ui.separator();
                         let mode_label = if self.loose_mode {
-                            "🜁 Loose goal"
+                            label_for(plain, "🜁 Loose goal", "Loose goal")
                         } else {
-                            "🎯 Intention"
+                            label_for(plain, "🎯 Intention", "Intention")
                         };
                         ui.label(egui::RichText::new(mode_label).small().strong().color(
                             if self.loose_mode {
@@ -835,268 +2749,946 @@ impl eframe::App for AgentApp {
                 ui.add_space(4.0);
 
                 // Zone 2: Live LLM token stream.
-                egui::CollapsingHeader::new(egui::RichText::new("💭 Live Stream").small().strong())
-                    .id_salt("live_stream_header")
-                    .default_open(true)
-                    .show(ui, |ui| {
-                        super::token_monitor::render(ui, &mut self.token_monitor);
-                        ui.add_space(6.0);
-                        ui.horizontal_wrapped(|ui| {
-                            let intensity = self.token_monitor.last_novelty().clamp(0.0, 1.25);
-                            let descriptor = if intensity > 0.95 {
-                                "wild"
-                            } else if intensity > 0.65 {
-                                "exploring"
-                            } else if intensity > 0.35 {
-                                "steady"
-                            } else {
-                                "grounded"
-                            };
-                            ui.label(
-                                egui::RichText::new(format!("Trace: {}", descriptor))
-                                    .small()
-                                    .color(egui::Color32::from_rgb(170, 255, 190)),
-                            );
-                            ui.label(
-                                egui::RichText::new(format!(
-                                    "· {} steps",
-                                    self.token_monitor.trace_len()
-                                ))
+                egui::CollapsingHeader::new(
+                    egui::RichText::new(label_for(plain, "💭 Live Stream", "Live Stream"))
+                        .small()
+                        .strong(),
+                )
+                .id_salt("live_stream_header")
+                .default_open(true)
+                .show(ui, |ui| {
+                    super::token_monitor::render(ui, &mut self.token_monitor);
+                    ui.add_space(6.0);
+                    let mut prefs_changed = false;
+                    ui.horizontal(|ui| {
+                        prefs_changed |= ui
+                            .checkbox(
+                                &mut self.ui_prefs.live_stream_monospace,
+                                egui::RichText::new("Monospace").small(),
+                            )
+                            .changed();
+                        ui.label(egui::RichText::new("· keep").small().weak());
+                        let window = ui
+                            .add(
+                                egui::DragValue::new(&mut self.ui_prefs.live_stream_window_chars)
+                                    .range(LIVE_STREAM_WINDOW_RANGE)
+                                    .speed(50)
+                                    .suffix(" chars"),
+                            )
+                            .on_hover_text("Older text scrolls out of the live view");
+                        // Save once the drag ends instead of on every step.
+                        prefs_changed |= window.drag_stopped() || window.lost_focus();
+                    });
+                    if prefs_changed {
+                        if let Err(error) = self.ui_prefs.save() {
+                            tracing::warn!("Failed to persist UI prefs: {}", error);
+                        }
+                    }
+                    ui.horizontal_wrapped(|ui| {
+                        let intensity = self.token_monitor.last_novelty().clamp(0.0, 1.25);
+                        let descriptor = if intensity > 0.95 {
+                            "wild"
+                        } else if intensity > 0.65 {
+                            "exploring"
+                        } else if intensity > 0.35 {
+                            "steady"
+                        } else {
+                            "grounded"
+                        };
+                        ui.label(
+                            egui::RichText::new(format!("Trace: {}", descriptor))
                                 .small()
-                                .weak(),
-                            );
-                            ui.label(
-                                egui::RichText::new(format!(
-                                    "· {} paths",
-                                    self.token_monitor.path_count()
-                                ))
+                                .color(egui::Color32::from_rgb(170, 255, 190)),
+                        );
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "· {} steps",
+                                self.token_monitor.trace_len()
+                            ))
+                            .small()
+                            .weak(),
+                        );
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "· {} paths",
+                                self.token_monitor.path_count()
+                            ))
+                            .small()
+                            .weak(),
+                        );
+                    });
+                    ui.add_space(4.0);
+                    egui::ScrollArea::vertical()
+                        .max_height(110.0)
+                        .stick_to_bottom(true)
+                        .id_salt("live_stream_scroll")
+                        .show(ui, |ui| {
+                            if let Some(ref text) = self.live_stream_text {
+                                let preview = live_stream_window(
+                                    text,
+                                    self.ui_prefs.live_stream_window_chars,
+                                );
+                                let wrapped =
+                                    wrap_text_for_ui_width(&preview, ui.available_width());
+                                let mut text = egui::RichText::new(wrapped)
+                                    .small()
+                                    .color(egui::Color32::from_gray(200));
+                                if self.ui_prefs.live_stream_monospace {
+                                    text = text.monospace();
+                                }
+                                ui.add(egui::Label::new(text).wrap());
+                            } else {
+                                ui.label(egui::RichText::new("—").weak().small().italics());
+                            }
+                        });
+                });
+
+                ui.add_space(4.0);
+
+                // Upcoming: what the agent plans to do next, soonest first.
+                let mut refresh_upcoming = false;
+                egui::CollapsingHeader::new(
+                    egui::RichText::new(label_for(plain, "⏭ Upcoming", "Upcoming"))
+                        .small()
+                        .strong(),
+                )
+                .id_salt("upcoming_header")
+                .default_open(false)
+                .show(ui, |ui| {
+                    if self.upcoming.is_none() {
+                        refresh_upcoming = true;
+                    }
+                    if ui
+                        .small_button(label_for(plain, "⟳ Refresh", "Refresh"))
+                        .clicked()
+                    {
+                        refresh_upcoming = true;
+                    }
+                    if let Some(ref error) = self.upcoming_error {
+                        ui.label(
+                            egui::RichText::new(error)
                                 .small()
-                                .weak(),
-                            );
+                                .color(egui::Color32::LIGHT_RED),
+                        );
+                    }
+                    egui::ScrollArea::vertical()
+                        .max_height(140.0)
+                        .id_salt("upcoming_scroll")
+                        .show(ui, |ui| match self.upcoming.as_deref() {
+                            Some([]) => {
+                                ui.label(
+                                    egui::RichText::new("Nothing queued")
+                                        .weak()
+                                        .small()
+                                        .italics(),
+                                );
+                            }
+                            Some(items) => {
+                                for item in items {
+                                    let when = item
+                                        .due_at
+                                        .map(|at| {
+                                            at.with_timezone(&chrono::Local)
+                                                .format("%m-%d %H:%M")
+                                                .to_string()
+                                        })
+                                        .unwrap_or_else(|| "—".to_string());
+                                    let row = ui.horizontal_wrapped(|ui| {
+                                        ui.label(egui::RichText::new(when).small().monospace());
+                                        ui.label(
+                                            egui::RichText::new(item.kind.label())
+                                                .small()
+                                                .color(egui::Color32::LIGHT_BLUE),
+                                        );
+                                        ui.label(egui::RichText::new(&item.summary).small());
+                                    });
+                                    if let Some(detail) = item.detail.as_deref() {
+                                        row.response.on_hover_text(detail);
+                                    }
+                                }
+                            }
+                            None => {
+                                ui.label(egui::RichText::new("Loading…").weak().small());
+                            }
                         });
-                        ui.add_space(4.0);
-                        egui::ScrollArea::vertical()
-                            .max_height(110.0)
-                            .stick_to_bottom(true)
-                            .id_salt("live_stream_scroll")
-                            .show(ui, |ui| {
-                                if let Some(ref text) = self.live_stream_text {
-                                    let preview = last_n_chars(text, 600);
-                                    let wrapped =
-                                        wrap_text_for_ui_width(&preview, ui.available_width());
-                                    ui.add(
-                                        egui::Label::new(
-                                            egui::RichText::new(wrapped)
+                });
+                if refresh_upcoming {
+                    self.refresh_upcoming();
+                }
+
+                // Working memory inspector: what the agent is holding in mind right now.
+                let mut refresh_memory = false;
+                let mut forget_entry: Option<(String, Option<String>)> = None;
+                let mut clear_memory = false;
+                egui::CollapsingHeader::new(
+                    egui::RichText::new(label_for(plain, "🗂 Working Memory", "Working Memory"))
+                        .small()
+                        .strong(),
+                )
+                .id_salt("working_memory_header")
+                .default_open(false)
+                .show(ui, |ui| {
+                    if self.working_memory.is_none() {
+                        refresh_memory = true;
+                    }
+                    ui.horizontal(|ui| {
+                        if ui
+                            .small_button(label_for(plain, "⟳ Refresh", "Refresh"))
+                            .clicked()
+                        {
+                            refresh_memory = true;
+                        }
+                        let has_entries = self
+                            .working_memory
+                            .as_ref()
+                            .is_some_and(|entries| !entries.is_empty());
+                        if self.confirm_clear_working_memory {
+                            ui.label(egui::RichText::new("Clear all?").small());
+                            if ui.small_button("Yes").clicked() {
+                                clear_memory = true;
+                                self.confirm_clear_working_memory = false;
+                            }
+                            if ui.small_button("No").clicked() {
+                                self.confirm_clear_working_memory = false;
+                            }
+                        } else if ui
+                            .add_enabled(has_entries, egui::Button::new("Clear all").small())
+                            .clicked()
+                        {
+                            self.confirm_clear_working_memory = true;
+                        }
+                    });
+                    ui.checkbox(
+                        &mut self.working_memory_active_scope_only,
+                        egui::RichText::new("Only what this chat sees").small(),
+                    );
+                    if let Some(ref error) = self.working_memory_error {
+                        ui.label(
+                            egui::RichText::new(error)
+                                .small()
+                                .color(egui::Color32::LIGHT_RED),
+                        );
+                    }
+                    let active_conversation_id = self.active_conversation_id.as_str();
+                    let scope_only = self.working_memory_active_scope_only;
+                    let conversations = &self.conversations;
+                    let scope_chars = self.ui_prefs.limits.scope_label_chars;
+                    egui::ScrollArea::vertical()
+                        .max_height(160.0)
+                        .id_salt("working_memory_scroll")
+                        .show(ui, |ui| match self.working_memory.as_deref() {
+                            Some([]) => {
+                                ui.label(
+                                    egui::RichText::new("Nothing in working memory")
+                                        .weak()
+                                        .small()
+                                        .italics(),
+                                );
+                            }
+                            Some(entries) => {
+                                for entry in entries.iter().filter(|entry| {
+                                    !scope_only
+                                        || working_memory_visible_in(entry, active_conversation_id)
+                                }) {
+                                    ui.horizontal_wrapped(|ui| {
+                                        if ui
+                                            .small_button(label_for(plain, "🗑", "Forget"))
+                                            .on_hover_text("Forget this entry")
+                                            .clicked()
+                                        {
+                                            forget_entry = Some((
+                                                entry.key.clone(),
+                                                entry.conversation_id.clone(),
+                                            ));
+                                        }
+                                        ui.label(
+                                            egui::RichText::new(&entry.key)
                                                 .small()
-                                                .color(egui::Color32::from_gray(200)),
-                                        )
-                                        .wrap(),
+                                                .strong()
+                                                .color(egui::Color32::LIGHT_YELLOW),
+                                        );
+                                        let scope = match entry.conversation_id.as_deref() {
+                                            None => {
+                                                label_for(plain, "🌐 global", "global").to_string()
+                                            }
+                                            Some(id) => format!(
+                                                "{} {}",
+                                                label_for(plain, "💬", "chat:"),
+                                                conversations
+                                                    .iter()
+                                                    .find(|c| c.id == id)
+                                                    .map(|c| truncate_str(&c.title, scope_chars))
+                                                    .unwrap_or_else(|| truncate_str(id, scope_chars))
+                                            ),
+                                        };
+                                        ui.label(egui::RichText::new(scope).small().weak());
+                                    });
+                                    let wrapped = wrap_text_for_ui_width(
+                                        &truncate_str(
+                                            &entry.content,
+                                            self.ui_prefs.limits.memory_entry_chars,
+                                        ),
+                                        ui.available_width(),
                                     );
-                                } else {
-                                    ui.label(egui::RichText::new("—").weak().small().italics());
+                                    ui.label(egui::RichText::new(wrapped).small().weak())
+                                        .on_hover_text(&entry.content);
+                                    ui.add_space(2.0);
                                 }
-                            });
-                    });
+                            }
+                            None => {
+                                ui.label(egui::RichText::new("Loading…").weak().small());
+                            }
+                        });
+                });
+                if refresh_memory {
+                    self.refresh_working_memory();
+                }
+                if let Some((key, scope)) = forget_entry {
+                    self.delete_working_memory_entry(&key, scope.as_deref());
+                }
+                if clear_memory {
+                    self.clear_working_memory();
+                }
 
                 ui.add_space(4.0);
                 ui.separator();
                 ui.add_space(4.0);
 
                 // Zone 3: Grouped turn history log.
-                super::chat::render_event_log(ui, &self.events, &mut self.event_detail_popup);
+                super::chat::render_event_log(
+                    ui,
+                    &self.events,
+                    &mut self.event_detail_popup,
+                    self.ui_prefs.plain_labels,
+                );
             });
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                super::sprite::render_agent_sprite(ui, &self.current_state, self.avatars.as_mut());
-                ui.vertical(|ui| {
-                    ui.heading("Ponderer");
-                    ui.horizontal_wrapped(|ui| {
-                        let (state_text, state_color) = visual_state_display(&self.current_state);
+            if self.auth_failed {
+                let mut apply = false;
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(80, 30, 30))
+                    .rounding(4.0)
+                    .inner_margin(egui::Margin::symmetric(8.0, 6.0))
+                    .show(ui, |ui| {
+                        ui.set_min_width(ui.available_width());
                         ui.label(
-                            egui::RichText::new(state_text)
-                                .color(state_color)
-                                .small()
-                                .strong(),
+                            egui::RichText::new(
+                                label_for(
+                                    plain,
+                                    "🔒 Backend authentication failed — check your token.",
+                                    "Backend authentication failed — check your token.",
+                                ),
+                            )
+                            .color(egui::Color32::from_rgb(255, 170, 170))
+                            .strong(),
                         );
-                        // Show how long the agent has been in the current state.
-                        // This makes "stuck Thinking" immediately visible.
-                        if let Some(since) = self.visual_state_since {
-                            let elapsed = chrono::Utc::now()
-                                .signed_duration_since(since)
-                                .num_seconds()
-                                .max(0) as u64;
-                            if elapsed >= 3 {
-                                ui.label(
-                                    egui::RichText::new(format!("({})", format_elapsed(elapsed)))
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new("Token:").small());
+                            let field = ui.add(
+                                egui::TextEdit::singleline(&mut self.auth_token_draft)
+                                    .password(true)
+                                    .hint_text("PONDERER_BACKEND_TOKEN")
+                                    .desired_width(260.0),
+                            );
+                            let submitted = field.lost_focus()
+                                && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                            if ui.small_button("Apply").clicked() || submitted {
+                                apply = true;
+                            }
+                        });
+                    });
+                ui.add_space(4.0);
+                if apply && !self.auth_token_draft.trim().is_empty() {
+                    self.apply_auth_token();
+                }
+            }
+            if let Some(error) = self.config_load_error.clone() {
+                let mut retry = false;
+                let mut dismiss = false;
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(70, 45, 20))
+                    .rounding(4.0)
+                    .inner_margin(egui::Margin::symmetric(8.0, 6.0))
+                    .show(ui, |ui| {
+                        ui.set_min_width(ui.available_width());
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(
+                                egui::RichText::new(
+                                    label_for(
+                                        plain,
+                                        "⚠ Backend config didn't load; settings show a local fallback and saving would overwrite the backend's copy.",
+                                        "Warning: backend config didn't load; settings show a local fallback and saving would overwrite the backend's copy.",
+                                    ),
+                                )
+                                .color(egui::Color32::from_rgb(255, 200, 120))
+                                .small(),
+                            )
+                            .on_hover_text(&error);
+                            if ui.small_button("Retry").clicked() {
+                                retry = true;
+                            }
+                            if ui.small_button("Dismiss").clicked() {
+                                dismiss = true;
+                            }
+                        });
+                    });
+                ui.add_space(4.0);
+                if retry {
+                    self.retry_config_load();
+                } else if dismiss {
+                    self.config_load_error = None;
+                }
+            }
+            if let Some(max_iterations) = self.iteration_limit_hint {
+                let mut open_behavior = false;
+                let mut dismiss = false;
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(70, 45, 20))
+                    .rounding(4.0)
+                    .inner_margin(egui::Margin::symmetric(8.0, 6.0))
+                    .show(ui, |ui| {
+                        ui.set_min_width(ui.available_width());
+                        ui.horizontal_wrapped(|ui| {
+                            let limit = max_iterations
+                                .map(|max| format!(" ({})", max))
+                                .unwrap_or_default();
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "{} The agent was still using tools when it hit the tool-iteration limit{}. Raising \"Max tool iterations per turn\" lets it finish.",
+                                    label_for(plain, "⚠", "Hint:"),
+                                    limit
+                                ))
+                                .color(egui::Color32::from_rgb(255, 200, 120))
+                                .small(),
+                            );
+                            if ui.small_button("Open Behavior settings").clicked() {
+                                open_behavior = true;
+                            }
+                            if ui.small_button("Dismiss").clicked() {
+                                dismiss = true;
+                            }
+                        });
+                    });
+                ui.add_space(4.0);
+                if open_behavior || dismiss {
+                    self.iteration_limit_hint = None;
+                    self.ui_prefs.iteration_limit_hint_seen = true;
+                    if let Err(error) = self.ui_prefs.save() {
+                        tracing::warn!("Failed to persist UI prefs: {}", error);
+                    }
+                }
+                if open_behavior {
+                    self.open_settings();
+                    self.settings_panel.open_tab(CORE_TAB_BEHAVIOR);
+                }
+            }
+            let mut answered_tool_input: Option<usize> = None;
+            for (index, pending) in self
+                .pending_tool_inputs
+                .iter_mut()
+                .enumerate()
+                .filter(|(_, pending)| pending.conversation_id == self.active_conversation_id)
+            {
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(30, 45, 70))
+                    .rounding(4.0)
+                    .inner_margin(egui::Margin::symmetric(8.0, 6.0))
+                    .show(ui, |ui| {
+                        ui.set_min_width(ui.available_width());
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} {} needs your input",
+                                label_for(plain, "❓", "Question:"),
+                                pending.tool_name
+                            ))
+                            .color(egui::Color32::from_rgb(150, 200, 255))
+                            .strong(),
+                        );
+                        ui.label(
+                            egui::RichText::new(wrap_text_for_ui_width(
+                                &pending.prompt,
+                                ui.available_width(),
+                            ))
+                            .small(),
+                        );
+                        ui.horizontal(|ui| {
+                            let field = ui.add(
+                                egui::TextEdit::singleline(&mut pending.answer)
+                                    .desired_width(ui.available_width() - 60.0)
+                                    .hint_text("Your answer"),
+                            );
+                            let submitted = field.lost_focus()
+                                && ui.input(|input| input.key_pressed(egui::Key::Enter));
+                            let can_send = !pending.answer.trim().is_empty();
+                            if (ui
+                                .add_enabled(can_send, egui::Button::new("Send"))
+                                .clicked()
+                                || submitted)
+                                && can_send
+                            {
+                                answered_tool_input = Some(index);
+                            }
+                        });
+                    });
+                ui.add_space(4.0);
+            }
+            if let Some(index) = answered_tool_input {
+                self.answer_tool_input(index);
+            }
+            let mut decided_batch: Option<usize> = None;
+            for (index, PendingToolBatch { batch, allowed, .. }) in self
+                .pending_tool_batches
+                .iter_mut()
+                .enumerate()
+                .filter(|(_, pending)| pending.batch.conversation_id == self.active_conversation_id)
+            {
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(70, 45, 20))
+                    .rounding(4.0)
+                    .inner_margin(egui::Margin::symmetric(8.0, 6.0))
+                    .show(ui, |ui| {
+                        ui.set_min_width(ui.available_width());
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} Confirm {} tool call{} before they run",
+                                label_for(plain, "⏸", "Paused:"),
+                                batch.calls.len(),
+                                if batch.calls.len() == 1 { "" } else { "s" }
+                            ))
+                            .color(egui::Color32::from_rgb(255, 200, 120))
+                            .strong(),
+                        );
+                        for (call, allow) in batch.calls.iter().zip(allowed.iter_mut()) {
+                            let label = match tool_argument_key(&call.arguments) {
+                                Some(key) => format!("{} · {}", call.tool_name, key),
+                                None => call.tool_name.clone(),
+                            };
+                            ui.checkbox(allow, egui::RichText::new(label).small().monospace())
+                                .on_hover_text(
+                                    serde_json::to_string_pretty(&call.arguments)
+                                        .unwrap_or_default(),
+                                );
+                        }
+                        ui.horizontal(|ui| {
+                            let approved = allowed.iter().filter(|allow| **allow).count();
+                            let run_label = if approved == allowed.len() {
+                                "Run all".to_string()
+                            } else {
+                                format!("Run {} of {}", approved, allowed.len())
+                            };
+                            if ui.button(run_label).clicked() {
+                                decided_batch = Some(index);
+                            }
+                            if ui.small_button("Deny all").clicked() {
+                                allowed.iter_mut().for_each(|allow| *allow = false);
+                                decided_batch = Some(index);
+                            }
+                        });
+                    });
+                ui.add_space(4.0);
+            }
+            if let Some(index) = decided_batch {
+                self.decide_tool_batch(index);
+            }
+            if focus_mode {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+                    if ui
+                        .small_button(label_for(plain, "⤢ Exit focus", "Exit focus"))
+                        .on_hover_text("Restore panels (F11)")
+                        .clicked()
+                    {
+                        self.toggle_focus_mode();
+                    }
+                });
+            } else {
+                let shown_state = self.displayed_visual_state();
+                let brand = self.brand_name();
+                let now = std::time::Instant::now();
+                let pose = if self.ui_prefs.idle_animation && shown_state == AgentVisualState::Idle
+                {
+                    ctx.request_repaint_after(self.idle_heartbeat.repaint_after(now));
+                    self.idle_heartbeat.pose(now)
+                } else {
+                    self.idle_heartbeat.rest(now);
+                    IdlePose::default()
+                };
+                ui.horizontal(|ui| {
+                    super::sprite::render_agent_sprite(
+                        ui,
+                        &shown_state,
+                        self.avatars.as_mut(),
+                        pose,
+                        &self.ui_prefs.sprite_fallback,
+                    );
+                    ui.vertical(|ui| {
+                        ui.heading(&brand);
+                        ui.horizontal_wrapped(|ui| {
+                            let (state_text, state_color) =
+                                visual_state_display(
+                                    &shown_state,
+                                    plain,
+                                    &self.ui_prefs.sprite_fallback,
+                                );
+                            ui.label(
+                                egui::RichText::new(state_text)
+                                    .color(state_color)
+                                    .small()
+                                    .strong(),
+                            );
+                            // Show how long the agent has been in the current state.
+                            // This makes "stuck Thinking" immediately visible.
+                            if let Some(since) = self.visual_state_since {
+                                let elapsed = chrono::Utc::now()
+                                    .signed_duration_since(since)
+                                    .num_seconds()
+                                    .max(0) as u64;
+                                if elapsed >= 3 {
+                                    ui.label(
+                                        egui::RichText::new(format!(
+                                            "({})",
+                                            format_elapsed(elapsed)
+                                        ))
                                         .color(if elapsed > 30 {
                                             egui::Color32::YELLOW
                                         } else {
                                             egui::Color32::GRAY
                                         })
                                         .small(),
+                                    );
+                                }
+                            }
+                            if self.autonomy_paused {
+                                ui.label(
+                                    egui::RichText::new(label_for(plain, "💤 autonomy paused", "autonomy paused"))
+                                        .color(egui::Color32::GRAY)
+                                        .small(),
                                 );
                             }
-                        }
-                        if let Some(ref o) = self.last_orientation {
-                            ui.label(egui::RichText::new("|").weak().small());
-                            ui.label(
-                                egui::RichText::new(format!("🧭 {}", o.disposition))
-                                    .color(egui::Color32::LIGHT_YELLOW)
+                            if let Some(ref o) = self.last_orientation {
+                                ui.label(egui::RichText::new("|").weak().small());
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                    "{} {}",
+                                    label_for(plain, "🧭", "Orientation:"),
+                                    o.disposition
+                                ))
+                                        .color(egui::Color32::LIGHT_YELLOW)
+                                        .small(),
+                                );
+                            }
+                            if let Some(ref action) = self.last_action {
+                                ui.label(egui::RichText::new("|").weak().small());
+                                ui.label(
+                                    egui::RichText::new(truncate_str(
+                                        action,
+                                        self.ui_prefs.limits.header_action_chars,
+                                    ))
+                                    .weak()
                                     .small(),
-                            );
-                        }
-                        if let Some(ref action) = self.last_action {
-                            ui.label(egui::RichText::new("|").weak().small());
-                            ui.label(egui::RichText::new(truncate_str(action, 50)).weak().small());
-                        }
+                                );
+                            }
+                        });
                     });
-                });
 
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    let pause_text = "⏸ Pause";
-                    if ui.button(pause_text).clicked() {
-                        match self.runtime.block_on(self.api_client.toggle_pause()) {
-                            Ok(paused) => {
-                                self.current_state = if paused {
-                                    AgentVisualState::Paused
-                                } else {
-                                    AgentVisualState::Idle
-                                };
-                            }
-                            Err(error) => {
-                                tracing::error!("Failed to toggle pause: {}", error);
-                                self.push_ui_error(format!("Failed to toggle pause: {}", error));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let pause_text = label_for(plain, "⏸ Pause", "Pause");
+                        let pause_button = ui
+                            .button(pause_text)
+                            .on_hover_text("Right-click to pause only autonomous cycles");
+                        if pause_button.clicked() {
+                            self.toggle_pause();
+                        }
+                        pause_button.context_menu(|ui| {
+                            let label = if self.autonomy_paused {
+                                "Resume autonomy"
+                            } else {
+                                "Pause autonomy only (chat stays live)"
+                            };
+                            if ui.button(label).clicked() {
+                                self.toggle_autonomy_pause();
+                                ui.close_menu();
                             }
+                        });
+
+                        if ui.button(label_for(plain, "⏹ Stop Turn", "Stop Turn")).clicked() {
+                            self.stop_active_turn();
                         }
-                    }
 
-                    if ui.button("⏹ Stop Turn").clicked() {
-                        match self.runtime.block_on(self.api_client.stop_agent_turn()) {
-                            Ok(_) => {
-                                let active = self.active_conversation_id.clone();
-                                self.streaming_chat_preview = None;
-                                self.clear_live_tool_progress(&active);
-                                self.refresh_conversations();
-                                self.refresh_chat_history();
-                                self.current_state = AgentVisualState::Idle;
-                            }
-                            Err(error) => {
-                                tracing::error!("Failed to stop active turn: {}", error);
-                                self.push_ui_error(format!(
-                                    "Failed to stop active turn: {}",
-                                    error
-                                ));
+                        if ui
+                            .button(label_for(plain, "💡 Think now", "Think now"))
+                            .on_hover_text("Run an autonomous cycle immediately")
+                            .clicked()
+                        {
+                            self.trigger_cycle();
+                        }
+
+                        if self.loose_mode {
+                            if ui
+                                .button(
+                                    egui::RichText::new(label_for(plain, "⏹ Stop Loose", "Stop Loose"))
+                                        .color(egui::Color32::from_rgb(255, 120, 90)),
+                                )
+                                .on_hover_text("Disarm Loose mode and cancel the active episode")
+                                .clicked()
+                            {
+                                match self.block_on_api(self.api_client.set_loose_mode(false)) {
+                                    Ok(enabled) => {
+                                        self.loose_mode = enabled;
+                                        self.settings_panel.config.loose_mode = enabled;
+                                        self.current_state = AgentVisualState::Idle;
+                                    }
+                                    Err(error) => self.push_ui_error(format!(
+                                        "Failed to stop Loose mode: {}",
+                                        error
+                                    )),
+                                }
                             }
+                        } else if ui
+                            .button(label_for(plain, "▶ Let Run Loose", "Let Run Loose"))
+                            .on_hover_text("Arm self-directed autonomy on this machine")
+                            .clicked()
+                        {
+                            self.show_loose_arm_confirmation = true;
+                        }
+
+                        if ui.button(label_for(plain, "⚙ Settings", "Settings")).clicked() {
+                            self.open_settings();
+                        }
+
+                        if ui.button(label_for(plain, "🎭 Character", "Character")).clicked() {
+                            self.character_panel.show = true;
+                        }
+
+                        let activity_btn_text = if self.show_activity_panel {
+                            label_for(plain, "📋 Hide Activity", "Hide Activity")
+                        } else {
+                            label_for(plain, "📋 Show Activity", "Show Activity")
+                        };
+                        if ui.button(activity_btn_text).clicked() {
+                            self.show_activity_panel = !self.show_activity_panel;
                         }
-                    }
 
-                    if self.loose_mode {
                         if ui
-                            .button(
-                                egui::RichText::new("⏹ Stop Loose")
-                                    .color(egui::Color32::from_rgb(255, 120, 90)),
-                            )
-                            .on_hover_text("Disarm Loose mode and cancel the active episode")
+                            .button(label_for(plain, "🎯 Focus", "Focus"))
+                            .on_hover_text("Hide everything but the chat (F11)")
                             .clicked()
                         {
-                            match self.runtime.block_on(self.api_client.set_loose_mode(false)) {
-                                Ok(enabled) => {
-                                    self.loose_mode = enabled;
-                                    self.settings_panel.config.loose_mode = enabled;
-                                    self.current_state = AgentVisualState::Idle;
+                            self.toggle_focus_mode();
+                        }
+                    });
+                });
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Conversation:");
+                    let previous_conversation_id = self.active_conversation_id.clone();
+                    let selected_text = self
+                        .conversations
+                        .iter()
+                        .find(|c| c.id == self.active_conversation_id)
+                        .map(conversation_display_label)
+                        .unwrap_or_else(|| "Default chat".to_string());
+
+                    egui::ComboBox::from_id_salt("chat_conversation_picker")
+                        .selected_text(selected_text)
+                        .show_ui(ui, |ui| {
+                            let tag_filter = self.conversation_tag_filter.as_deref();
+                            for (group, members) in grouped_conversations(&self.conversations) {
+                                let visible: Vec<_> = members
+                                    .into_iter()
+                                    .filter(|conversation| {
+                                        conversation.id == previous_conversation_id
+                                            || tag_filter.is_none_or(|tag| {
+                                                conversation_has_tag(conversation, tag)
+                                            })
+                                    })
+                                    .collect();
+                                if visible.is_empty() {
+                                    continue;
+                                }
+                                // Ungrouped conversations lead the list without a heading.
+                                if let Some(group) = group {
+                                    ui.add_space(2.0);
+                                    ui.label(egui::RichText::new(group).small().strong());
+                                }
+                                for conversation in visible {
+                                    ui.selectable_value(
+                                        &mut self.active_conversation_id,
+                                        conversation.id.clone(),
+                                        conversation_display_label(conversation),
+                                    );
                                 }
-                                Err(error) => self
-                                    .push_ui_error(format!("Failed to stop Loose mode: {}", error)),
                             }
-                        }
-                    } else if ui
-                        .button("▶ Let Run Loose")
-                        .on_hover_text("Arm self-directed autonomy on this machine")
+                        });
+
+                    if ui.button("New Chat").clicked() {
+                        self.create_new_conversation();
+                    }
+
+                    if ui
+                        .button("Import…")
+                        .on_hover_text("Open an exported chat (OpenAI-style JSON) as a new conversation")
+                        .clicked()
+                    {
+                        self.import_conversation_from_file();
+                    }
+
+                    if ui
+                        .button("Organize…")
+                        .on_hover_text("Group conversations and drag them into order")
                         .clicked()
                     {
-                        self.show_loose_arm_confirmation = true;
+                        self.conversation_organizer.open = true;
                     }
 
-                    if ui.button("⚙ Settings").clicked() {
-                        self.settings_panel.open();
-                        self.refresh_scheduled_jobs();
+                    if ui
+                        .button("Archived…")
+                        .on_hover_text("Browse and restore archived conversations")
+                        .clicked()
+                    {
+                        self.open_archived_conversations();
                     }
 
-                    if ui.button("🎭 Character").clicked() {
-                        self.character_panel.show = true;
+                    if ui
+                        .button("Rename")
+                        .on_hover_text("Rename this conversation")
+                        .clicked()
+                    {
+                        self.begin_rename_active_conversation();
                     }
 
-                    let activity_btn_text = if self.show_activity_panel {
-                        "📋 Hide Activity"
-                    } else {
-                        "📋 Show Activity"
-                    };
-                    if ui.button(activity_btn_text).clicked() {
-                        self.show_activity_panel = !self.show_activity_panel;
+                    if ui
+                        .button("Summarize")
+                        .on_hover_text("Get a short recap of this conversation")
+                        .clicked()
+                    {
+                        self.open_conversation_summary();
                     }
-                });
-            });
 
-            ui.separator();
+                    let mut all_tags = self
+                        .conversations
+                        .iter()
+                        .flat_map(|conversation| conversation.tags.iter().cloned())
+                        .collect::<Vec<_>>();
+                    all_tags.sort_by_key(|tag| tag.to_lowercase());
+                    all_tags.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+                    if !all_tags.is_empty() {
+                        egui::ComboBox::from_id_salt("chat_conversation_tag_filter")
+                            .selected_text(
+                                self.conversation_tag_filter
+                                    .as_deref()
+                                    .map(|tag| format!("#{}", tag))
+                                    .unwrap_or_else(|| "All tags".to_string()),
+                            )
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(
+                                    &mut self.conversation_tag_filter,
+                                    None,
+                                    "All tags",
+                                );
+                                for tag in all_tags {
+                                    let label = format!("#{}", tag);
+                                    ui.selectable_value(
+                                        &mut self.conversation_tag_filter,
+                                        Some(tag),
+                                        label,
+                                    );
+                                }
+                            });
+                    }
 
-            ui.horizontal(|ui| {
-                ui.label("Conversation:");
-                let previous_conversation_id = self.active_conversation_id.clone();
-                let selected_text = self
-                    .conversations
-                    .iter()
-                    .find(|c| c.id == self.active_conversation_id)
-                    .map(conversation_display_label)
-                    .unwrap_or_else(|| "Default chat".to_string());
-
-                egui::ComboBox::from_id_salt("chat_conversation_picker")
-                    .selected_text(selected_text)
-                    .show_ui(ui, |ui| {
-                        for conversation in &self.conversations {
-                            ui.selectable_value(
-                                &mut self.active_conversation_id,
-                                conversation.id.clone(),
-                                conversation_display_label(conversation),
-                            );
-                        }
+                    let active_note = self
+                        .conversations
+                        .iter()
+                        .find(|c| c.id == self.active_conversation_id)
+                        .and_then(|c| c.note.clone())
+                        .filter(|note| !note.trim().is_empty());
+                    let details_button = ui.button(if active_note.is_some() {
+                        label_for(plain, "Details 📝", "Details (note)")
+                    } else {
+                        "Details"
                     });
+                    let details_button = match active_note.as_deref() {
+                        Some(note) => details_button.on_hover_text(note),
+                        None => details_button
+                            .on_hover_text("Edit tags, a private note, and a system prompt prefix"),
+                    };
+                    if details_button.clicked() {
+                        let active = self
+                            .conversations
+                            .iter()
+                            .find(|c| c.id == self.active_conversation_id);
+                        self.conversation_details = Some(ConversationDetailsDraft {
+                            conversation_id: self.active_conversation_id.clone(),
+                            tags_text: active.map(|c| c.tags.join(", ")).unwrap_or_default(),
+                            note: active.and_then(|c| c.note.clone()).unwrap_or_default(),
+                            system_prompt_prefix: active
+                                .and_then(|c| c.system_prompt_prefix.clone())
+                                .unwrap_or_default(),
+                            seed: active.and_then(|c| c.seed),
+                            pinned: active.is_some_and(|c| c.pinned),
+                        });
+                    }
 
-                if ui.button("New Chat").clicked() {
-                    self.create_new_conversation();
-                }
+                    if ui
+                        .button(
+                            egui::RichText::new("Delete")
+                                .color(egui::Color32::from_rgb(200, 80, 80)),
+                        )
+                        .on_hover_text("Delete this conversation")
+                        .clicked()
+                    {
+                        self.confirm_delete_conversation_id =
+                            Some(self.active_conversation_id.clone());
+                    }
 
-                if ui
-                    .button("Rename")
-                    .on_hover_text("Rename this conversation")
-                    .clicked()
-                {
-                    let current_title = self
+                    let current_tool_mode = self
                         .conversations
                         .iter()
                         .find(|c| c.id == self.active_conversation_id)
-                        .map(|c| c.title.clone())
+                        .map(|c| c.tool_mode)
                         .unwrap_or_default();
-                    self.rename_conversation =
-                        Some((self.active_conversation_id.clone(), current_title));
-                }
+                    let mut tool_mode = current_tool_mode;
+                    egui::ComboBox::from_id_salt("chat_conversation_tool_mode")
+                        .selected_text(tool_mode.label())
+                        .show_ui(ui, |ui| {
+                            for mode in ToolMode::ALL {
+                                ui.selectable_value(&mut tool_mode, mode, mode.label())
+                                    .on_hover_text(mode.description());
+                            }
+                        })
+                        .response
+                        .on_hover_text(tool_mode.description());
+                    if tool_mode != current_tool_mode {
+                        self.set_active_tool_mode(tool_mode);
+                    }
 
-                if ui
-                    .button(
-                        egui::RichText::new("Delete").color(egui::Color32::from_rgb(200, 80, 80)),
-                    )
-                    .on_hover_text("Delete this conversation")
-                    .clicked()
-                {
-                    self.confirm_delete_conversation_id = Some(self.active_conversation_id.clone());
-                }
+                    if let Some(usage) = self
+                        .conversations
+                        .iter()
+                        .find(|c| c.id == self.active_conversation_id)
+                        .and_then(|c| c.usage)
+                    {
+                        let cost = usage
+                            .estimated_cost_usd
+                            .map(|cost| format!("${:.4}", cost))
+                            .unwrap_or_else(|| "no price set for this model".to_string());
+                        ui.label(egui::RichText::new(usage.summary()).small().weak())
+                            .on_hover_text(format!(
+                                "Prompt tokens: {}\nCompletion tokens: {}\nEstimated cost: {}",
+                                usage.prompt_tokens, usage.completion_tokens, cost
+                            ));
+                    }
 
-                if self.active_conversation_id != previous_conversation_id {
-                    self.streaming_chat_preview = None;
-                    self.refresh_chat_history();
-                }
-            });
-            ui.add_space(6.0);
+                    if self.active_conversation_id != previous_conversation_id {
+                        self.streaming_chat_preview = None;
+                        self.refresh_chat_history();
+                    }
+                });
+                ui.add_space(6.0);
+            }
 
             let active_streaming_preview = self
                 .streaming_chat_preview
@@ -1117,26 +3709,106 @@ impl eframe::App for AgentApp {
             };
             let chat_height = (ui.available_height() - composer_reserved - live_reserved).max(0.0);
 
-            let mut requested_prompt_turn_id: Option<String> = None;
+            let speaker_names = ChatSpeakerNames::resolve(
+                self.ui_prefs.chat_names,
+                &self.applied_config.character_name,
+                &self.applied_config.username,
+                &self.ui_prefs.operator_name,
+            );
+            let awaiting_approval = self
+                .conversations
+                .iter()
+                .find(|c| c.id == self.active_conversation_id)
+                .is_some_and(|c| c.runtime_state == ChatTurnPhase::AwaitingApproval);
+            let mut requested_chat_action: Option<ChatPaneAction> = None;
             ui.allocate_ui_with_layout(
                 egui::vec2(ui.available_width(), chat_height),
                 egui::Layout::top_down(egui::Align::Min),
                 |ui| {
-                    requested_prompt_turn_id = super::chat::render_private_chat(
+                    requested_chat_action = super::chat::render_private_chat(
                         ui,
                         &self.chat_history,
                         active_streaming_preview.as_deref(),
                         &mut self.chat_media_cache,
+                        &self.turn_tool_calls,
+                        self.failed_sends
+                            .get(&self.active_conversation_id)
+                            .map(Vec::as_slice)
+                            .unwrap_or_default(),
+                        self.compactions
+                            .get(&self.active_conversation_id)
+                            .map(Vec::as_slice)
+                            .unwrap_or_default(),
+                        &speaker_names,
+                        self.ui_prefs.long_reply_chars,
+                        awaiting_approval,
+                        self.chat_scroll_states
+                            .entry(self.active_conversation_id.clone())
+                            .or_default(),
+                        self.ui_prefs.plain_labels,
                     );
                 },
             );
-            if let Some(turn_id) = requested_prompt_turn_id {
-                self.open_prompt_inspector_for_turn(&turn_id);
+            match requested_chat_action {
+                Some(ChatPaneAction::ViewPrompt { turn_id }) => {
+                    self.open_prompt_inspector_for_turn(&turn_id);
+                }
+                Some(ChatPaneAction::ContinueMessage { message_id }) => {
+                    self.continue_chat_message(&message_id);
+                }
+                Some(ChatPaneAction::BranchFrom { message_id }) => {
+                    self.branch_conversation_at(&message_id);
+                }
+                Some(ChatPaneAction::RateTurn { turn_id, rating }) => {
+                    self.rate_turn(&turn_id, rating);
+                }
+                Some(ChatPaneAction::Remember { message_id }) => {
+                    if let Some(message) =
+                        self.chat_history.iter().find(|message| message.id == message_id)
+                    {
+                        self.remember_draft = Some(RememberDraft {
+                            conversation_id: self.active_conversation_id.clone(),
+                            // The text shown in the bubble, without tool/thinking/media blocks.
+                            text: parse_chat_payload(&message.content).display_content,
+                            this_chat_only: false,
+                        });
+                    }
+                }
+                Some(ChatPaneAction::LoadTurnTools { turn_id }) => {
+                    self.load_turn_tool_calls(&turn_id);
+                }
+                Some(ChatPaneAction::RetryFailedSend { failed_id }) => {
+                    self.retry_failed_send(failed_id);
+                }
+                Some(ChatPaneAction::DiscardFailedSend { failed_id }) => {
+                    let conversation_id = self.active_conversation_id.clone();
+                    take_failed_send(&mut self.failed_sends, &conversation_id, failed_id);
+                }
+                None => {}
             }
 
             if !active_progress.is_empty() {
                 ui.add_space(6.0);
-                egui::CollapsingHeader::new(egui::RichText::new("⚡ Live Agent Turn").strong())
+                if let Some((iteration, max_iterations)) = active_progress
+                    .iter()
+                    .rev()
+                    .find_map(|entry| entry.iteration_budget)
+                {
+                    let color = if iteration_budget_near_limit(iteration, max_iterations) {
+                        egui::Color32::from_rgb(230, 170, 80)
+                    } else {
+                        egui::Color32::GRAY
+                    };
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "iteration {} of {}",
+                            iteration, max_iterations
+                        ))
+                        .small()
+                        .color(color),
+                    );
+                }
+                egui::CollapsingHeader::new(egui::RichText::new(label_for(plain, "⚡ Live Agent Turn", "Live Agent Turn")).strong())
                     .id_salt("live_agent_turn")
                     .default_open(true)
                     .show(ui, |ui| {
@@ -1145,8 +3817,8 @@ impl eframe::App for AgentApp {
                             .stick_to_bottom(true)
                             .id_salt("live_turn_scroll")
                             .show(ui, |ui| {
-                                for entry in &active_progress {
-                                    render_live_tool_entry(ui, entry);
+                                for (idx, entry) in active_progress.iter().enumerate() {
+                                    render_live_tool_entry(ui, idx, entry, &self.ui_prefs.limits);
                                 }
                             });
                     });
@@ -1161,7 +3833,7 @@ impl eframe::App for AgentApp {
             );
             ui.add_space(4.0);
             ui.horizontal(|ui| {
-                ui.label("💬");
+                ui.label(label_for(plain, "💬", "Message:"));
                 let response = ui.add_sized(
                     [ui.available_width() - 80.0, 68.0],
                     egui::TextEdit::multiline(&mut self.user_input)
@@ -1228,7 +3900,7 @@ impl eframe::App for AgentApp {
                     });
                 });
             if arm {
-                match self.runtime.block_on(self.api_client.set_loose_mode(true)) {
+                match self.block_on_api(self.api_client.set_loose_mode(true)) {
                     Ok(enabled) => {
                         self.loose_mode = enabled;
                         self.settings_panel.config.loose_mode = enabled;
@@ -1302,12 +3974,151 @@ impl eframe::App for AgentApp {
                                 .desired_width(280.0)
                                 .hint_text("Conversation title"),
                         );
-                        response.request_focus();
-                        ui.add_space(8.0);
+                        response.request_focus();
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            if ui.button("Rename").clicked()
+                                || ui.input(|i| i.key_pressed(egui::Key::Enter))
+                            {
+                                confirmed = true;
+                            }
+                            if ui.button("Cancel").clicked() {
+                                cancelled = true;
+                            }
+                        });
+                    }
+                });
+            if confirmed {
+                if let Some((id, draft)) = self.rename_conversation.take() {
+                    let title = draft.trim().to_string();
+                    if !title.is_empty() {
+                        self.rename_conversation(&id, &title);
+                    }
+                }
+            } else if cancelled || !open {
+                self.rename_conversation = None;
+            }
+        }
+
+        // "Remember this" dialog.
+        if self.remember_draft.is_some() {
+            let mut open = true;
+            let mut confirmed = false;
+            let mut cancelled = false;
+            egui::Window::new("Remember")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    if let Some(ref mut draft) = self.remember_draft {
+                        ui.label(
+                            egui::RichText::new("Edit what the agent should keep in mind.")
+                                .small()
+                                .weak(),
+                        );
+                        egui::ScrollArea::vertical()
+                            .max_height(220.0)
+                            .show(ui, |ui| {
+                                ui.add(
+                                    egui::TextEdit::multiline(&mut draft.text)
+                                        .desired_width(360.0)
+                                        .desired_rows(6),
+                                );
+                            });
+                        ui.checkbox(&mut draft.this_chat_only, "Only in this chat");
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(
+                                    !draft.text.trim().is_empty(),
+                                    egui::Button::new("Remember"),
+                                )
+                                .clicked()
+                            {
+                                confirmed = true;
+                            }
+                            if ui.button("Cancel").clicked() {
+                                cancelled = true;
+                            }
+                        });
+                    }
+                });
+            if confirmed {
+                if let Some(draft) = self.remember_draft.take() {
+                    let scope = draft
+                        .this_chat_only
+                        .then_some(draft.conversation_id.as_str());
+                    self.remember_text(draft.text.trim(), scope);
+                }
+            } else if cancelled || !open {
+                self.remember_draft = None;
+            }
+        }
+
+        // Conversation tags/note editor.
+        if self.conversation_details.is_some() {
+            let mut open = true;
+            let mut confirmed = false;
+            let mut cancelled = false;
+            egui::Window::new("Conversation Details")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    if let Some(draft) = self.conversation_details.as_mut() {
+                        ui.label("Tags (comma separated):");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut draft.tags_text)
+                                .desired_width(320.0)
+                                .hint_text("work, research"),
+                        );
+                        ui.add_space(6.0);
+                        ui.label("Private note:");
+                        ui.add(
+                            egui::TextEdit::multiline(&mut draft.note)
+                                .desired_rows(4)
+                                .desired_width(320.0),
+                        );
+                        ui.add_space(6.0);
+                        ui.label("System prompt prefix:");
+                        ui.add(
+                            egui::TextEdit::multiline(&mut draft.system_prompt_prefix)
+                                .desired_rows(4)
+                                .desired_width(320.0)
+                                .hint_text("e.g. This chat is about Rust code; be terse."),
+                        );
+                        ui.label(
+                            egui::RichText::new(
+                                "Added before the character prompt for this conversation only. Leave empty to use the global prompt.",
+                            )
+                            .small()
+                            .weak(),
+                        );
+                        ui.add_space(6.0);
                         ui.horizontal(|ui| {
-                            if ui.button("Rename").clicked()
-                                || ui.input(|i| i.key_pressed(egui::Key::Enter))
+                            let mut fixed = draft.seed.is_some();
+                            if ui
+                                .checkbox(&mut fixed, "Fixed seed")
+                                .on_hover_text(
+                                    "Reuse one sampling seed for every turn so a reply can be reproduced (servers that support it)",
+                                )
+                                .changed()
                             {
+                                draft.seed = fixed.then(rand_seed);
+                            }
+                            if let Some(seed) = draft.seed.as_mut() {
+                                ui.add(egui::DragValue::new(seed));
+                                if ui.small_button("New").clicked() {
+                                    *seed = rand_seed();
+                                }
+                            }
+                        });
+                        ui.checkbox(&mut draft.pinned, "Pinned (never auto-archived)");
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            if ui.button("Save").clicked() {
                                 confirmed = true;
                             }
                             if ui.button("Cancel").clicked() {
@@ -1317,14 +4128,11 @@ impl eframe::App for AgentApp {
                     }
                 });
             if confirmed {
-                if let Some((id, draft)) = self.rename_conversation.take() {
-                    let title = draft.trim().to_string();
-                    if !title.is_empty() {
-                        self.rename_conversation(&id, &title);
-                    }
+                if let Some(draft) = self.conversation_details.take() {
+                    self.save_conversation_details(&draft);
                 }
             } else if cancelled || !open {
-                self.rename_conversation = None;
+                self.conversation_details = None;
             }
         }
 
@@ -1368,8 +4176,62 @@ impl eframe::App for AgentApp {
             }
         }
 
+        if let Some(window) = self.action_explanation.as_ref() {
+            let mut open = true;
+            let mut inspect_turn: Option<String> = None;
+            egui::Window::new(format!(
+                "Why: {}",
+                truncate_str(&window.action, self.ui_prefs.limits.short_label_chars)
+            ))
+            .open(&mut open)
+            .resizable(true)
+            .vscroll(true)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                if let Some(error) = window.error.as_deref() {
+                    ui.colored_label(egui::Color32::from_rgb(220, 120, 120), error);
+                    return;
+                }
+                let Some(explanation) = window.explanation.as_ref() else {
+                    return;
+                };
+                let sections = [
+                    ("Orientation", explanation.orientation.as_deref()),
+                    ("Decision", explanation.decision.as_deref()),
+                    ("Result", explanation.result.as_deref()),
+                ];
+                for (heading, text) in sections {
+                    if let Some(text) = text.filter(|text| !text.trim().is_empty()) {
+                        ui.label(egui::RichText::new(heading).strong());
+                        ui.label(text);
+                        ui.add_space(6.0);
+                    }
+                }
+                if let Some(section) = explanation.prompt_section.as_deref() {
+                    ui.label(egui::RichText::new("Prompt context").strong());
+                    ui.label(egui::RichText::new(section).monospace().small());
+                    ui.add_space(6.0);
+                }
+                if let Some(turn_id) = explanation.turn_id.as_deref() {
+                    if ui.button("Open full turn prompt").clicked() {
+                        inspect_turn = Some(turn_id.to_string());
+                    }
+                }
+            });
+            if !open {
+                self.action_explanation = None;
+            }
+            if let Some(turn_id) = inspect_turn {
+                self.open_prompt_inspector_for_turn(&turn_id);
+            }
+        }
+
+        let token_budget = self.ui_prefs.prompt_token_budget;
+        let limits = self.ui_prefs.limits;
+        let plain = self.ui_prefs.plain_labels;
         if let Some(inspector) = self.prompt_inspector.as_mut() {
             let mut open = inspector.open;
+            let mut copy_curl = false;
             egui::Window::new(format!(
                 "Turn Prompt · {}",
                 &inspector.turn_id.chars().take(12).collect::<String>()
@@ -1378,11 +4240,30 @@ impl eframe::App for AgentApp {
             .resizable(true)
             .vscroll(true)
             .show(ctx, |ui| {
-                ui.label(
-                    egui::RichText::new(format!("Turn ID: {}", inspector.turn_id))
-                        .small()
-                        .weak(),
-                );
+                let turn_label = match inspector.seed {
+                    Some(seed) => format!("Turn ID: {} · seed {}", inspector.turn_id, seed),
+                    None => format!("Turn ID: {}", inspector.turn_id),
+                };
+                ui.label(egui::RichText::new(turn_label).small().weak());
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Copy as curl")
+                        .on_hover_text(
+                            "Copy the most recent LLM request (token masked) as a curl command",
+                        )
+                        .clicked()
+                    {
+                        copy_curl = true;
+                    }
+                    if let Some(status) = inspector.curl_status.as_deref() {
+                        ui.label(egui::RichText::new(status).small().weak());
+                    }
+                });
+                if let Some(timing) = &inspector.timing {
+                    ui.add_space(6.0);
+                    render_turn_timing(ui, timing, &limits, plain);
+                }
                 ui.add_space(6.0);
                 if let Some(error) = inspector.error.as_deref() {
                     ui.colored_label(egui::Color32::LIGHT_RED, error);
@@ -1413,9 +4294,21 @@ impl eframe::App for AgentApp {
                         }
                     });
                     ui.add_space(6.0);
+                    render_prompt_token_summary(
+                        ui,
+                        &inspector.prompt_text,
+                        &inspector.system_prompt_text,
+                        token_budget,
+                        &limits,
+                    );
+                    ui.add_space(6.0);
                     ui.label(egui::RichText::new("Context Prompt").strong());
                     if inspector.highlight_sections {
-                        render_highlighted_prompt_sections(ui, &inspector.prompt_text);
+                        render_highlighted_prompt_sections(
+                            ui,
+                            &inspector.prompt_text,
+                            token_budget,
+                        );
                     } else {
                         let mut prompt_text = inspector.prompt_text.clone();
                         ui.add(
@@ -1440,7 +4333,11 @@ impl eframe::App for AgentApp {
                                 .weak(),
                             );
                         } else if inspector.highlight_sections {
-                            render_highlighted_prompt_sections(ui, &inspector.system_prompt_text);
+                            render_highlighted_prompt_sections(
+                                ui,
+                                &inspector.system_prompt_text,
+                                token_budget,
+                            );
                         } else {
                             let mut system_prompt = inspector.system_prompt_text.clone();
                             ui.add(
@@ -1455,6 +4352,21 @@ impl eframe::App for AgentApp {
                 }
             });
             inspector.open = open;
+            if copy_curl {
+                inspector.curl_status = Some(
+                    match block_on_bounded(
+                        &self.runtime,
+                        self.api_client.request_timeout() * 2,
+                        self.api_client.get_last_llm_request(),
+                    ) {
+                        Ok(capture) => {
+                            ctx.copy_text(capture.to_curl());
+                            "Copied last LLM request".to_string()
+                        }
+                        Err(error) => format!("Copy failed: {}", error),
+                    },
+                );
+            }
             if !inspector.open {
                 self.prompt_inspector = None;
             }
@@ -1467,13 +4379,73 @@ impl eframe::App for AgentApp {
         if !scheduled_job_actions.is_empty() {
             self.apply_scheduled_job_actions(scheduled_job_actions);
         }
+        let tool_actions = self.settings_panel.take_tool_actions();
+        if !tool_actions.is_empty() {
+            self.apply_tool_actions(tool_actions);
+        }
+        if let Some(flags) = self.settings_panel.take_config_flags_update() {
+            self.apply_config_flags(flags);
+        }
+        if let Some(display) = self.settings_panel.take_display_settings() {
+            self.apply_display_settings(display);
+        }
+        let storage_actions = self.settings_panel.take_storage_actions();
+        if !storage_actions.is_empty() {
+            self.apply_storage_actions(storage_actions, ctx);
+        }
+        self.poll_database_transfer();
 
         if let Some(new_config) = self.character_panel.render(ctx) {
             self.persist_config(new_config);
         }
 
+        if let Some(request) = self.model_compare.render(ctx, &self.active_conversation_id) {
+            self.start_model_comparison(request, ctx);
+        }
+
+        if let Some(request) = self.conversation_summary.render(ctx) {
+            self.start_conversation_summary(request, ctx);
+        }
+
+        if self.settings_panel.take_diagnostics_request() || self.diagnostics.render(ctx) {
+            self.start_diagnostics(ctx);
+        }
+        if self.settings_panel.take_debug_dump_request() {
+            self.save_debug_dump();
+        }
+
+        match self.quick_ask.render(ctx) {
+            Some(QuickAskAction::Ask(message)) => self.send_quick_ask(&message),
+            Some(QuickAskAction::OpenInApp(conversation_id)) => {
+                self.active_conversation_id = conversation_id;
+                self.refresh_chat_history();
+                ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+                ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+            }
+            None => {}
+        }
+
+        match self.archived_conversations.render(ctx) {
+            Some(ArchiveAction::Refresh) => self.refresh_archived_conversations(),
+            Some(ArchiveAction::Restore {
+                conversation_id,
+                open,
+            }) => self.restore_conversation(&conversation_id, open),
+            None => {}
+        }
+
+        if let Some(placements) = self.conversation_organizer.render(ctx, &self.conversations) {
+            match self.block_on_api(self.api_client.reorder_conversations(&placements)) {
+                Ok(()) => self.refresh_conversations(),
+                Err(error) => {
+                    tracing::error!("Failed to reorder conversations: {:#}", error);
+                    self.push_ui_error(format!("Failed to reorder conversations: {:#}", error));
+                }
+            }
+        }
+
         if let Some(ref tool) = approve_tool {
-            match self.runtime.block_on(self.api_client.approve_tool(tool)) {
+            match self.block_on_api(self.api_client.approve_tool(tool)) {
                 Ok(()) => tracing::info!("Session approval granted for: {}", tool),
                 Err(e) => self.push_ui_error(format!("Failed to approve tool: {}", e)),
             }
@@ -1482,25 +4454,79 @@ impl eframe::App for AgentApp {
         if let Some(ref tool) = dismiss_tool {
             self.pending_approvals.retain(|(t, _)| t != tool);
         }
+        if let Some((id, approve)) = resolve_proposal {
+            self.resolve_config_proposal(&id, approve);
+        }
 
         ctx.request_repaint_after(std::time::Duration::from_millis(100));
     }
 }
 
-fn visual_state_display(state: &AgentVisualState) -> (&'static str, egui::Color32) {
-    match state {
-        AgentVisualState::Idle => ("💤 Idle", egui::Color32::from_gray(150)),
-        AgentVisualState::Reading => ("👁 Reading", egui::Color32::from_rgb(200, 200, 100)),
-        AgentVisualState::Thinking => ("🤔 Thinking", egui::Color32::LIGHT_BLUE),
-        AgentVisualState::Writing => ("✍ Writing", egui::Color32::LIGHT_GREEN),
-        AgentVisualState::Happy => ("😊 Happy", egui::Color32::from_rgb(100, 255, 150)),
-        AgentVisualState::Confused => ("😕 Confused", egui::Color32::from_rgb(255, 150, 100)),
-        AgentVisualState::Paused => ("⏸ Paused", egui::Color32::GRAY),
+/// Name shown in the window title and header when nothing else is set.
+pub const DEFAULT_BRAND_NAME: &str = "Ponderer";
+
+/// Window title and header name: the Display-tab override, then the character
+/// card name, then `DEFAULT_BRAND_NAME`. Blank names are skipped.
+pub fn brand_name(agent_name: &str, character_name: &str) -> String {
+    [agent_name, character_name]
+        .into_iter()
+        .map(str::trim)
+        .find(|name| !name.is_empty())
+        .unwrap_or(DEFAULT_BRAND_NAME)
+        .to_string()
+}
+
+/// Status-strip label for `state`. The glyph comes from the same
+/// `sprite::resolve_fallback` mapping as the sprite, so custom glyphs show here too.
+fn visual_state_display(
+    state: &AgentVisualState,
+    plain: bool,
+    fallback: &SpriteFallback,
+) -> (String, egui::Color32) {
+    let (text, color) = match state {
+        AgentVisualState::Idle => ("Idle", egui::Color32::from_gray(150)),
+        AgentVisualState::Reading => ("Reading", egui::Color32::from_rgb(200, 200, 100)),
+        AgentVisualState::Thinking => ("Thinking", egui::Color32::LIGHT_BLUE),
+        AgentVisualState::Writing => ("Writing", egui::Color32::LIGHT_GREEN),
+        AgentVisualState::Happy => ("Happy", egui::Color32::from_rgb(100, 255, 150)),
+        AgentVisualState::Confused => ("Confused", egui::Color32::from_rgb(255, 150, 100)),
+        AgentVisualState::Paused => ("Paused", egui::Color32::GRAY),
+        AgentVisualState::WaitingForUser => {
+            ("Waiting for you", egui::Color32::from_rgb(230, 170, 80))
+        }
+    };
+    if plain {
+        return (text.to_string(), color);
+    }
+    let (glyph, _) = resolve_fallback(state, fallback);
+    (format!("{} {}", glyph, text), color)
+}
+
+/// Fresh seed for the conversation "Fixed seed" toggle. Kept below 2^53 so it
+/// survives JSON number round-trips in any client.
+fn rand_seed() -> u64 {
+    uuid::Uuid::new_v4().as_u64_pair().0 >> 11
+}
+
+/// Picks the emoji or plain-text variant of a label. Plain labels (`UiPrefs::plain_labels`)
+/// read better through screen readers and avoid tofu on fonts without emoji.
+pub(crate) fn label_for<'a>(plain: bool, emoji: &'a str, plain_text: &'a str) -> &'a str {
+    if plain {
+        plain_text
+    } else {
+        emoji
     }
 }
 
-fn render_live_tool_entry(ui: &mut egui::Ui, entry: &LiveToolProgress) {
+fn render_live_tool_entry(
+    ui: &mut egui::Ui,
+    idx: usize,
+    entry: &LiveToolProgress,
+    limits: &UiLimits,
+) {
     let color = tool_badge_color(&entry.tool_name);
+    // Finished JSON output gets a tree; partial chunks fail to parse and stay text.
+    let json_output = parse_structured_json(&entry.output_preview);
     ui.horizontal_wrapped(|ui| {
         ui.label(
             egui::RichText::new(&entry.tool_name)
@@ -1515,7 +4541,18 @@ fn render_live_tool_entry(ui: &mut egui::Ui, entry: &LiveToolProgress) {
                     .small(),
             );
         }
-        let output = truncate_str(&entry.output_preview, 200);
+        if let Some(ref key) = entry.argument_key {
+            ui.label(
+                egui::RichText::new(truncate_str(key, limits.tool_output_chars))
+                    .monospace()
+                    .small()
+                    .color(egui::Color32::from_gray(230)),
+            );
+        }
+        if json_output.is_some() {
+            return;
+        }
+        let output = truncate_str(&entry.output_preview, limits.tool_output_chars);
         let wrapped_output = wrap_text_for_ui_width(&output, ui.available_width());
         ui.add(
             egui::Label::new(
@@ -1526,6 +4563,11 @@ fn render_live_tool_entry(ui: &mut egui::Ui, entry: &LiveToolProgress) {
             .wrap(),
         );
     });
+    if let Some(value) = json_output {
+        ui.indent(("live_tool_json", idx), |ui| {
+            render_json_tree(ui, ("live_tool", idx, &entry.tool_call_id), &value);
+        });
+    }
     ui.add_space(2.0);
 }
 
@@ -1533,9 +4575,16 @@ fn tool_badge_color(tool_name: &str) -> egui::Color32 {
     let name = tool_name.to_ascii_lowercase();
     if name.starts_with("shell") || name.contains("run_command") || name.contains("bash") {
         egui::Color32::from_rgb(255, 200, 60)
-    } else if name.contains("file") || name.starts_with("read") || name.starts_with("write") {
+    } else if name.contains("file")
+        || (name.starts_with("read") && !name.contains("url"))
+        || name.starts_with("write")
+    {
         egui::Color32::from_rgb(100, 160, 255)
-    } else if name.starts_with("http") || name.starts_with("web") || name.starts_with("fetch") {
+    } else if name.starts_with("http")
+        || name.starts_with("web")
+        || name.starts_with("fetch")
+        || name.contains("url")
+    {
         egui::Color32::from_rgb(200, 100, 255)
     } else if name.starts_with("memory")
         || name.starts_with("recall")
@@ -1562,16 +4611,13 @@ fn format_elapsed(secs: u64) -> String {
     }
 }
 
-fn truncate_str(text: &str, max_chars: usize) -> String {
-    let mut out = String::with_capacity(text.len().min(max_chars + 4));
-    for (i, ch) in text.chars().enumerate() {
-        if i >= max_chars {
-            out.push('…');
-            break;
-        }
-        out.push(ch);
+/// Keeps the first `max_chars` user-perceived characters (grapheme clusters),
+/// so a ZWJ emoji or a letter with combining marks is never split.
+pub(crate) fn truncate_str(text: &str, max_chars: usize) -> String {
+    match text.grapheme_indices(true).nth(max_chars) {
+        Some((cut, _)) => format!("{}…", &text[..cut]),
+        None => text.to_string(),
     }
-    out
 }
 
 fn max_token_len_for_width(width: f32) -> usize {
@@ -1583,10 +4629,10 @@ fn wrap_text_for_ui_width(input: &str, width: f32) -> String {
     let mut out = String::with_capacity(input.len());
     let mut run_len = 0usize;
 
-    for ch in input.chars() {
-        if ch.is_whitespace() {
+    for grapheme in input.graphemes(true) {
+        if grapheme.chars().all(char::is_whitespace) {
             run_len = 0;
-            out.push(ch);
+            out.push_str(grapheme);
             continue;
         }
 
@@ -1595,19 +4641,26 @@ fn wrap_text_for_ui_width(input: &str, width: f32) -> String {
             run_len = 0;
         }
 
-        out.push(ch);
+        out.push_str(grapheme);
         run_len += 1;
     }
 
     out
 }
 
-fn last_n_chars(text: &str, n: usize) -> String {
-    let chars: Vec<char> = text.chars().collect();
-    if chars.len() <= n {
-        text.to_string()
-    } else {
-        chars[chars.len() - n..].iter().collect()
+/// Trailing `max_chars` of the live stream, prefixed with `…` when the start
+/// was dropped so a clipped window is not mistaken for the whole thought.
+fn live_stream_window(text: &str, max_chars: usize) -> String {
+    if max_chars == 0 {
+        return if text.is_empty() {
+            String::new()
+        } else {
+            "…".to_string()
+        };
+    }
+    match text.grapheme_indices(true).nth_back(max_chars - 1) {
+        Some((0, _)) | None => text.to_string(),
+        Some((start, _)) => format!("…{}", &text[start..]),
     }
 }
 
@@ -1631,7 +4684,185 @@ struct PromptSection {
     body: String,
 }
 
-fn render_highlighted_prompt_sections(ui: &mut egui::Ui, prompt: &str) {
+/// Rough BPE-style token estimate, since the desktop has no model tokenizer:
+/// a token per ~4 characters of an ASCII word, one per punctuation mark, and one
+/// per non-ASCII character (CJK and emoji usually tokenize close to that).
+fn estimate_tokens(text: &str) -> usize {
+    let mut tokens = 0;
+    let mut word_len = 0usize;
+    for ch in text.chars() {
+        if ch.is_ascii_alphanumeric() {
+            word_len += 1;
+            continue;
+        }
+        tokens += word_len.div_ceil(4);
+        word_len = 0;
+        if !ch.is_whitespace() {
+            tokens += 1;
+        }
+    }
+    tokens + word_len.div_ceil(4)
+}
+
+/// Per-section token estimates for the inspector, in prompt order.
+fn prompt_token_breakdown(prompt: &str) -> Vec<(String, usize)> {
+    split_prompt_sections(prompt)
+        .into_iter()
+        .map(|section| {
+            let tokens = estimate_tokens(&section.body);
+            (section.title, tokens)
+        })
+        .collect()
+}
+
+/// Amber once `tokens` reaches `share` of the budget, red past the whole budget.
+fn token_budget_color(tokens: usize, budget: usize, share: f32) -> Option<egui::Color32> {
+    if budget == 0 {
+        return None;
+    }
+    if tokens > budget {
+        Some(egui::Color32::from_rgb(235, 110, 110))
+    } else if tokens as f32 >= budget as f32 * share {
+        Some(egui::Color32::from_rgb(230, 170, 80))
+    } else {
+        None
+    }
+}
+
+/// Sections at or above this share of the prompt token budget are flagged.
+const SECTION_BUDGET_SHARE: f32 = 0.25;
+/// The total is flagged at or above this share of the budget.
+const TOTAL_BUDGET_SHARE: f32 = 0.8;
+
+fn render_prompt_token_summary(
+    ui: &mut egui::Ui,
+    prompt: &str,
+    system_prompt: &str,
+    budget: usize,
+    limits: &UiLimits,
+) {
+    let sections = prompt_token_breakdown(prompt);
+    let system_tokens = estimate_tokens(system_prompt);
+    let total = sections.iter().map(|(_, tokens)| tokens).sum::<usize>() + system_tokens;
+    let total_text = if budget > 0 {
+        format!(
+            "≈ {} tokens total ({:.0}% of {} budget)",
+            total,
+            total as f32 * 100.0 / budget as f32,
+            budget
+        )
+    } else {
+        format!("≈ {} tokens total", total)
+    };
+    let mut total_label = egui::RichText::new(total_text).strong();
+    if let Some(color) = token_budget_color(total, budget, TOTAL_BUDGET_SHARE) {
+        total_label = total_label.color(color);
+    }
+    egui::CollapsingHeader::new(total_label)
+        .id_salt("prompt_token_summary")
+        .default_open(false)
+        .show(ui, |ui| {
+            egui::Grid::new("prompt_token_grid")
+                .num_columns(2)
+                .spacing([16.0, 2.0])
+                .show(ui, |ui| {
+                    let rows = std::iter::once(("System prompt".to_string(), system_tokens))
+                        .filter(|(_, tokens)| *tokens > 0)
+                        .chain(sections);
+                    for (title, tokens) in rows {
+                        ui.label(
+                            egui::RichText::new(truncate_str(&title, limits.section_title_chars))
+                                .small(),
+                        );
+                        let mut count = egui::RichText::new(format!("{}", tokens)).small();
+                        if let Some(color) =
+                            token_budget_color(tokens, budget, SECTION_BUDGET_SHARE)
+                        {
+                            count = count.color(color);
+                        }
+                        ui.label(count);
+                        ui.end_row();
+                    }
+                });
+            ui.label(
+                egui::RichText::new(
+                    "Heuristic estimate; real counts depend on the model's tokenizer.",
+                )
+                .small()
+                .weak(),
+            );
+        });
+}
+
+fn format_duration_ms(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{} ms", ms)
+    } else if ms < 60_000 {
+        format!("{:.1} s", ms as f64 / 1000.0)
+    } else {
+        format_elapsed(ms / 1000)
+    }
+}
+
+/// Per-phase timing rows with a share bar each; the slowest phase is highlighted.
+fn render_turn_timing(ui: &mut egui::Ui, timing: &TurnTiming, limits: &UiLimits, plain: bool) {
+    let header = format!(
+        "{}{} total · LLM {} · tools {}",
+        label_for(plain, "⏱ ", ""),
+        format_duration_ms(timing.total_ms),
+        format_duration_ms(timing.total_for(TimingPhaseKind::LlmCall)),
+        format_duration_ms(timing.total_for(TimingPhaseKind::ToolCall)),
+    );
+    let slowest = timing.slowest_phase();
+    let unaccounted = timing.unaccounted_ms();
+    egui::CollapsingHeader::new(egui::RichText::new(header).strong())
+        .id_salt("turn_timing_summary")
+        .default_open(false)
+        .show(ui, |ui| {
+            let share = |ms: u64| {
+                if timing.total_ms == 0 {
+                    0.0
+                } else {
+                    ms as f32 / timing.total_ms as f32
+                }
+            };
+            egui::Grid::new("turn_timing_grid")
+                .num_columns(3)
+                .spacing([12.0, 2.0])
+                .show(ui, |ui| {
+                    let rows = timing
+                        .phases
+                        .iter()
+                        .enumerate()
+                        .map(|(index, phase)| {
+                            (
+                                phase.label.as_str(),
+                                phase.duration_ms,
+                                Some(index) == slowest,
+                            )
+                        })
+                        .chain((unaccounted > 0).then_some(("other", unaccounted, false)));
+                    for (label, ms, is_slowest) in rows {
+                        let mut text =
+                            egui::RichText::new(truncate_str(label, limits.short_label_chars))
+                                .small();
+                        if is_slowest {
+                            text = text.strong().color(egui::Color32::from_rgb(230, 170, 60));
+                        }
+                        ui.label(text);
+                        ui.add(
+                            egui::ProgressBar::new(share(ms))
+                                .desired_width(140.0)
+                                .desired_height(8.0),
+                        );
+                        ui.label(egui::RichText::new(format_duration_ms(ms)).small());
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn render_highlighted_prompt_sections(ui: &mut egui::Ui, prompt: &str, budget: usize) {
     let sections = split_prompt_sections(prompt);
     if sections.is_empty() {
         let mut raw = prompt.to_string();
@@ -1658,6 +4889,14 @@ fn render_highlighted_prompt_sections(ui: &mut egui::Ui, prompt: &str) {
                         .small()
                         .color(section.color),
                 );
+                ui.separator();
+                let tokens = estimate_tokens(&section.body);
+                let mut count = egui::RichText::new(format!("≈ {} tokens", tokens)).small();
+                count = match token_budget_color(tokens, budget, SECTION_BUDGET_SHARE) {
+                    Some(color) => count.color(color),
+                    None => count.weak(),
+                };
+                ui.label(count);
             });
             ui.add_space(3.0);
             let mut body = section.body.clone();
@@ -1768,7 +5007,45 @@ fn classify_prompt_section(title: &str, body: &str) -> (&'static str, egui::Colo
 
 #[cfg(test)]
 mod tests {
-    use super::parse_subtask_id;
+    use super::{
+        brand_name, estimate_tokens, is_stale_operator_request, iteration_budget_near_limit,
+        label_for, live_stream_window, parse_subtask_id, parse_tag_list, prompt_token_breakdown,
+        still_awaiting, take_failed_send, tool_badge_color, truncate_str,
+        upsert_live_tool_progress, visual_state_display, working_memory_visible_in,
+        wrap_text_for_ui_width, LiveToolProgress, PollTracker, StreamingCoalescer, UiLimits,
+    };
+    use crate::api::{AgentVisualState, ApiError, ChatTurnPhase, WorkingMemoryEntry};
+    use crate::ui::chat::FailedChatSend;
+    use std::collections::HashMap;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn operator_cards_drop_only_after_leaving_awaiting_approval() {
+        let mut seen = false;
+        // The event can beat the poll that reports the new state.
+        assert!(still_awaiting(&mut seen, Some(&ChatTurnPhase::Processing)));
+        assert!(still_awaiting(
+            &mut seen,
+            Some(&ChatTurnPhase::AwaitingApproval)
+        ));
+        assert!(still_awaiting(&mut seen, None));
+        assert!(!still_awaiting(&mut seen, Some(&ChatTurnPhase::Processing)));
+
+        let gone = anyhow::Error::new(ApiError::NotFound {
+            body: String::new(),
+        });
+        let conflict = anyhow::Error::new(ApiError::Server {
+            status: 409,
+            body: String::new(),
+        });
+        let down = anyhow::Error::new(ApiError::Server {
+            status: 500,
+            body: String::new(),
+        });
+        assert!(is_stale_operator_request(&gone.context("answer")));
+        assert!(is_stale_operator_request(&conflict));
+        assert!(!is_stale_operator_request(&down));
+    }
 
     #[test]
     fn extracts_subtask_id_from_bracket_prefix() {
@@ -1780,4 +5057,273 @@ mod tests {
     fn ignores_non_prefixed_lines() {
         assert!(parse_subtask_id("shell -> output").is_none());
     }
+
+    #[test]
+    fn parses_tag_list_trimming_and_deduplicating() {
+        assert_eq!(
+            parse_tag_list(" work, Rust ,, work , rust,ideas "),
+            vec!["work", "Rust", "ideas"]
+        );
+        assert!(parse_tag_list(" , ").is_empty());
+    }
+
+    #[test]
+    fn iteration_budget_warns_near_the_limit() {
+        assert!(!iteration_budget_near_limit(3, 10));
+        assert!(!iteration_budget_near_limit(7, 10));
+        assert!(iteration_budget_near_limit(8, 10));
+        assert!(iteration_budget_near_limit(10, 10));
+        assert!(!iteration_budget_near_limit(1, 0));
+    }
+
+    fn progress(tool_call_id: Option<&str>, output: &str) -> LiveToolProgress {
+        LiveToolProgress {
+            conversation_id: "c1".to_string(),
+            tool_name: "shell".to_string(),
+            output_preview: output.to_string(),
+            subtask_id: None,
+            iteration_budget: None,
+            tool_call_id: tool_call_id.map(ToString::to_string),
+            argument_key: None,
+        }
+    }
+
+    #[test]
+    fn streamed_tool_chunks_update_a_single_entry() {
+        let mut entries = Vec::new();
+        upsert_live_tool_progress(&mut entries, progress(Some("call_1"), "line 1"));
+        upsert_live_tool_progress(&mut entries, progress(Some("call_1"), "line 1\nline 2"));
+        upsert_live_tool_progress(&mut entries, progress(Some("call_2"), "other"));
+        upsert_live_tool_progress(&mut entries, progress(None, "legacy"));
+        upsert_live_tool_progress(&mut entries, progress(None, "legacy"));
+
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].output_preview, "line 1\nline 2");
+        assert_eq!(entries[1].output_preview, "other");
+    }
+
+    #[test]
+    fn conversation_scoped_memory_is_hidden_from_other_conversations() {
+        let entry = |scope: Option<&str>| WorkingMemoryEntry {
+            key: "k".to_string(),
+            content: "v".to_string(),
+            conversation_id: scope.map(ToString::to_string),
+            updated_at: None,
+        };
+        assert!(working_memory_visible_in(&entry(None), "c2"));
+        assert!(working_memory_visible_in(&entry(Some("c1")), "c1"));
+        assert!(!working_memory_visible_in(&entry(Some("c1")), "c2"));
+    }
+
+    #[test]
+    fn retrying_a_failed_send_removes_only_that_entry() {
+        let failed = |id: u64, content: &str| FailedChatSend {
+            id,
+            content: content.to_string(),
+            error: "connection refused".to_string(),
+            retryable: true,
+        };
+        let mut failed_sends = HashMap::new();
+        failed_sends.insert(
+            "c1".to_string(),
+            vec![failed(0, "first"), failed(1, "second")],
+        );
+
+        assert!(take_failed_send(&mut failed_sends, "c2", 0).is_none());
+        let taken = take_failed_send(&mut failed_sends, "c1", 1).expect("present");
+        assert_eq!(taken.content, "second");
+        assert!(take_failed_send(&mut failed_sends, "c1", 1).is_none());
+
+        take_failed_send(&mut failed_sends, "c1", 0).expect("present");
+        assert!(!failed_sends.contains_key("c1"));
+    }
+
+    #[test]
+    fn streaming_coalescer_buffers_within_window_and_flushes_on_done() {
+        let mut coalescer = StreamingCoalescer::new(Duration::from_millis(50));
+        let start = Instant::now();
+
+        let first = coalescer.offer("c1", "He", false, start);
+        assert_eq!(first.len(), 1, "first snapshot applies immediately");
+
+        let at = |ms| start + Duration::from_millis(ms);
+        assert!(coalescer.offer("c1", "Hell", false, at(10)).is_empty());
+        assert!(coalescer.offer("c1", "Hello", false, at(20)).is_empty());
+        assert!(coalescer.poll(at(30)).is_none());
+        assert_eq!(
+            coalescer.poll(at(60)).expect("window elapsed").content,
+            "Hello"
+        );
+
+        assert!(coalescer
+            .offer("c1", "Hello, wor", false, at(70))
+            .is_empty());
+        let flushed = coalescer.offer("c1", "Hello, world", true, at(75));
+        assert_eq!(flushed.len(), 1);
+        assert!(flushed[0].done);
+        assert_eq!(flushed[0].content, "Hello, world");
+        assert!(!coalescer.has_pending());
+    }
+
+    #[test]
+    fn streaming_coalescer_flushes_other_conversation_before_switching() {
+        let mut coalescer = StreamingCoalescer::new(Duration::from_millis(50));
+        let start = Instant::now();
+        coalescer.offer("c1", "a", false, start);
+        coalescer.offer("c1", "ab", false, start + Duration::from_millis(5));
+
+        let ready = coalescer.offer("c2", "x", true, start + Duration::from_millis(10));
+        let order: Vec<_> = ready.iter().map(|u| u.conversation_id.as_str()).collect();
+        assert_eq!(order, ["c1", "c2"]);
+    }
+
+    #[test]
+    fn label_for_switches_between_emoji_and_plain_variants() {
+        assert_eq!(label_for(false, "🧠 Mind", "Mind"), "🧠 Mind");
+        assert_eq!(label_for(true, "🧠 Mind", "Mind"), "Mind");
+
+        let mut fallback = HashMap::new();
+        let (emoji, _) = visual_state_display(&AgentVisualState::Thinking, false, &fallback);
+        let (plain, _) = visual_state_display(&AgentVisualState::Thinking, true, &fallback);
+        assert_eq!(emoji, "🤔 Thinking");
+        assert_eq!(plain, "Thinking");
+        assert!(plain.is_ascii());
+
+        // Custom sprite glyphs reach the status strip; plain labels stay plain.
+        fallback.insert(AgentVisualState::Thinking, "(?)".to_string());
+        let (custom, _) = visual_state_display(&AgentVisualState::Thinking, false, &fallback);
+        let (plain, _) = visual_state_display(&AgentVisualState::Thinking, true, &fallback);
+        assert_eq!(custom, "(?) Thinking");
+        assert_eq!(plain, "Thinking");
+    }
+
+    #[test]
+    fn url_tools_get_the_network_badge_not_the_file_badge() {
+        let network = tool_badge_color("web_search");
+        assert_eq!(tool_badge_color("read_url"), network);
+        assert_eq!(tool_badge_color("fetch_url"), network);
+        assert_eq!(
+            tool_badge_color("read_file"),
+            tool_badge_color("write_file")
+        );
+        assert_ne!(tool_badge_color("read_file"), network);
+    }
+
+    #[test]
+    fn truncate_str_respects_configured_limits() {
+        let limits = UiLimits {
+            status_line_chars: 5,
+            ..UiLimits::default()
+        };
+        assert_eq!(
+            truncate_str("write_journal", limits.status_line_chars),
+            "write…"
+        );
+        assert_eq!(truncate_str("ok", limits.status_line_chars), "ok");
+
+        let long = "x".repeat(120);
+        let default_cut = truncate_str(&long, UiLimits::default().status_line_chars);
+        assert_eq!(default_cut.chars().count(), 81);
+    }
+
+    #[test]
+    fn live_stream_window_keeps_the_tail_and_marks_truncation() {
+        assert_eq!(live_stream_window("short", 600), "short");
+        assert_eq!(live_stream_window("abcdef", 6), "abcdef");
+        assert_eq!(live_stream_window("abcdef", 4), "…cdef");
+        // Counts characters, not bytes.
+        assert_eq!(live_stream_window("héllo wörld", 5), "…wörld");
+    }
+
+    #[test]
+    fn preview_builders_keep_multi_byte_and_combining_text_intact() {
+        let family = "👨\u{200d}👩\u{200d}👧";
+        let decomposed = "e\u{301}";
+        let text = format!("{family}{decomposed}日本語");
+
+        // One family emoji and one accented e are one character each.
+        assert_eq!(truncate_str(&text, 1), format!("{family}…"));
+        assert_eq!(truncate_str(&text, 2), format!("{family}{decomposed}…"));
+        assert_eq!(truncate_str(&text, 5), text);
+        assert_eq!(truncate_str(&text, 0), "…");
+
+        assert_eq!(live_stream_window(&text, 4), format!("…{decomposed}日本語"));
+        assert_eq!(live_stream_window(&text, 5), text);
+        assert_eq!(live_stream_window(&text, 0), "…");
+
+        // Hard wraps land between clusters, never inside one.
+        let wrapped = wrap_text_for_ui_width(&family.repeat(30), 120.0);
+        assert_eq!(wrapped.replace('\n', ""), family.repeat(30));
+        for line in wrapped.lines() {
+            assert_eq!(line.len() % family.len(), 0);
+        }
+
+        // Every cut still splits on a char boundary for arbitrary mixed input.
+        let mixed = "aé日👍🏽e\u{301}🇯🇵x".repeat(3);
+        for limit in 0..30 {
+            let cut = truncate_str(&mixed, limit);
+            assert!(mixed.starts_with(cut.trim_end_matches('…')));
+            let tail = live_stream_window(&mixed, limit);
+            assert!(mixed.ends_with(tail.trim_start_matches('…')));
+        }
+    }
+
+    #[test]
+    fn poll_tracker_skips_overlap_and_discards_polls_older_than_a_sync_refresh() {
+        let mut tracker = PollTracker::default();
+
+        let first = tracker.begin().expect("idle tracker starts a poll");
+        assert_eq!(
+            tracker.begin(),
+            None,
+            "no second poll while one is in flight"
+        );
+        assert!(tracker.finish(first), "untouched poll applies");
+
+        let stale = tracker.begin().expect("next poll");
+        tracker.invalidate(); // e.g. the user sent a message and we refreshed inline
+        assert!(
+            !tracker.finish(stale),
+            "poll started before the refresh is dropped"
+        );
+
+        let fresh = tracker.begin().expect("tracker is free again");
+        assert!(tracker.finish(fresh));
+    }
+
+    #[test]
+    fn prompt_tokens_are_counted_per_section() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("hello world"), 4);
+        assert_eq!(estimate_tokens("a, b."), 4);
+        assert_eq!(estimate_tokens("日本語"), 3);
+
+        let prompt = "Working memory\nnotes: tea\n\n---\n\nNew operator message\nhi";
+        let breakdown = prompt_token_breakdown(prompt);
+        assert_eq!(
+            breakdown,
+            [
+                (
+                    "Working memory".to_string(),
+                    estimate_tokens("Working memory\nnotes: tea")
+                ),
+                (
+                    "New operator message".to_string(),
+                    estimate_tokens("New operator message\nhi")
+                ),
+            ]
+        );
+        let total: usize = breakdown.iter().map(|(_, tokens)| tokens).sum();
+        assert!(
+            total <= estimate_tokens(prompt),
+            "separators are not double counted"
+        );
+    }
+
+    #[test]
+    fn brand_name_falls_back_from_override_to_character_to_default() {
+        assert_eq!(brand_name(" Aria ", "Mira"), "Aria");
+        assert_eq!(brand_name("", "Mira"), "Mira");
+        assert_eq!(brand_name("  ", " "), "Ponderer");
+    }
 }
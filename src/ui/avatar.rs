@@ -208,7 +208,9 @@ impl AvatarSet {
         use crate::api::AgentVisualState;
 
         match state {
-            AgentVisualState::Idle | AgentVisualState::Paused => self.idle.as_mut(),
+            AgentVisualState::Idle
+            | AgentVisualState::Paused
+            | AgentVisualState::WaitingForUser => self.idle.as_mut(),
             AgentVisualState::Thinking | AgentVisualState::Reading | AgentVisualState::Confused => {
                 self.thinking.as_mut().or(self.idle.as_mut())
             }
@@ -5,8 +5,11 @@ use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
+use unicode_segmentation::UnicodeSegmentation;
 
-use crate::api::{ChatMessage, FrontendEvent};
+use super::app::label_for;
+use super::json_tree::{parse_structured_json, render_json_tree};
+use crate::api::{ChatMessage, ChatTurnToolCall, FrontendEvent, TurnRating};
 
 const CHAT_TOOL_BLOCK_START: &str = "[tool_calls]";
 const CHAT_TOOL_BLOCK_END: &str = "[/tool_calls]";
@@ -20,6 +23,7 @@ const CHAT_TURN_CONTROL_BLOCK_END: &str = "[/turn_control]";
 #[derive(Debug, Clone, Default, Deserialize)]
 struct ChatToolCallDetail {
     tool_name: String,
+    #[serde(deserialize_with = "deserialize_string_or_json")]
     arguments_preview: String,
     output_kind: String,
     output_preview: String,
@@ -61,10 +65,12 @@ struct ChatTurnControlDetail {
 }
 
 #[derive(Debug, Clone, Default)]
-struct ChatRenderPayload {
-    display_content: String,
+pub(crate) struct ChatRenderPayload {
+    pub(crate) display_content: String,
     tool_details: Vec<ChatToolCallDetail>,
     thinking_details: Vec<String>,
+    /// Reasoning stored with the message, shown apart from inline thinking.
+    reasoning: Option<String>,
     media_details: Vec<ChatMediaDetail>,
     turn_control: Option<ChatTurnControlDetail>,
 }
@@ -201,6 +207,7 @@ pub fn render_event_log(
     ui: &mut egui::Ui,
     events: &[FrontendEvent],
     detail_popup: &mut Option<String>,
+    plain: bool,
 ) {
     ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
         if events.is_empty() {
@@ -225,7 +232,7 @@ pub fn render_event_log(
         if cycle_starts.is_empty() {
             // No cycle markers yet — render flat for backwards compat.
             for (i, event) in events.iter().enumerate() {
-                render_single_event(ui, event, i, detail_popup);
+                render_single_event(ui, event, i, detail_popup, plain);
             }
             return;
         }
@@ -233,7 +240,7 @@ pub fn render_event_log(
         // Render preamble events (before first CycleStart).
         let preamble_end = cycle_starts[0].0;
         for (i, event) in events[..preamble_end].iter().enumerate() {
-            render_single_event(ui, event, i, detail_popup);
+            render_single_event(ui, event, i, detail_popup, plain);
         }
 
         // Render each cycle as a collapsible group; most recent open by default.
@@ -267,7 +274,7 @@ pub fn render_event_log(
             .default_open(is_last)
             .show(ui, |ui| {
                 for (ei, event) in group_events.iter().enumerate() {
-                    render_single_event(ui, event, gi * 10000 + ei, detail_popup);
+                    render_single_event(ui, event, gi * 10000 + ei, detail_popup, plain);
                 }
             });
             ui.add_space(2.0);
@@ -317,6 +324,7 @@ fn render_single_event(
     event: &FrontendEvent,
     event_idx: usize,
     detail_popup: &mut Option<String>,
+    plain: bool,
 ) {
     match event {
         FrontendEvent::Observation(text) => {
@@ -362,6 +370,7 @@ fn render_single_event(
         FrontendEvent::ToolCallProgress {
             tool_name,
             output_preview,
+            arguments,
             ..
         } => {
             ui.horizontal_wrapped(|ui| {
@@ -371,6 +380,13 @@ fn render_single_event(
                         .small()
                         .strong(),
                 );
+                if let Some(key) = arguments.as_ref().and_then(tool_argument_key) {
+                    ui.label(
+                        RichText::new(truncate_for_ui(&key, TOOL_KEY_PREVIEW_CHARS))
+                            .monospace()
+                            .small(),
+                    );
+                }
                 if output_preview.chars().count() > EXPAND_THRESHOLD {
                     let wrapped = wrap_text_for_ui_width(
                         &truncate_for_ui(output_preview, EXPAND_THRESHOLD),
@@ -396,7 +412,7 @@ fn render_single_event(
             });
             ui.add_space(2.0);
         }
-        FrontendEvent::ActionTaken { action, result } => {
+        FrontendEvent::ActionTaken { action, result, .. } => {
             let full = format!("✅ {}: {}", action, result);
             event_line(ui, &full, Color32::GREEN, detail_popup);
             ui.add_space(3.0);
@@ -437,15 +453,177 @@ fn render_single_event(
             event_line(ui, &full, Color32::RED, detail_popup);
             ui.add_space(3.0);
         }
+        FrontendEvent::AgentReset => {
+            ui.label(
+                RichText::new(format!(
+                    "{}Agent reset: conversations and memory cleared",
+                    label_for(plain, "🧹 ", "")
+                ))
+                .color(Color32::LIGHT_BLUE)
+                .small(),
+            );
+            ui.add_space(3.0);
+        }
+        FrontendEvent::ConversationCompacted { summary, .. } => {
+            let full = format!(
+                "{}Summarized older messages: {}",
+                label_for(plain, "🗜 ", ""),
+                summary
+            );
+            event_line(ui, &full, Color32::GRAY, detail_popup);
+            ui.add_space(3.0);
+        }
+        FrontendEvent::IterationLimitHit {
+            max_iterations,
+            mid_progress,
+            ..
+        } => {
+            let limit = max_iterations
+                .map(|max| format!(" ({})", max))
+                .unwrap_or_default();
+            let icon = label_for(plain, "⏹ ", "");
+            let full = if *mid_progress {
+                format!(
+                    "{}Hit the tool-iteration limit{} while still working",
+                    icon, limit
+                )
+            } else {
+                format!("{}Hit the tool-iteration limit{}", icon, limit)
+            };
+            event_line(ui, &full, Color32::from_rgb(230, 170, 80), detail_popup);
+            ui.add_space(3.0);
+        }
         FrontendEvent::StateChanged(_)
         | FrontendEvent::ChatStreaming { .. }
         | FrontendEvent::GenerationStarted { .. }
         | FrontendEvent::GenerationMetrics { .. }
         | FrontendEvent::GenerationFinished { .. }
         | FrontendEvent::ApprovalRequest { .. }
+        | FrontendEvent::ToolInputRequested { .. }
+        | FrontendEvent::ConfigProposed(_)
+        | FrontendEvent::ToolBatchPending(_)
         | FrontendEvent::CycleStart { .. } => {
             // Handled by caller (state in header, streaming in chat pane,
-            // approvals as popup, cycle starts used for grouping only).
+            // approvals, config proposals, tool batches, and tool questions
+            // as prompts, cycle starts used for
+            // grouping only).
+        }
+    }
+}
+
+/// Per-message action requested from the chat pane; handled by `app.rs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChatPaneAction {
+    ViewPrompt {
+        turn_id: String,
+    },
+    ContinueMessage {
+        message_id: String,
+    },
+    BranchFrom {
+        message_id: String,
+    },
+    Remember {
+        message_id: String,
+    },
+    RateTurn {
+        turn_id: String,
+        rating: Option<TurnRating>,
+    },
+    LoadTurnTools {
+        turn_id: String,
+    },
+    RetryFailedSend {
+        failed_id: u64,
+    },
+    DiscardFailedSend {
+        failed_id: u64,
+    },
+}
+
+/// A user message the backend never accepted. Lives only in the UI, rendered
+/// inline after the real history until it is retried or discarded.
+#[derive(Debug, Clone)]
+pub struct FailedChatSend {
+    pub id: u64,
+    pub content: String,
+    pub error: String,
+    pub retryable: bool,
+}
+
+/// Where the backend compacted a conversation. Rendered as a thin separator
+/// after `after_message_id` (or above the history when it is unknown).
+#[derive(Debug, Clone)]
+pub struct CompactionMarker {
+    pub after_message_id: Option<String>,
+    pub summary: String,
+}
+
+/// Per-conversation scroll bookkeeping for the "↓ latest" button and unread badge.
+#[derive(Debug, Clone, Default)]
+pub struct ChatScrollState {
+    at_bottom: bool,
+    seen_message_count: Option<usize>,
+    unread: usize,
+    jump_to_latest: bool,
+}
+
+impl ChatScrollState {
+    /// Record the pane's position after a frame. Messages that arrive while the
+    /// operator is scrolled up count as unread until they return to the bottom.
+    fn observe(&mut self, message_count: usize, at_bottom: bool) {
+        self.at_bottom = at_bottom;
+        let seen = *self.seen_message_count.get_or_insert(message_count);
+        if at_bottom || message_count < seen {
+            self.seen_message_count = Some(message_count);
+            self.unread = 0;
+        } else {
+            self.unread = message_count - seen;
+        }
+    }
+}
+
+/// Speaker labels shown on chat bubbles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatSpeakerNames {
+    pub agent: String,
+    pub operator: String,
+}
+
+impl Default for ChatSpeakerNames {
+    fn default() -> Self {
+        Self {
+            agent: "Agent".to_string(),
+            operator: "You".to_string(),
+        }
+    }
+}
+
+impl ChatSpeakerNames {
+    /// Agent: character card name, then the configured username. Operator: the
+    /// desktop display name. Each falls back to its role label when blank, and
+    /// both do when `use_names` is off.
+    pub fn resolve(
+        use_names: bool,
+        character_name: &str,
+        agent_username: &str,
+        operator_name: &str,
+    ) -> Self {
+        let defaults = Self::default();
+        if !use_names {
+            return defaults;
+        }
+        let first_named = |candidates: &[&str], fallback: String| {
+            candidates
+                .iter()
+                .map(|name| name.trim())
+                .find(|name| !name.is_empty())
+                .map(str::to_string)
+                .unwrap_or(fallback)
+        };
+        Self {
+            agent: first_named(&[character_name, agent_username], defaults.agent),
+            operator: first_named(&[operator_name], defaults.operator),
         }
     }
 }
@@ -456,8 +634,16 @@ pub fn render_private_chat(
     messages: &[ChatMessage],
     streaming_preview: Option<&str>,
     media_cache: &mut ChatMediaCache,
-) -> Option<String> {
-    let mut requested_prompt_turn_id: Option<String> = None;
+    turn_tool_calls: &HashMap<String, Vec<ChatTurnToolCall>>,
+    failed_sends: &[FailedChatSend],
+    compactions: &[CompactionMarker],
+    names: &ChatSpeakerNames,
+    long_reply_chars: usize,
+    awaiting_approval: bool,
+    scroll_state: &mut ChatScrollState,
+    plain: bool,
+) -> Option<ChatPaneAction> {
+    let mut requested_action: Option<ChatPaneAction> = None;
     ui.with_layout(egui::Layout::top_down(egui::Align::Min), |ui| {
         ui.heading("Private Chat");
         ui.add_space(4.0);
@@ -472,92 +658,260 @@ pub fn render_private_chat(
         // for live-tool output + composer, and forcing a min height causes
         // overlap/pileups when agent tool activity expands.
         let chat_scroll_height = ui.available_height().max(0.0);
-        ScrollArea::vertical()
+        let mut scroll_area = ScrollArea::vertical()
             .stick_to_bottom(true)
-            .max_height(chat_scroll_height)
-            .show(ui, |ui| {
-                let has_live_preview = streaming_preview.map_or(false, |p| !p.trim().is_empty());
+            .max_height(chat_scroll_height);
+        if std::mem::take(&mut scroll_state.jump_to_latest) {
+            scroll_area = scroll_area.vertical_scroll_offset(f32::MAX);
+        }
+        let scroll_output = scroll_area.show(ui, |ui| {
+            let has_live_preview = streaming_preview.map_or(false, |p| !p.trim().is_empty());
 
-                if messages.is_empty() && !has_live_preview {
-                    ui.centered_and_justified(|ui| {
-                        ui.label(
-                            RichText::new(
-                                "No messages yet. Type below to start chatting with your agent.",
-                            )
-                            .weak()
-                            .italics(),
+            if messages.is_empty() && !has_live_preview && failed_sends.is_empty() {
+                ui.centered_and_justified(|ui| {
+                    ui.label(
+                        RichText::new(
+                            "No messages yet. Type below to start chatting with your agent.",
+                        )
+                        .weak()
+                        .italics(),
+                    );
+                });
+                return;
+            }
+
+            let placed = |marker: &&CompactionMarker| {
+                marker
+                    .after_message_id
+                    .as_deref()
+                    .is_some_and(|id| messages.iter().any(|msg| msg.id == id))
+            };
+            for marker in compactions.iter().filter(|marker| !placed(marker)) {
+                render_compaction_separator(ui, marker);
+            }
+
+            for msg in messages {
+                let is_operator = msg.role == "operator";
+                let time_str = msg.created_at.format("%H:%M").to_string();
+                let payload = message_render_payload(msg);
+                let row_width = ui.available_width();
+                let bubble_cap = (row_width - 8.0).max(120.0);
+                let max_bubble_width = (row_width * 0.7).max(120.0).min(bubble_cap);
+                let bubble_width = max_bubble_width.min(row_width);
+                ui.horizontal_top(|ui| {
+                    if is_operator {
+                        let spacer = (row_width - bubble_width).max(0.0);
+                        if spacer > 0.0 {
+                            ui.add_space(spacer);
+                        }
+                    }
+
+                    ui.vertical(|ui| {
+                        if let Some(action) = render_chat_message_bubble(
+                            ui,
+                            msg,
+                            &time_str,
+                            &payload,
+                            is_operator,
+                            names,
+                            long_reply_chars,
+                            bubble_width,
+                            media_cache,
+                            plain,
+                        ) {
+                            requested_action = Some(action);
+                        }
+                    });
+                });
+
+                if let Some(turn_control) = payload.turn_control.as_ref() {
+                    render_turn_control_separator(ui, turn_control, plain);
+                    ui.add_space(6.0);
+                }
+
+                if payload.reasoning.is_some()
+                    || !payload.thinking_details.is_empty()
+                    || !payload.tool_details.is_empty()
+                {
+                    ui.add_space(4.0);
+                    ui.horizontal_top(|ui| {
+                        render_message_detail_panels(ui, &msg.id, &payload);
+                    });
+                }
+
+                if let Some(turn_id) = msg.turn_id.as_deref().filter(|_| !is_operator) {
+                    ui.add_space(4.0);
+                    ui.horizontal_top(|ui| {
+                        let needs_load = render_turn_tool_history_panel(
+                            ui,
+                            &msg.id,
+                            turn_tool_calls.get(turn_id).map(Vec::as_slice),
                         );
+                        if needs_load {
+                            requested_action = Some(ChatPaneAction::LoadTurnTools {
+                                turn_id: turn_id.to_string(),
+                            });
+                        }
                     });
-                    return;
                 }
 
-                for msg in messages {
-                    let is_operator = msg.role == "operator";
-                    let time_str = msg.created_at.format("%H:%M").to_string();
-                    let payload = parse_chat_payload(&msg.content);
+                ui.add_space(8.0);
+
+                for marker in compactions
+                    .iter()
+                    .filter(|marker| marker.after_message_id.as_deref() == Some(msg.id.as_str()))
+                {
+                    render_compaction_separator(ui, marker);
+                }
+            }
+
+            for failed in failed_sends {
+                let row_width = ui.available_width();
+                let bubble_width = (row_width * 0.7).max(120.0).min(row_width);
+                ui.horizontal_top(|ui| {
+                    ui.add_space((row_width - bubble_width).max(0.0));
+                    ui.vertical(|ui| {
+                        if let Some(action) =
+                            render_failed_send_bubble(ui, failed, names, bubble_width, plain)
+                        {
+                            requested_action = Some(action);
+                        }
+                    });
+                });
+                ui.add_space(8.0);
+            }
+
+            // Render streaming preview AFTER messages so it appears at the bottom.
+            if let Some(preview) = streaming_preview {
+                let trimmed = preview.trim();
+                if !trimmed.is_empty() {
                     let row_width = ui.available_width();
                     let bubble_cap = (row_width - 8.0).max(120.0);
                     let max_bubble_width = (row_width * 0.7).max(120.0).min(bubble_cap);
-                    let bubble_width = max_bubble_width.min(row_width);
                     ui.horizontal_top(|ui| {
-                        if is_operator {
-                            let spacer = (row_width - bubble_width).max(0.0);
-                            if spacer > 0.0 {
-                                ui.add_space(spacer);
-                            }
-                        }
-
                         ui.vertical(|ui| {
-                            let prompt_clicked = render_chat_message_bubble(
+                            render_streaming_preview_bubble(
                                 ui,
-                                msg,
-                                &time_str,
-                                &payload,
-                                is_operator,
-                                bubble_width,
-                                media_cache,
+                                trimmed,
+                                &names.agent,
+                                max_bubble_width,
                             );
-                            if prompt_clicked {
-                                if let Some(turn_id) = msg.turn_id.as_deref() {
-                                    requested_prompt_turn_id = Some(turn_id.to_string());
-                                }
-                            }
                         });
                     });
+                    ui.add_space(8.0);
+                }
+            }
 
-                    if let Some(turn_control) = payload.turn_control.as_ref() {
-                        render_turn_control_separator(ui, turn_control);
-                        ui.add_space(6.0);
-                    }
+            if awaiting_approval {
+                render_awaiting_approval_notice(ui, &names.agent);
+            }
+        });
 
-                    if !payload.thinking_details.is_empty() || !payload.tool_details.is_empty() {
-                        ui.add_space(4.0);
-                        ui.horizontal_top(|ui| {
-                            render_message_detail_panels(ui, &msg.id, &payload);
-                        });
+        let max_offset =
+            (scroll_output.content_size.y - scroll_output.inner_rect.height()).max(0.0);
+        let at_bottom = scroll_output.state.offset.y >= max_offset - 24.0;
+        scroll_state.observe(messages.len(), at_bottom);
+
+        if !scroll_state.at_bottom {
+            let label = if scroll_state.unread > 0 {
+                format!("↓ latest ({} new)", scroll_state.unread)
+            } else {
+                "↓ latest".to_string()
+            };
+            let anchor = scroll_output.inner_rect.right_bottom() - egui::vec2(110.0, 36.0);
+            egui::Area::new(ui.id().with("chat_jump_to_latest"))
+                .order(egui::Order::Foreground)
+                .fixed_pos(anchor)
+                .show(ui.ctx(), |ui| {
+                    if ui.button(label).clicked() {
+                        scroll_state.jump_to_latest = true;
                     }
+                });
+        }
+    });
+    requested_action
+}
 
-                    ui.add_space(8.0);
-                }
+/// Inline marker where a turn paused for the operator's approval.
+fn render_awaiting_approval_notice(ui: &mut egui::Ui, agent_name: &str) {
+    let amber = Color32::from_rgb(230, 170, 80);
+    egui::Frame::none()
+        .stroke(egui::Stroke::new(1.0, amber))
+        .rounding(6.0)
+        .inner_margin(egui::Margin::symmetric(10.0, 6.0))
+        .show(ui, |ui| {
+            ui.label(
+                RichText::new(format!("{} is waiting for you", agent_name))
+                    .strong()
+                    .color(amber),
+            );
+            ui.label(
+                RichText::new(
+                    "This turn is paused until you answer or approve the pending request.",
+                )
+                .small()
+                .weak(),
+            );
+        });
+    ui.add_space(8.0);
+}
 
-                // Render streaming preview AFTER messages so it appears at the bottom.
-                if let Some(preview) = streaming_preview {
-                    let trimmed = preview.trim();
-                    if !trimmed.is_empty() {
-                        let row_width = ui.available_width();
-                        let bubble_cap = (row_width - 8.0).max(120.0);
-                        let max_bubble_width = (row_width * 0.7).max(120.0).min(bubble_cap);
-                        ui.horizontal_top(|ui| {
-                            ui.vertical(|ui| {
-                                render_streaming_preview_bubble(ui, trimmed, max_bubble_width);
-                            });
-                        });
-                        ui.add_space(8.0);
-                    }
+fn render_failed_send_bubble(
+    ui: &mut egui::Ui,
+    failed: &FailedChatSend,
+    names: &ChatSpeakerNames,
+    max_bubble_width: f32,
+    plain: bool,
+) -> Option<ChatPaneAction> {
+    let mut action = None;
+    egui::Frame::group(ui.style())
+        .fill(Color32::from_rgb(60, 28, 28))
+        .stroke(egui::Stroke::new(1.0, Color32::from_rgb(170, 70, 70)))
+        .show(ui, |ui| {
+            let inner_width = (max_bubble_width - 14.0).max(100.0);
+            ui.set_width(inner_width);
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(&names.operator)
+                        .color(Color32::from_rgb(100, 149, 237))
+                        .strong(),
+                );
+                ui.label(
+                    RichText::new(label_for(plain, "⚠ not sent", "not sent"))
+                        .color(Color32::from_rgb(255, 140, 140))
+                        .small(),
+                );
+            });
+            let wrapped = wrap_text_for_ui_width(&failed.content, inner_width);
+            ui.add(egui::Label::new(RichText::new(wrapped)).wrap());
+            ui.add_space(2.0);
+            ui.label(
+                RichText::new(wrap_text_for_ui_width(&failed.error, inner_width))
+                    .small()
+                    .color(Color32::from_rgb(255, 160, 160)),
+            );
+            ui.horizontal(|ui| {
+                let retry = ui.add_enabled(failed.retryable, egui::Button::new("↻ Retry").small());
+                let retry = if failed.retryable {
+                    retry
+                } else {
+                    retry.on_disabled_hover_text(
+                        "This error will repeat on resend; fix the cause (e.g. token) first",
+                    )
+                };
+                if retry.clicked() {
+                    action = Some(ChatPaneAction::RetryFailedSend {
+                        failed_id: failed.id,
+                    });
+                }
+                if ui.small_button("Discard").clicked() {
+                    action = Some(ChatPaneAction::DiscardFailedSend {
+                        failed_id: failed.id,
+                    });
                 }
             });
-    });
-    requested_prompt_turn_id
+        });
+    action
 }
 
 fn render_chat_message_bubble(
@@ -566,10 +920,13 @@ fn render_chat_message_bubble(
     time_str: &str,
     payload: &ChatRenderPayload,
     is_operator: bool,
+    names: &ChatSpeakerNames,
+    long_reply_chars: usize,
     max_bubble_width: f32,
     media_cache: &mut ChatMediaCache,
-) -> bool {
-    let mut prompt_clicked = false;
+    plain: bool,
+) -> Option<ChatPaneAction> {
+    let mut action = None;
     ui.group(|ui| {
         let inner_width = (max_bubble_width - 14.0).max(100.0);
         ui.set_min_width(inner_width);
@@ -579,13 +936,13 @@ fn render_chat_message_bubble(
 
         let (role_label, role_color, bg_color) = if is_operator {
             (
-                "You",
+                names.operator.as_str(),
                 Color32::from_rgb(100, 149, 237),
                 Color32::from_rgb(30, 40, 60),
             )
         } else {
             (
-                "Agent",
+                names.agent.as_str(),
                 Color32::from_rgb(144, 238, 144),
                 Color32::from_rgb(30, 50, 40),
             )
@@ -599,9 +956,47 @@ fn render_chat_message_bubble(
             if !is_operator && msg.turn_id.is_some() {
                 ui.add_space(6.0);
                 if ui.small_button("View Prompt").clicked() {
-                    prompt_clicked = true;
+                    action = msg
+                        .turn_id
+                        .clone()
+                        .map(|turn_id| ChatPaneAction::ViewPrompt { turn_id });
+                }
+                for (rating, label, hover) in [
+                    (TurnRating::Up, label_for(plain, "👍", "Good"), "Good reply"),
+                    (TurnRating::Down, label_for(plain, "👎", "Bad"), "Bad reply"),
+                ] {
+                    let selected = msg.rating == Some(rating);
+                    if ui
+                        .selectable_label(selected, RichText::new(label).small())
+                        .on_hover_text(hover)
+                        .clicked()
+                    {
+                        // Clicking the current rating again clears it.
+                        action = msg.turn_id.clone().map(|turn_id| ChatPaneAction::RateTurn {
+                            turn_id,
+                            rating: (!selected).then_some(rating),
+                        });
+                    }
                 }
             }
+            if ui
+                .small_button("Branch")
+                .on_hover_text("Start a new conversation with the history up to this message")
+                .clicked()
+            {
+                action = Some(ChatPaneAction::BranchFrom {
+                    message_id: msg.id.clone(),
+                });
+            }
+            if ui
+                .small_button("Remember")
+                .on_hover_text("Pin this message (or a note about it) to working memory")
+                .clicked()
+            {
+                action = Some(ChatPaneAction::Remember {
+                    message_id: msg.id.clone(),
+                });
+            }
         });
 
         ui.add(
@@ -631,8 +1026,50 @@ fn render_chat_message_bubble(
                     .italics(),
             );
         }
+
+        if let Some(chars) = (!is_operator)
+            .then(|| long_reply_length(&payload.display_content, long_reply_chars))
+            .flatten()
+        {
+            ui.label(
+                RichText::new(format!("Long reply · {} chars", chars))
+                    .small()
+                    .color(Color32::from_rgb(230, 170, 80)),
+            )
+            .on_hover_text("Longer than the limit set under Settings → Display → Replies");
+        }
+
+        if !is_operator && msg.truncated {
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(label_for(
+                        plain,
+                        "✂ Cut off at the token limit",
+                        "Cut off at the token limit",
+                    ))
+                    .weak()
+                    .small()
+                    .italics(),
+                );
+                if ui.small_button("Continue").clicked() {
+                    action = Some(ChatPaneAction::ContinueMessage {
+                        message_id: msg.id.clone(),
+                    });
+                }
+            });
+        }
     });
-    prompt_clicked
+    action
+}
+
+/// Character count of a reply over `limit`, or `None` when it fits or the marker is off.
+fn long_reply_length(display_content: &str, limit: usize) -> Option<usize> {
+    if limit == 0 {
+        return None;
+    }
+    let chars = display_content.trim().chars().count();
+    (chars > limit).then_some(chars)
 }
 
 fn render_message_detail_panels(ui: &mut egui::Ui, message_id: &str, payload: &ChatRenderPayload) {
@@ -645,6 +1082,13 @@ fn render_message_detail_panels(ui: &mut egui::Ui, message_id: &str, payload: &C
     let has_thinking = !payload.thinking_details.is_empty();
     let has_tool_calls = !payload.tool_details.is_empty();
 
+    if let Some(reasoning) = payload.reasoning.as_deref() {
+        render_reasoning_panel(ui, message_id, reasoning, wrap_token_len);
+        if has_thinking || has_tool_calls {
+            ui.add_space(4.0);
+        }
+    }
+
     if has_thinking {
         render_thinking_panel(ui, message_id, &payload.thinking_details, wrap_token_len);
         if has_tool_calls {
@@ -748,6 +1192,28 @@ fn normalize_media_kind(kind: &str) -> &'static str {
     }
 }
 
+fn render_reasoning_panel(
+    ui: &mut egui::Ui,
+    message_id: &str,
+    reasoning: &str,
+    wrap_token_len: usize,
+) {
+    egui::CollapsingHeader::new(
+        RichText::new(format!("Reasoning · {} chars", reasoning.chars().count()))
+            .color(Color32::from_rgb(170, 150, 210)),
+    )
+    .id_salt((message_id, "reasoning"))
+    .default_open(false)
+    .show(ui, |ui| {
+        ui.label(
+            RichText::new(force_wrap_long_tokens(reasoning, wrap_token_len))
+                .small()
+                .italics()
+                .color(Color32::from_rgb(190, 190, 200)),
+        );
+    });
+}
+
 fn render_thinking_panel(
     ui: &mut egui::Ui,
     message_id: &str,
@@ -777,7 +1243,7 @@ fn render_tool_calls_panel(
         .id_salt((message_id, "tool_calls"))
         .default_open(false)
         .show(ui, |ui| {
-            for detail in tool_details {
+            for (idx, detail) in tool_details.iter().enumerate() {
                 ui.group(|ui| {
                     ui.label(
                         RichText::new(format!("{} [{}]", detail.tool_name, detail.output_kind))
@@ -796,10 +1262,17 @@ fn render_tool_calls_panel(
                     }
                     if !detail.output_preview.trim().is_empty() {
                         ui.label(RichText::new("Output").small().color(Color32::LIGHT_GREEN));
-                        ui.monospace(force_wrap_long_tokens(
-                            detail.output_preview.trim(),
-                            wrap_token_len,
-                        ));
+                        match parse_structured_json(&detail.output_preview) {
+                            Some(value) => {
+                                render_json_tree(ui, (message_id, "tool_output", idx), &value)
+                            }
+                            None => {
+                                ui.monospace(force_wrap_long_tokens(
+                                    detail.output_preview.trim(),
+                                    wrap_token_len,
+                                ));
+                            }
+                        }
                     }
                 });
                 ui.add_space(4.0);
@@ -807,7 +1280,111 @@ fn render_tool_calls_panel(
         });
 }
 
-fn render_streaming_preview_bubble(ui: &mut egui::Ui, preview: &str, max_bubble_width: f32) {
+/// Durable per-turn tool record, fetched lazily the first time the header is opened.
+/// Returns `true` when the panel is open but the turn's records are not loaded yet.
+fn render_turn_tool_history_panel(
+    ui: &mut egui::Ui,
+    message_id: &str,
+    records: Option<&[ChatTurnToolCall]>,
+) -> bool {
+    let details_width = (ui.available_width() - 8.0).max(120.0);
+    ui.set_min_width(details_width);
+    ui.set_width(details_width);
+    ui.set_max_width(details_width);
+    let wrap_token_len = max_token_len_for_width(details_width);
+
+    let title = match records {
+        Some(records) => format!("Tools used ({})", records.len()),
+        None => "Tools used".to_string(),
+    };
+    let response = egui::CollapsingHeader::new(title)
+        .id_salt((message_id, "turn_tools"))
+        .default_open(false)
+        .show(ui, |ui| {
+            let Some(records) = records else {
+                ui.label(
+                    RichText::new("Loading tool history…")
+                        .weak()
+                        .small()
+                        .italics(),
+                );
+                return;
+            };
+            if records.is_empty() {
+                ui.label(
+                    RichText::new("No tools were called in this turn.")
+                        .weak()
+                        .small()
+                        .italics(),
+                );
+                return;
+            }
+            for (idx, record) in records.iter().enumerate() {
+                let header = match tool_argument_key(&record.arguments) {
+                    Some(key) => format!(
+                        "{} · {}",
+                        record.tool_name,
+                        truncate_for_ui(&key, TOOL_KEY_PREVIEW_CHARS)
+                    ),
+                    None => record.tool_name.clone(),
+                };
+                egui::CollapsingHeader::new(RichText::new(header).strong())
+                    .id_salt((message_id, "turn_tool", idx))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        if !record.arguments.is_null() {
+                            ui.label(
+                                RichText::new("Arguments")
+                                    .small()
+                                    .color(Color32::LIGHT_BLUE),
+                            );
+                            ui.monospace(force_wrap_long_tokens(
+                                &json_value_display_text(&record.arguments),
+                                wrap_token_len,
+                            ));
+                        }
+                        if !record.output.is_null() {
+                            ui.label(RichText::new("Output").small().color(Color32::LIGHT_GREEN));
+                            let structured = match &record.output {
+                                serde_json::Value::String(text) => parse_structured_json(text),
+                                serde_json::Value::Object(_) | serde_json::Value::Array(_) => {
+                                    Some(record.output.clone())
+                                }
+                                _ => None,
+                            };
+                            match structured {
+                                Some(value) => render_json_tree(
+                                    ui,
+                                    (message_id, "turn_tool_output", idx),
+                                    &value,
+                                ),
+                                None => {
+                                    ui.monospace(force_wrap_long_tokens(
+                                        &json_value_display_text(&record.output),
+                                        wrap_token_len,
+                                    ));
+                                }
+                            }
+                        }
+                    });
+            }
+        });
+    response.body_returned.is_some() && records.is_none()
+}
+
+fn json_value_display_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.trim().to_string(),
+        other => serde_json::to_string_pretty(other).unwrap_or_else(|_| other.to_string()),
+    }
+}
+
+fn render_streaming_preview_bubble(
+    ui: &mut egui::Ui,
+    preview: &str,
+    agent_name: &str,
+    max_bubble_width: f32,
+) {
     let (display_text, thoughts) = strip_inline_thinking_tags(preview);
 
     ui.group(|ui| {
@@ -820,7 +1397,7 @@ fn render_streaming_preview_bubble(ui: &mut egui::Ui, preview: &str, max_bubble_
 
         ui.horizontal(|ui| {
             ui.label(
-                RichText::new("Agent")
+                RichText::new(agent_name)
                     .color(Color32::from_rgb(144, 238, 144))
                     .strong(),
             );
@@ -849,8 +1426,33 @@ fn render_streaming_preview_bubble(ui: &mut egui::Ui, preview: &str, max_bubble_
     });
 }
 
-fn render_turn_control_separator(ui: &mut egui::Ui, detail: &ChatTurnControlDetail) {
+fn render_compaction_separator(ui: &mut egui::Ui, marker: &CompactionMarker) {
+    ui.vertical_centered(|ui| {
+        let label = ui.label(
+            RichText::new("— summarized older messages —")
+                .small()
+                .weak()
+                .italics(),
+        );
+        if !marker.summary.trim().is_empty() {
+            label.on_hover_text(truncate_for_ui(marker.summary.trim(), 600));
+        }
+    });
+    ui.add_space(6.0);
+}
+
+fn render_turn_control_separator(ui: &mut egui::Ui, detail: &ChatTurnControlDetail, plain: bool) {
     ui.add_space(2.0);
+    if detail.status == "hit_limit" {
+        ui.label(
+            RichText::new(format!(
+                "{}Stopped at the tool-iteration limit. Raise it in Settings → Behavior if the agent was still making progress.",
+                label_for(plain, "⚠ ", "")
+            ))
+            .small()
+            .color(Color32::from_rgb(230, 170, 80)),
+        );
+    }
     ui.horizontal_wrapped(|ui| {
         ui.label(
             RichText::new(format!(
@@ -887,10 +1489,10 @@ fn force_wrap_long_tokens(input: &str, max_token_len: usize) -> String {
     let mut out = String::with_capacity(input.len());
     let mut run_len = 0usize;
 
-    for ch in input.chars() {
-        if ch.is_whitespace() {
+    for grapheme in input.graphemes(true) {
+        if grapheme.chars().all(char::is_whitespace) {
             run_len = 0;
-            out.push(ch);
+            out.push_str(grapheme);
             continue;
         }
 
@@ -899,14 +1501,14 @@ fn force_wrap_long_tokens(input: &str, max_token_len: usize) -> String {
             run_len = 0;
         }
 
-        out.push(ch);
+        out.push_str(grapheme);
         run_len += 1;
     }
 
     out
 }
 
-fn parse_chat_payload(content: &str) -> ChatRenderPayload {
+pub(crate) fn parse_chat_payload(content: &str) -> ChatRenderPayload {
     let (without_tools, raw_tools) =
         extract_block(content, CHAT_TOOL_BLOCK_START, CHAT_TOOL_BLOCK_END);
     let tool_details = raw_tools
@@ -945,11 +1547,26 @@ fn parse_chat_payload(content: &str) -> ChatRenderPayload {
         display_content: display_content.trim().to_string(),
         tool_details,
         thinking_details,
+        reasoning: None,
         media_details,
         turn_control,
     }
 }
 
+/// Accepts either a plain string or any JSON value, stringifying the latter.
+/// Some OpenAI-compatible servers hand back tool arguments as an object
+/// rather than stringified JSON, and the backend forwards them verbatim.
+fn deserialize_string_or_json<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(text) => text,
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    })
+}
+
 fn extract_block(content: &str, start_marker: &str, end_marker: &str) -> (String, Option<String>) {
     let Some(start_idx) = content.find(start_marker) else {
         return (content.to_string(), None);
@@ -1061,16 +1678,67 @@ fn strip_optional_json_code_fence(raw: &str) -> &str {
     inner
 }
 
-fn truncate_for_ui(text: &str, max_chars: usize) -> String {
-    let mut out = String::new();
-    for (i, ch) in text.chars().enumerate() {
-        if i >= max_chars {
-            out.push('…');
-            break;
+/// Argument names that best identify what a tool call does, most telling first.
+const TOOL_KEY_ARGUMENTS: &[&str] = &[
+    "command",
+    "cmd",
+    "url",
+    "path",
+    "file_path",
+    "query",
+    "pattern",
+    "name",
+    "prompt",
+];
+/// Longest headline argument shown next to a tool name in the log and turn history.
+const TOOL_KEY_PREVIEW_CHARS: usize = 80;
+
+/// Picks the argument that says the most about a tool call (the shell command,
+/// the URL, the file path) so it can be shown next to the tool name. Accepts
+/// arguments as an object or as a JSON-encoded string; string-array values
+/// (`["ls", "-la"]`) are joined with spaces. Only the first line is kept.
+pub fn tool_argument_key(arguments: &serde_json::Value) -> Option<String> {
+    let parsed;
+    let arguments = match arguments {
+        serde_json::Value::String(text) => {
+            parsed = serde_json::from_str::<serde_json::Value>(text).ok()?;
+            &parsed
         }
-        out.push(ch);
+        other => other,
+    };
+    let object = arguments.as_object()?;
+    TOOL_KEY_ARGUMENTS.iter().find_map(|key| {
+        let text = match object.get(*key)? {
+            serde_json::Value::String(text) => text.clone(),
+            serde_json::Value::Array(items) => items
+                .iter()
+                .map(|item| item.as_str())
+                .collect::<Option<Vec<_>>>()?
+                .join(" "),
+            _ => return None,
+        };
+        let first_line = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+        Some(first_line.to_string())
+    })
+}
+
+fn truncate_for_ui(text: &str, max_chars: usize) -> String {
+    match text.grapheme_indices(true).nth(max_chars) {
+        Some((cut, _)) => format!("{}…", &text[..cut]),
+        None => text.to_string(),
     }
-    out
+}
+
+/// Parses `msg.content` and attaches the reasoning the backend stored separately.
+fn message_render_payload(msg: &ChatMessage) -> ChatRenderPayload {
+    let mut payload = parse_chat_payload(&msg.content);
+    payload.reasoning = msg
+        .reasoning
+        .as_deref()
+        .map(str::trim)
+        .filter(|reasoning| !reasoning.is_empty())
+        .map(str::to_string);
+    payload
 }
 
 fn strip_inline_thinking_tags(content: &str) -> (String, Vec<String>) {
@@ -1110,6 +1778,26 @@ fn strip_inline_thinking_tags(content: &str) -> (String, Vec<String>) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn speaker_names_prefer_card_then_username_then_role_labels() {
+        let names = ChatSpeakerNames::resolve(true, "Mira", "ponder_bot", "Sam");
+        assert_eq!(names.agent, "Mira");
+        assert_eq!(names.operator, "Sam");
+
+        let names = ChatSpeakerNames::resolve(true, "  ", "ponder_bot", "");
+        assert_eq!(names.agent, "ponder_bot");
+        assert_eq!(names.operator, "You");
+
+        assert_eq!(
+            ChatSpeakerNames::resolve(true, "", "", " "),
+            ChatSpeakerNames::default()
+        );
+        assert_eq!(
+            ChatSpeakerNames::resolve(false, "Mira", "ponder_bot", "Sam"),
+            ChatSpeakerNames::default()
+        );
+    }
+
     #[test]
     fn extracts_embedded_tool_details() {
         let content = "Done.\n\n[tool_calls]\n[{\"tool_name\":\"shell\",\"arguments_preview\":\"{}\",\"output_kind\":\"text\",\"output_preview\":\"ok\"}]\n[/tool_calls]";
@@ -1119,6 +1807,51 @@ mod tests {
         assert_eq!(payload.tool_details[0].tool_name, "shell");
     }
 
+    #[test]
+    fn accepts_string_tool_arguments_preview() {
+        let content = "[tool_calls]\n[{\"tool_name\":\"shell\",\"arguments_preview\":\"{\\\"cmd\\\":\\\"ls\\\"}\",\"output_kind\":\"text\",\"output_preview\":\"ok\"}]\n[/tool_calls]";
+        let payload = parse_chat_payload(content);
+        assert_eq!(payload.tool_details.len(), 1);
+        assert_eq!(
+            payload.tool_details[0].arguments_preview,
+            "{\"cmd\":\"ls\"}"
+        );
+    }
+
+    #[test]
+    fn accepts_object_tool_arguments_preview() {
+        let content = "[tool_calls]\n[{\"tool_name\":\"shell\",\"arguments_preview\":{\"cmd\":\"ls\"},\"output_kind\":\"text\",\"output_preview\":\"ok\"}]\n[/tool_calls]";
+        let payload = parse_chat_payload(content);
+        assert_eq!(payload.tool_details.len(), 1);
+        assert_eq!(
+            payload.tool_details[0].arguments_preview,
+            "{\"cmd\":\"ls\"}"
+        );
+    }
+
+    #[test]
+    fn scroll_state_counts_unread_only_while_scrolled_up() {
+        let mut state = ChatScrollState::default();
+        state.observe(3, true);
+        assert_eq!(state.unread, 0);
+
+        state.observe(3, false);
+        state.observe(5, false);
+        assert_eq!(state.unread, 2);
+
+        state.observe(5, true);
+        assert_eq!(state.unread, 0);
+        state.observe(6, true);
+        assert_eq!(state.unread, 0);
+    }
+
+    #[test]
+    fn scroll_state_first_observation_marks_history_seen() {
+        let mut state = ChatScrollState::default();
+        state.observe(40, false);
+        assert_eq!(state.unread, 0);
+    }
+
     #[test]
     fn leaves_plain_message_unchanged() {
         let content = "Hello there";
@@ -1128,6 +1861,55 @@ mod tests {
         assert!(payload.thinking_details.is_empty());
     }
 
+    #[test]
+    fn tool_argument_key_picks_the_telling_argument() {
+        use serde_json::json;
+        assert_eq!(
+            tool_argument_key(&json!({ "timeout": 30, "command": "cargo test\n--quiet" })),
+            Some("cargo test".to_string())
+        );
+        assert_eq!(
+            tool_argument_key(&json!({ "url": "https://example.com", "query": "x" })),
+            Some("https://example.com".to_string())
+        );
+        assert_eq!(
+            tool_argument_key(&json!({ "cmd": ["ls", "-la"] })),
+            Some("ls -la".to_string())
+        );
+        assert_eq!(
+            tool_argument_key(&json!("{\"file_path\": \"src/main.rs\"}")),
+            Some("src/main.rs".to_string())
+        );
+        assert_eq!(tool_argument_key(&json!({ "path": "   " })), None);
+        assert_eq!(tool_argument_key(&json!({ "count": 3 })), None);
+        assert_eq!(tool_argument_key(&json!(null)), None);
+    }
+
+    #[test]
+    fn stored_reasoning_stays_apart_from_the_answer() {
+        let mut msg: ChatMessage = serde_json::from_value(serde_json::json!({
+            "id": "m1",
+            "conversation_id": "c1",
+            "role": "agent",
+            "content": "The answer is 4.",
+            "created_at": "2026-02-17T05:19:24Z",
+            "processed": true,
+            "turn_id": "t1",
+            "reasoning": "  2 + 2 is 4.  "
+        }))
+        .expect("decode");
+        let payload = message_render_payload(&msg);
+        assert_eq!(payload.reasoning.as_deref(), Some("2 + 2 is 4."));
+        assert_eq!(payload.display_content, "The answer is 4.");
+        assert!(payload.thinking_details.is_empty());
+
+        msg.reasoning = Some("   ".to_string());
+        msg.content = "<think>legacy</think>Answer".to_string();
+        let payload = message_render_payload(&msg);
+        assert_eq!(payload.reasoning, None);
+        assert_eq!(payload.thinking_details, vec!["legacy"]);
+    }
+
     #[test]
     fn parses_embedded_thinking_block() {
         let content = "Answer\n\n[thinking]\n[\"step one\",\"step two\"]\n[/thinking]";
@@ -1183,4 +1965,11 @@ mod tests {
         assert_eq!(turn_control.decision, "continue");
         assert_eq!(turn_control.status, "still_working");
     }
+
+    #[test]
+    fn long_reply_marker_respects_limit_and_off_switch() {
+        assert_eq!(long_reply_length("  short  ", 10), None);
+        assert_eq!(long_reply_length("ééééé", 4), Some(5));
+        assert_eq!(long_reply_length(&"x".repeat(50_000), 0), None);
+    }
 }
@@ -0,0 +1,300 @@
+use eframe::egui::{self, RichText};
+
+/// Actions reachable from the Ctrl+K palette. `app.rs` dispatches each one to
+/// the same handler its toolbar/picker button uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteAction {
+    NewChat,
+    ImportChat,
+    OrganizeChats,
+    ArchivedChats,
+    SummarizeChat,
+    RenameChat,
+    DeleteChat,
+    TogglePause,
+    ToggleAutonomyPause,
+    StopTurn,
+    ThinkNow,
+    OpenSettings,
+    OpenCharacter,
+    ToggleActivityPanel,
+    ToggleFocusMode,
+    CompareModels,
+    QuickAsk,
+    DebugDump,
+}
+
+pub struct PaletteCommand {
+    pub action: PaletteAction,
+    pub label: &'static str,
+    pub keywords: &'static str,
+}
+
+/// Extend this list to expose a new action; order is the unfiltered display order.
+pub const PALETTE_COMMANDS: &[PaletteCommand] = &[
+    PaletteCommand {
+        action: PaletteAction::NewChat,
+        label: "New chat",
+        keywords: "conversation create start",
+    },
+    PaletteCommand {
+        action: PaletteAction::ImportChat,
+        label: "Import chat…",
+        keywords: "conversation openai json export load",
+    },
+    PaletteCommand {
+        action: PaletteAction::OrganizeChats,
+        label: "Organize chats…",
+        keywords: "conversation group folder reorder sort drag",
+    },
+    PaletteCommand {
+        action: PaletteAction::ArchivedChats,
+        label: "Archived chats…",
+        keywords: "conversation restore unarchive old hidden retention",
+    },
+    PaletteCommand {
+        action: PaletteAction::SummarizeChat,
+        label: "Summarize chat",
+        keywords: "conversation recap digest journal",
+    },
+    PaletteCommand {
+        action: PaletteAction::RenameChat,
+        label: "Rename chat",
+        keywords: "conversation title",
+    },
+    PaletteCommand {
+        action: PaletteAction::DeleteChat,
+        label: "Delete chat",
+        keywords: "conversation remove",
+    },
+    PaletteCommand {
+        action: PaletteAction::TogglePause,
+        label: "Pause / resume agent",
+        keywords: "toggle pause resume",
+    },
+    PaletteCommand {
+        action: PaletteAction::ToggleAutonomyPause,
+        label: "Pause / resume autonomy only",
+        keywords: "quiet autonomous cycles background",
+    },
+    PaletteCommand {
+        action: PaletteAction::StopTurn,
+        label: "Stop turn",
+        keywords: "cancel abort halt",
+    },
+    PaletteCommand {
+        action: PaletteAction::ThinkNow,
+        label: "Think now",
+        keywords: "cycle trigger ooda nudge autonomous",
+    },
+    PaletteCommand {
+        action: PaletteAction::OpenSettings,
+        label: "Open settings",
+        keywords: "preferences config",
+    },
+    PaletteCommand {
+        action: PaletteAction::OpenCharacter,
+        label: "Open character",
+        keywords: "persona card",
+    },
+    PaletteCommand {
+        action: PaletteAction::ToggleActivityPanel,
+        label: "Toggle activity panel",
+        keywords: "mind sidebar show hide",
+    },
+    PaletteCommand {
+        action: PaletteAction::ToggleFocusMode,
+        label: "Toggle focus mode",
+        keywords: "zen distraction chrome clean f11",
+    },
+    PaletteCommand {
+        action: PaletteAction::CompareModels,
+        label: "Compare models",
+        keywords: "evaluate side by side llm debug dry run",
+    },
+    PaletteCommand {
+        action: PaletteAction::QuickAsk,
+        label: "Quick ask about clipboard",
+        keywords: "mini composer popup hotkey ctrl shift space paste",
+    },
+    PaletteCommand {
+        action: PaletteAction::DebugDump,
+        label: "Save debug dump",
+        keywords: "bug report snapshot events diagnostics export state",
+    },
+];
+
+#[derive(Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    query: String,
+    selected: usize,
+}
+
+impl CommandPalette {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    /// Draws the palette when open and returns the chosen action, if any.
+    pub fn render(&mut self, ctx: &egui::Context) -> Option<PaletteAction> {
+        if !self.open {
+            return None;
+        }
+
+        let matches = filter_commands(&self.query);
+        if matches.is_empty() {
+            self.selected = 0;
+        } else {
+            self.selected = self.selected.min(matches.len() - 1);
+        }
+
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+            )
+        });
+        if escape {
+            self.open = false;
+            return None;
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        if down && self.selected + 1 < matches.len() {
+            self.selected += 1;
+        }
+
+        let mut chosen = enter
+            .then(|| matches.get(self.selected).map(|command| command.action))
+            .flatten();
+
+        egui::Window::new("Command Palette")
+            .collapsible(false)
+            .resizable(false)
+            .title_bar(false)
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 80.0))
+            .fixed_size(egui::vec2(360.0, 0.0))
+            .show(ctx, |ui| {
+                let search = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text("Type a command…")
+                        .desired_width(f32::INFINITY),
+                );
+                search.request_focus();
+                if search.changed() {
+                    self.selected = 0;
+                }
+                ui.separator();
+
+                if matches.is_empty() {
+                    ui.label(RichText::new("No matching commands").weak().italics());
+                }
+                for (idx, command) in matches.iter().enumerate() {
+                    if ui
+                        .selectable_label(idx == self.selected, command.label)
+                        .clicked()
+                    {
+                        chosen = Some(command.action);
+                    }
+                }
+                ui.label(
+                    RichText::new("↑/↓ to move · Enter to run · Esc to close")
+                        .small()
+                        .weak(),
+                );
+            });
+
+        if chosen.is_some() {
+            self.open = false;
+        }
+        chosen
+    }
+}
+
+/// Commands matching `query`, best match first. Empty query keeps list order.
+fn filter_commands(query: &str) -> Vec<&'static PaletteCommand> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return PALETTE_COMMANDS.iter().collect();
+    }
+
+    let mut scored = PALETTE_COMMANDS
+        .iter()
+        .filter_map(|command| {
+            let label_score = fuzzy_score(&query, &command.label.to_lowercase());
+            let keyword_score = fuzzy_score(&query, command.keywords).map(|score| score / 2);
+            label_score.max(keyword_score).map(|score| (score, command))
+        })
+        .collect::<Vec<_>>();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, command)| command).collect()
+}
+
+/// Subsequence match: every query char must appear in order. Consecutive runs
+/// and word-start hits score higher.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let mut score = 0;
+    let mut previous_match: Option<usize> = None;
+    let mut candidate_chars = candidate.char_indices();
+    let mut prev_char = ' ';
+
+    for query_char in query.chars() {
+        loop {
+            let (idx, candidate_char) = candidate_chars.next()?;
+            let at_word_start = prev_char == ' ' || prev_char == '/';
+            prev_char = candidate_char;
+            if candidate_char != query_char {
+                continue;
+            }
+            score += 1;
+            if at_word_start {
+                score += 3;
+            }
+            if previous_match.is_some_and(|prev| prev + candidate_char.len_utf8() >= idx) {
+                score += 2;
+            }
+            previous_match = Some(idx);
+            break;
+        }
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_lists_every_command_in_order() {
+        let commands = filter_commands("  ");
+        assert_eq!(commands.len(), PALETTE_COMMANDS.len());
+        assert_eq!(commands[0].action, PaletteAction::NewChat);
+    }
+
+    #[test]
+    fn fuzzy_query_ranks_best_label_first() {
+        let commands = filter_commands("stp");
+        assert_eq!(commands[0].action, PaletteAction::StopTurn);
+
+        let commands = filter_commands("set");
+        assert_eq!(commands[0].action, PaletteAction::OpenSettings);
+    }
+
+    #[test]
+    fn keywords_match_when_label_does_not() {
+        let commands = filter_commands("sidebar");
+        assert_eq!(commands[0].action, PaletteAction::ToggleActivityPanel);
+    }
+
+    #[test]
+    fn non_subsequence_query_matches_nothing() {
+        assert!(fuzzy_score("zzz", "new chat").is_none());
+        assert!(filter_commands("qqqq").is_empty());
+    }
+}
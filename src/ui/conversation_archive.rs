@@ -0,0 +1,189 @@
+use eframe::egui::{self, Color32, RichText};
+
+use crate::api::ChatConversation;
+
+/// How many archived conversations one listing fetches.
+pub const ARCHIVED_LIST_LIMIT: usize = 200;
+
+/// What the Archived window asks `app.rs` to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArchiveAction {
+    Refresh,
+    /// Un-archive; with `open` the main window also switches to it.
+    Restore {
+        conversation_id: String,
+        open: bool,
+    },
+}
+
+/// Case-insensitive match on title, tags, and note; blank queries match everything.
+pub fn archived_matches(conversation: &ChatConversation, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return true;
+    }
+    conversation.title.to_lowercase().contains(&query)
+        || conversation
+            .tags
+            .iter()
+            .any(|tag| tag.to_lowercase().contains(&query))
+        || conversation
+            .note
+            .as_deref()
+            .is_some_and(|note| note.to_lowercase().contains(&query))
+}
+
+/// Lists conversations the backend archived (by retention or by hand) and
+/// restores them. Archived conversations never appear in the main picker.
+#[derive(Default)]
+pub struct ArchivedConversationsWindow {
+    pub open: bool,
+    conversations: Option<Vec<ChatConversation>>,
+    error: Option<String>,
+    query: String,
+}
+
+impl ArchivedConversationsWindow {
+    pub fn set_list(&mut self, result: Result<Vec<ChatConversation>, String>) {
+        match result {
+            Ok(conversations) => {
+                self.conversations = Some(conversations);
+                self.error = None;
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    /// Drops a restored conversation from the list without refetching.
+    pub fn restored(&mut self, conversation_id: &str) {
+        if let Some(conversations) = self.conversations.as_mut() {
+            conversations.retain(|conversation| conversation.id != conversation_id);
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> Option<ArchiveAction> {
+        if !self.open {
+            return None;
+        }
+        let mut action = None;
+        let mut open = self.open;
+        egui::Window::new("Archived Conversations")
+            .id(egui::Id::new("archived_conversations_window"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.query)
+                            .hint_text("Filter by title, tag, or note")
+                            .desired_width(260.0),
+                    );
+                    if ui.button("Refresh").clicked() {
+                        action = Some(ArchiveAction::Refresh);
+                    }
+                });
+                ui.label(
+                    RichText::new(
+                        "Conversations untouched for the retention period (Settings → Memory → Retention) land here. Pinned ones never do.",
+                    )
+                    .small()
+                    .weak(),
+                );
+                if let Some(error) = &self.error {
+                    ui.colored_label(Color32::from_rgb(220, 120, 120), error);
+                }
+                ui.separator();
+
+                let Some(conversations) = &self.conversations else {
+                    return;
+                };
+                let visible: Vec<_> = conversations
+                    .iter()
+                    .filter(|conversation| archived_matches(conversation, &self.query))
+                    .collect();
+                if visible.is_empty() {
+                    ui.label(RichText::new("No archived conversations.").italics().weak());
+                    return;
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(360.0)
+                    .show(ui, |ui| {
+                        for conversation in visible {
+                            ui.horizontal(|ui| {
+                                ui.vertical(|ui| {
+                                    ui.label(RichText::new(&conversation.title).strong());
+                                    let mut details =
+                                        format!("{} messages", conversation.message_count);
+                                    if let Some(archived_at) = conversation.archived_at {
+                                        details.push_str(&format!(
+                                            " · archived {}",
+                                            archived_at.format("%Y-%m-%d")
+                                        ));
+                                    }
+                                    ui.label(RichText::new(details).small().weak());
+                                });
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        if ui.button("Restore & open").clicked() {
+                                            action = Some(ArchiveAction::Restore {
+                                                conversation_id: conversation.id.clone(),
+                                                open: true,
+                                            });
+                                        }
+                                        if ui.button("Restore").clicked() {
+                                            action = Some(ArchiveAction::Restore {
+                                                conversation_id: conversation.id.clone(),
+                                                open: false,
+                                            });
+                                        }
+                                    },
+                                );
+                            });
+                            ui.separator();
+                        }
+                    });
+            });
+        self.open = open;
+        action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archived(id: &str, title: &str, tags: &[&str], note: Option<&str>) -> ChatConversation {
+        ChatConversation::fixture(
+            id,
+            serde_json::json!({
+                "title": title,
+                "message_count": 3,
+                "tags": tags,
+                "note": note,
+                "archived_at": "2026-04-05T00:00:00Z",
+            }),
+        )
+    }
+
+    #[test]
+    fn filter_searches_title_tags_and_note_and_restore_removes_the_row() {
+        let trip = archived("c1", "Kyoto trip", &["travel"], None);
+        let taxes = archived("c2", "2025 paperwork", &[], Some("Taxes, receipts"));
+        assert!(archived_matches(&trip, "  "));
+        assert!(archived_matches(&trip, "KYOTO"));
+        assert!(archived_matches(&trip, "trav"));
+        assert!(archived_matches(&taxes, "receipts"));
+        assert!(!archived_matches(&taxes, "travel"));
+
+        let mut window = ArchivedConversationsWindow::default();
+        window.set_list(Ok(vec![trip, taxes]));
+        window.restored("c1");
+        window.set_list(Err("backend offline".to_string()));
+        let remaining = window.conversations.as_ref().expect("list kept");
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, "c2");
+        assert_eq!(window.error.as_deref(), Some("backend offline"));
+    }
+}
@@ -0,0 +1,311 @@
+use eframe::egui::{self, Color32, RichText};
+
+use crate::api::{ChatConversation, ConversationPlacement};
+
+/// Heading for conversations without a group in the organizer. The picker lists
+/// them first with no heading.
+pub const UNGROUPED_LABEL: &str = "Ungrouped";
+
+/// Conversations bucketed for display: ungrouped first, then named groups
+/// A→Z. Group names match case-insensitively ("Work" and "work" share one
+/// bucket, under the first spelling seen). Within a bucket, conversations with a `sort_index`
+/// come first in index order, then the rest in the backend's recency order.
+pub fn grouped_conversations(
+    conversations: &[ChatConversation],
+) -> Vec<(Option<&str>, Vec<&ChatConversation>)> {
+    let mut buckets: Vec<(Option<&str>, Vec<&ChatConversation>)> = Vec::new();
+    for conversation in conversations {
+        let group = normalized_group(conversation.group.as_deref());
+        match buckets
+            .iter_mut()
+            .find(|(name, _)| same_group(*name, group))
+        {
+            Some((_, members)) => members.push(conversation),
+            None => buckets.push((group, vec![conversation])),
+        }
+    }
+    buckets.sort_by_key(|(name, _)| name.map(str::to_lowercase));
+    for (_, members) in &mut buckets {
+        // Stable sort keeps recency order among unindexed conversations.
+        members.sort_by_key(|conversation| {
+            (conversation.sort_index.is_none(), conversation.sort_index)
+        });
+    }
+    buckets
+}
+
+/// Moves `moved_id` into `target_group`, before `before_id` (or to the end of the
+/// group), and returns fresh placements for every conversation so the whole
+/// order persists.
+pub fn move_conversation(
+    conversations: &[ChatConversation],
+    moved_id: &str,
+    target_group: Option<&str>,
+    before_id: Option<&str>,
+) -> Vec<ConversationPlacement> {
+    let target_group = normalized_group(target_group);
+    let mut buckets: Vec<(Option<String>, Vec<String>)> = grouped_conversations(conversations)
+        .into_iter()
+        .map(|(name, members)| {
+            (
+                name.map(str::to_string),
+                members
+                    .into_iter()
+                    .map(|conversation| conversation.id.clone())
+                    .filter(|id| id != moved_id)
+                    .collect(),
+            )
+        })
+        .collect();
+
+    if !buckets
+        .iter()
+        .any(|(name, _)| same_group(name.as_deref(), target_group))
+    {
+        buckets.push((target_group.map(str::to_string), Vec::new()));
+    }
+    if let Some((_, members)) = buckets
+        .iter_mut()
+        .find(|(name, _)| same_group(name.as_deref(), target_group))
+    {
+        let position = before_id
+            .and_then(|before| members.iter().position(|id| id == before))
+            .unwrap_or(members.len());
+        members.insert(position, moved_id.to_string());
+    }
+
+    buckets
+        .into_iter()
+        .flat_map(|(group, members)| {
+            members
+                .into_iter()
+                .enumerate()
+                .map(move |(index, id)| ConversationPlacement {
+                    id,
+                    group: group.clone(),
+                    sort_index: index as i64,
+                })
+        })
+        .collect()
+}
+
+fn normalized_group(group: Option<&str>) -> Option<&str> {
+    group.map(str::trim).filter(|group| !group.is_empty())
+}
+
+fn same_group(left: Option<&str>, right: Option<&str>) -> bool {
+    match (left, right) {
+        (Some(left), Some(right)) => left.to_lowercase() == right.to_lowercase(),
+        (left, right) => left.is_none() && right.is_none(),
+    }
+}
+
+/// Drag-and-drop window for arranging conversations into groups.
+#[derive(Default)]
+pub struct ConversationOrganizer {
+    pub open: bool,
+    new_group: String,
+    /// Groups added here that have no conversations yet.
+    empty_groups: Vec<String>,
+}
+
+impl ConversationOrganizer {
+    /// Draws the organizer and returns the new placements after a drop.
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        conversations: &[ChatConversation],
+    ) -> Option<Vec<ConversationPlacement>> {
+        if !self.open {
+            return None;
+        }
+
+        let mut buckets = grouped_conversations(conversations);
+        self.empty_groups.retain(|group| {
+            !buckets
+                .iter()
+                .any(|(name, _)| same_group(*name, Some(group)))
+        });
+        for group in &self.empty_groups {
+            buckets.push((Some(group.as_str()), Vec::new()));
+        }
+        if !buckets.iter().any(|(name, _)| name.is_none()) {
+            buckets.insert(0, (None, Vec::new()));
+        }
+
+        let mut dropped: Option<(String, Option<String>, Option<String>)> = None;
+        let mut added_group = None;
+        let mut open = self.open;
+        egui::Window::new("Organize Conversations")
+            .open(&mut open)
+            .resizable(true)
+            .default_width(360.0)
+            .vscroll(true)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new("Drag a conversation onto another to place it before that one, or onto a group heading to move it to the end of that group.")
+                        .small()
+                        .weak(),
+                );
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_group)
+                            .hint_text("New group name")
+                            .desired_width(180.0),
+                    );
+                    let name = self.new_group.trim().to_string();
+                    if ui
+                        .add_enabled(!name.is_empty(), egui::Button::new("Add group"))
+                        .clicked()
+                    {
+                        added_group = Some(name);
+                    }
+                });
+                ui.separator();
+
+                for (group, members) in &buckets {
+                    let heading = ui.label(
+                        RichText::new(group.unwrap_or(UNGROUPED_LABEL))
+                            .strong()
+                            .color(Color32::LIGHT_GRAY),
+                    );
+                    highlight_drop_target(ui, &heading);
+                    if let Some(moved) = heading.dnd_release_payload::<String>() {
+                        dropped = Some((moved.to_string(), group.map(str::to_string), None));
+                    }
+                    if members.is_empty() {
+                        ui.label(RichText::new("  (empty)").small().weak().italics());
+                    }
+                    for conversation in members {
+                        let row = ui
+                            .dnd_drag_source(
+                                egui::Id::new(("conversation_drag", &conversation.id)),
+                                conversation.id.clone(),
+                                |ui| {
+                                    ui.label(format!("  ☰ {}", conversation.title));
+                                },
+                            )
+                            .response;
+                        highlight_drop_target(ui, &row);
+                        if let Some(moved) = row.dnd_release_payload::<String>() {
+                            if *moved != conversation.id {
+                                dropped = Some((
+                                    moved.to_string(),
+                                    group.map(str::to_string),
+                                    Some(conversation.id.clone()),
+                                ));
+                            }
+                        }
+                    }
+                    ui.add_space(6.0);
+                }
+            });
+        self.open = open;
+        if let Some(group) = added_group {
+            self.empty_groups.push(group);
+            self.new_group.clear();
+        }
+
+        dropped.map(|(moved_id, group, before_id)| {
+            move_conversation(
+                conversations,
+                &moved_id,
+                group.as_deref(),
+                before_id.as_deref(),
+            )
+        })
+    }
+}
+
+/// Draws an insertion line above a row or heading while a conversation hovers it.
+fn highlight_drop_target(ui: &egui::Ui, response: &egui::Response) {
+    if response.dnd_hover_payload::<String>().is_some() {
+        let rect = response.rect;
+        ui.painter().hline(
+            rect.x_range(),
+            rect.top(),
+            egui::Stroke::new(2.0, Color32::LIGHT_BLUE),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation(id: &str, group: Option<&str>, sort_index: Option<i64>) -> ChatConversation {
+        ChatConversation::fixture(
+            id,
+            serde_json::json!({ "group": group, "sort_index": sort_index }),
+        )
+    }
+
+    fn ids<'a>(bucket: &[&'a ChatConversation]) -> Vec<&'a str> {
+        bucket.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    #[test]
+    fn ungrouped_come_first_and_indexed_conversations_lead_their_group() {
+        let conversations = [
+            conversation("recent", None, None),
+            conversation("w-new", Some("work"), None),
+            conversation("pinned", None, Some(0)),
+            conversation("a1", Some("Archive"), None),
+            conversation("w-first", Some(" work "), Some(0)),
+            conversation("w-caps", Some("WORK"), None),
+        ];
+        let buckets = grouped_conversations(&conversations);
+        let names: Vec<_> = buckets.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, [None, Some("Archive"), Some("work")]);
+        assert_eq!(ids(&buckets[0].1), ["pinned", "recent"]);
+        assert_eq!(ids(&buckets[2].1), ["w-first", "w-new", "w-caps"]);
+    }
+
+    #[test]
+    fn moving_a_conversation_persists_a_full_dense_order() {
+        let conversations = [
+            conversation("a", None, None),
+            conversation("b", None, None),
+            conversation("c", Some("Work"), Some(0)),
+        ];
+
+        let placements = move_conversation(&conversations, "b", Some("Work"), Some("c"));
+        let placed = |id: &str| placements.iter().find(|p| p.id == id).cloned();
+        assert_eq!(placements.len(), 3);
+        assert_eq!(
+            placed("b"),
+            Some(ConversationPlacement {
+                id: "b".to_string(),
+                group: Some("Work".to_string()),
+                sort_index: 0,
+            })
+        );
+        assert_eq!(placed("c").map(|p| p.sort_index), Some(1));
+        assert_eq!(
+            placed("a").map(|p| (p.group, p.sort_index)),
+            Some((None, 0))
+        );
+
+        // Applying the placements reproduces the same order on the next load.
+        let reloaded: Vec<_> = conversations
+            .iter()
+            .map(|c| {
+                let p = placed(&c.id).expect("placement");
+                conversation(&c.id, p.group.as_deref(), Some(p.sort_index))
+            })
+            .collect();
+        let buckets = grouped_conversations(&reloaded);
+        assert_eq!(ids(&buckets[1].1), ["b", "c"]);
+
+        // A differently cased name joins the existing group under its spelling.
+        let recased = move_conversation(&conversations, "a", Some("work"), None);
+        assert!(recased
+            .iter()
+            .any(|p| p.id == "a" && p.group.as_deref() == Some("Work") && p.sort_index == 1));
+
+        let into_new_group = move_conversation(&conversations, "a", Some("Later"), None);
+        assert!(into_new_group
+            .iter()
+            .any(|p| p.id == "a" && p.group.as_deref() == Some("Later") && p.sort_index == 0));
+    }
+}
@@ -0,0 +1,121 @@
+use eframe::egui::{self, Color32, RichText};
+use flume::Receiver;
+
+use crate::api::ConversationRecap;
+
+/// A finished summarize request, tagged with the conversation it covers so a
+/// late result never shows under another conversation's title.
+#[derive(Debug, Clone)]
+pub struct RecapOutcome {
+    pub conversation_id: String,
+    pub result: Result<ConversationRecap, String>,
+}
+
+/// A summarize request picked in the window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummarizeRequest {
+    pub conversation_id: String,
+    pub save_to_journal: bool,
+}
+
+/// Window that asks the backend for a recap of one conversation and shows it.
+#[derive(Default)]
+pub struct ConversationSummaryWindow {
+    pub open: bool,
+    conversation_id: String,
+    title: String,
+    save_to_journal: bool,
+    pending: Option<Receiver<RecapOutcome>>,
+    outcome: Option<Result<ConversationRecap, String>>,
+}
+
+impl ConversationSummaryWindow {
+    /// Opens the window for a conversation, clearing a recap (or a running
+    /// request) of a different one.
+    pub fn open_for(&mut self, conversation_id: &str, title: &str) {
+        if self.conversation_id != conversation_id {
+            self.outcome = None;
+            self.pending = None;
+        }
+        self.open = true;
+        self.conversation_id = conversation_id.to_string();
+        self.title = title.to_string();
+    }
+
+    /// Hands over the in-flight request; `render` picks up its result.
+    pub fn start(&mut self, result_rx: Receiver<RecapOutcome>) {
+        self.pending = Some(result_rx);
+        self.outcome = None;
+    }
+
+    /// Draws the window and returns a request when `Summarize` is clicked.
+    pub fn render(&mut self, ctx: &egui::Context) -> Option<SummarizeRequest> {
+        if let Some(outcome) = self.pending.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.pending = None;
+            if outcome.conversation_id == self.conversation_id {
+                self.outcome = Some(outcome.result);
+            }
+        }
+        if !self.open {
+            return None;
+        }
+
+        let mut request = None;
+        let mut open = self.open;
+        egui::Window::new(format!("Summary: {}", self.title))
+            .id(egui::Id::new("conversation_summary_window"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.save_to_journal, "Also save to journal");
+                    let running = self.pending.is_some();
+                    let label = if self.outcome.is_some() {
+                        "Summarize again"
+                    } else {
+                        "Summarize"
+                    };
+                    if ui.add_enabled(!running, egui::Button::new(label)).clicked() {
+                        request = Some(SummarizeRequest {
+                            conversation_id: self.conversation_id.clone(),
+                            save_to_journal: self.save_to_journal,
+                        });
+                    }
+                    if running {
+                        ui.spinner();
+                        ui.label(RichText::new("Reading the conversation…").small().weak());
+                    }
+                });
+                ui.separator();
+
+                match &self.outcome {
+                    None => {}
+                    Some(Err(error)) => {
+                        ui.colored_label(Color32::from_rgb(220, 120, 120), error);
+                    }
+                    Some(Ok(recap)) => {
+                        let mut details = format!("{} messages", recap.message_count);
+                        if recap.chunk_count > 1 {
+                            details
+                                .push_str(&format!(" · summarized in {} parts", recap.chunk_count));
+                        }
+                        if recap.journal_entry_id.is_some() {
+                            details.push_str(" · saved to journal");
+                        }
+                        ui.label(RichText::new(details).small().weak());
+                        egui::ScrollArea::vertical()
+                            .max_height(420.0)
+                            .show(ui, |ui| {
+                                ui.add(egui::Label::new(&recap.summary).wrap().selectable(true));
+                            });
+                        if ui.small_button("Copy").clicked() {
+                            ui.ctx().copy_text(recap.summary.clone());
+                        }
+                    }
+                }
+            });
+        self.open = open;
+        request
+    }
+}
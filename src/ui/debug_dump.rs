@@ -0,0 +1,245 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::api::{is_secret_field_name, AgentVisualState, FrontendEvent, OrientationSummary};
+use crate::config::AgentConfig;
+
+/// Only the newest events go into a dump; older ones rarely explain a glitch.
+pub const DEBUG_DUMP_MAX_EVENTS: usize = 500;
+/// Written in place of every secret config value.
+pub const REDACTED: &str = "[redacted]";
+
+/// Field names whose string values never leave the machine in a dump. Checked
+/// at every depth, so plugin settings (`persistent_auth_encryption_key`) and
+/// tool-call headers count too.
+fn is_secret_field(name: &str) -> bool {
+    is_secret_field_name(name) || name.eq_ignore_ascii_case("authorization")
+}
+
+fn is_unset(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(text) => text.is_empty(),
+        _ => false,
+    }
+}
+
+/// Replaces non-empty string values of secret-looking fields with `REDACTED`.
+/// Config edits shaped like `ConfigFieldChange` (`{field, old, new}`) name the
+/// secret in `field`, so their `old`/`new` are redacted whatever their type.
+/// Empty strings and nulls stay, so a dump still shows whether a secret is set.
+pub fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let secret_change = map
+                .get("field")
+                .and_then(Value::as_str)
+                .is_some_and(is_secret_field);
+            for (name, field) in map.iter_mut() {
+                let secret = if secret_change && matches!(name.as_str(), "old" | "new") {
+                    !is_unset(field)
+                } else {
+                    is_secret_field(name) && field.is_string() && !is_unset(field)
+                };
+                if secret {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact_secrets(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// How the desktop app last saw the backend.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionSnapshot {
+    pub backend_url: String,
+    pub auth_failed: bool,
+    /// Error from the most recent status poll; `None` when it succeeded.
+    pub last_status_error: Option<String>,
+}
+
+/// Everything a bug report needs from the running UI, as one JSON document.
+#[derive(Debug, Clone, Serialize)]
+pub struct DebugDump {
+    pub captured_at: DateTime<Utc>,
+    pub app_version: &'static str,
+    pub connection: ConnectionSnapshot,
+    pub visual_state: AgentVisualState,
+    pub current_activity: Option<String>,
+    pub last_orientation: Option<OrientationSummary>,
+    pub last_action: Option<String>,
+    pub last_journal: Option<String>,
+    /// Total events seen this session; `events` holds at most the newest
+    /// `DEBUG_DUMP_MAX_EVENTS` of them, oldest first, redacted like the config.
+    pub event_count: usize,
+    pub events: Value,
+    /// The applied config (last loaded from or saved to the backend), not
+    /// unsaved Settings edits.
+    pub config: Value,
+}
+
+impl DebugDump {
+    pub fn new(
+        connection: ConnectionSnapshot,
+        visual_state: AgentVisualState,
+        events: &[FrontendEvent],
+        config: &impl Serialize,
+    ) -> anyhow::Result<Self> {
+        let mut config = serde_json::to_value(config).context("failed to serialize config")?;
+        redact_secrets(&mut config);
+        let skip = events.len().saturating_sub(DEBUG_DUMP_MAX_EVENTS);
+        // Tool arguments and config proposals can carry credentials too.
+        let mut recent_events =
+            serde_json::to_value(&events[skip..]).context("failed to serialize events")?;
+        redact_secrets(&mut recent_events);
+        Ok(Self {
+            captured_at: Utc::now(),
+            app_version: env!("CARGO_PKG_VERSION"),
+            connection,
+            visual_state,
+            current_activity: None,
+            last_orientation: None,
+            last_action: None,
+            last_journal: None,
+            event_count: events.len(),
+            events: recent_events,
+            config,
+        })
+    }
+
+    /// Writes `ponderer-debug-<local timestamp>.json` into `dir`, creating it if needed.
+    pub fn write_to(&self, dir: &Path) -> anyhow::Result<PathBuf> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+        let path = dir.join(format!(
+            "ponderer-debug-{}.json",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        let json = serde_json::to_string_pretty(self).context("failed to serialize debug dump")?;
+        std::fs::write(&path, json)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Dumps land next to the config file, like `ponderer_ui.json`.
+pub fn debug_dump_dir() -> PathBuf {
+    AgentConfig::config_path().with_file_name("debug_dumps")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dump_redacts_secrets_and_keeps_the_newest_events() {
+        let config = serde_json::json!({
+            "llm_api_url": "http://localhost:11434/v1",
+            "llm_model": "qwen2.5",
+            "llm_api_key": "sk-live-123",
+            "telegram_bot_token": "4242:telegram-secret",
+            "telegram_chat_id": 99,
+            "max_tool_iterations": 10,
+            "plugin_settings": {
+                "browser-orb": {
+                    "persistent_auth_encryption_key": "hunter2",
+                    "allowed_domains": "example.com",
+                },
+                "graphchan-orb": { "api_token": "" },
+            },
+        });
+        let mut events: Vec<FrontendEvent> = (0..DEBUG_DUMP_MAX_EVENTS + 1)
+            .map(|i| FrontendEvent::Observation(format!("event {}", i)))
+            .collect();
+        events.push(FrontendEvent::ConfigProposed(
+            serde_json::from_value(serde_json::json!({
+                "id": "p1",
+                "reason": "Switch providers",
+                "changes": [
+                    { "field": "llm_api_key", "old": "sk-old-456", "new": "sk-new-789" },
+                    { "field": "llm_model", "old": "llama3", "new": "qwen2.5" },
+                ],
+            }))
+            .expect("proposal"),
+        ));
+        events.push(FrontendEvent::ToolCallProgress {
+            conversation_id: "c1".to_string(),
+            tool_name: "http_fetch".to_string(),
+            output_preview: String::new(),
+            iteration: None,
+            max_iterations: None,
+            tool_call_id: None,
+            partial: false,
+            arguments: Some(serde_json::json!({
+                "url": "https://api.example.com",
+                "headers": { "Authorization": "Bearer bearer-xyz", "api_token": "tok-abc" },
+            })),
+        });
+        let mut dump = DebugDump::new(
+            ConnectionSnapshot {
+                backend_url: "http://127.0.0.1:8787".to_string(),
+                auth_failed: false,
+                last_status_error: Some("connection refused".to_string()),
+            },
+            AgentVisualState::Idle,
+            &events,
+            &config,
+        )
+        .expect("dump");
+        dump.last_action = Some("write_journal".to_string());
+
+        let json = serde_json::to_string(&dump).expect("serialize");
+        for secret in [
+            "sk-live-123",
+            "telegram-secret",
+            "hunter2",
+            "sk-old-456",
+            "sk-new-789",
+            "tok-abc",
+            "bearer-xyz",
+        ] {
+            assert!(!json.contains(secret), "{} leaked", secret);
+        }
+        assert_eq!(dump.config["llm_api_key"], REDACTED);
+        assert_eq!(dump.config["telegram_bot_token"], REDACTED);
+        assert_eq!(
+            dump.config["plugin_settings"]["browser-orb"]["persistent_auth_encryption_key"],
+            REDACTED
+        );
+        assert_eq!(
+            dump.config["plugin_settings"]["graphchan-orb"]["api_token"],
+            ""
+        );
+        assert_eq!(dump.config["llm_model"], "qwen2.5");
+        assert_eq!(dump.config["telegram_chat_id"], 99);
+
+        assert_eq!(dump.event_count, DEBUG_DUMP_MAX_EVENTS + 3);
+        assert_eq!(
+            dump.events.as_array().map(Vec::len),
+            Some(DEBUG_DUMP_MAX_EVENTS)
+        );
+        let value: Value = serde_json::from_str(&json).expect("json");
+        assert_eq!(value["events"][0]["Observation"], "event 3");
+        let changes = &value["events"][DEBUG_DUMP_MAX_EVENTS - 2]["ConfigProposed"]["changes"];
+        assert_eq!(changes[0]["old"], REDACTED);
+        assert_eq!(changes[0]["new"], REDACTED);
+        assert_eq!(changes[1]["new"], "qwen2.5");
+        let arguments =
+            &value["events"][DEBUG_DUMP_MAX_EVENTS - 1]["ToolCallProgress"]["arguments"];
+        assert_eq!(arguments["headers"]["api_token"], REDACTED);
+        assert_eq!(arguments["url"], "https://api.example.com");
+        assert_eq!(
+            value["connection"]["last_status_error"],
+            "connection refused"
+        );
+        assert_eq!(value["last_action"], "write_journal");
+    }
+}
@@ -0,0 +1,192 @@
+use eframe::egui::{self, Color32, RichText};
+use flume::Receiver;
+
+use crate::api::{is_auth_error, CheckStatus, DiagnosticCheck};
+
+/// Rows for the connection itself, plus whatever the backend's own checks
+/// returned. `health` is the `/v1/health` probe, which needs the token in
+/// required-auth mode, so a 401/403 there means "running, wrong token".
+/// `server_checks` is only consulted once the probe succeeded.
+pub fn collect_checks(
+    health: &anyhow::Result<()>,
+    server_checks: anyhow::Result<Vec<DiagnosticCheck>>,
+) -> Vec<DiagnosticCheck> {
+    match health {
+        Ok(()) => {}
+        Err(error) if is_auth_error(error) => {
+            return vec![
+                DiagnosticCheck::pass("Backend reachable", "Responded"),
+                DiagnosticCheck::fail("Auth token", format!("{:#}", error)),
+            ];
+        }
+        Err(error) => {
+            return vec![
+                DiagnosticCheck::fail("Backend reachable", format!("{:#}", error)),
+                DiagnosticCheck::skip("Auth token", "Skipped: backend unreachable"),
+            ];
+        }
+    }
+    let mut checks = vec![
+        DiagnosticCheck::pass("Backend reachable", ""),
+        DiagnosticCheck::pass("Auth token", "Accepted"),
+    ];
+    match server_checks {
+        Ok(server_checks) => checks.extend(server_checks),
+        Err(error) => checks.push(DiagnosticCheck::fail(
+            "Server checks",
+            format!("{:#}", error),
+        )),
+    }
+    checks
+}
+
+/// Settings-launched window listing each health check as a green/red row.
+#[derive(Default)]
+pub struct DiagnosticsWindow {
+    pub open: bool,
+    pending: Option<Receiver<Vec<DiagnosticCheck>>>,
+    checks: Option<Vec<DiagnosticCheck>>,
+}
+
+impl DiagnosticsWindow {
+    pub fn is_running(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Hands over an in-flight run; `render` picks up its rows.
+    pub fn start(&mut self, result_rx: Receiver<Vec<DiagnosticCheck>>) {
+        self.open = true;
+        self.pending = Some(result_rx);
+    }
+
+    /// Draws the window; returns `true` when `Run again` is clicked.
+    pub fn render(&mut self, ctx: &egui::Context) -> bool {
+        if let Some(checks) = self.pending.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.checks = Some(checks);
+            self.pending = None;
+        }
+        if !self.open {
+            return false;
+        }
+
+        let mut rerun = false;
+        let mut open = self.open;
+        egui::Window::new("Diagnostics")
+            .id(egui::Id::new("diagnostics_window"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let running = self.pending.is_some();
+                    if ui
+                        .add_enabled(!running, egui::Button::new("Run again"))
+                        .clicked()
+                    {
+                        rerun = true;
+                    }
+                    if running {
+                        ui.spinner();
+                        ui.label(RichText::new("Checking…").small().weak());
+                    }
+                });
+                ui.separator();
+                let Some(checks) = &self.checks else {
+                    return;
+                };
+                egui::Grid::new("diagnostics_grid")
+                    .num_columns(3)
+                    .spacing([10.0, 6.0])
+                    .show(ui, |ui| {
+                        for check in checks {
+                            let (mark, color) = match check.status {
+                                CheckStatus::Pass => ("OK", Color32::from_rgb(80, 200, 100)),
+                                CheckStatus::Fail => ("FAIL", Color32::from_rgb(220, 90, 90)),
+                                CheckStatus::Skip => ("—", Color32::GRAY),
+                            };
+                            ui.label(RichText::new(mark).strong().color(color));
+                            ui.label(&check.name);
+                            ui.add(
+                                egui::Label::new(RichText::new(&check.detail).small().weak())
+                                    .wrap()
+                                    .selectable(true),
+                            );
+                            ui.end_row();
+                        }
+                    });
+            });
+        self.open = open;
+        rerun
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ApiError;
+
+    fn statuses(checks: &[DiagnosticCheck]) -> Vec<(&str, CheckStatus)> {
+        checks
+            .iter()
+            .map(|check| (check.name.as_str(), check.status))
+            .collect()
+    }
+
+    #[test]
+    fn unreachable_backend_skips_everything_else() {
+        let checks = collect_checks(
+            &Err(anyhow::anyhow!("connection refused")),
+            Ok(vec![DiagnosticCheck::pass("LLM server", "")]),
+        );
+        assert_eq!(
+            statuses(&checks),
+            [
+                ("Backend reachable", CheckStatus::Fail),
+                ("Auth token", CheckStatus::Skip),
+            ]
+        );
+        assert!(checks[0].detail.contains("connection refused"));
+    }
+
+    #[test]
+    fn rejected_token_and_server_rows_are_reported() {
+        let rejected = collect_checks(
+            &Err(anyhow::Error::new(ApiError::Auth { status: 401 })),
+            Ok(Vec::new()),
+        );
+        assert_eq!(
+            statuses(&rejected),
+            [
+                ("Backend reachable", CheckStatus::Pass),
+                ("Auth token", CheckStatus::Fail),
+            ]
+        );
+
+        let healthy = collect_checks(
+            &Ok(()),
+            Ok(vec![
+                DiagnosticCheck::pass("LLM server", "llama3 loaded"),
+                DiagnosticCheck::fail("ComfyUI", "connection refused"),
+            ]),
+        );
+        assert_eq!(
+            statuses(&healthy),
+            [
+                ("Backend reachable", CheckStatus::Pass),
+                ("Auth token", CheckStatus::Pass),
+                ("LLM server", CheckStatus::Pass),
+                ("ComfyUI", CheckStatus::Fail),
+            ]
+        );
+
+        let old_backend = collect_checks(&Ok(()), Err(anyhow::anyhow!("404 Not Found")));
+        assert_eq!(
+            statuses(&old_backend),
+            [
+                ("Backend reachable", CheckStatus::Pass),
+                ("Auth token", CheckStatus::Pass),
+                ("Server checks", CheckStatus::Fail),
+            ]
+        );
+    }
+}
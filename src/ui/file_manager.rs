@@ -0,0 +1,39 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Opens `path` in the platform file manager without blocking the UI. With
+/// `select`, `path` is a file to highlight in its folder; Linux file managers
+/// have no common "select" flag, so there the containing folder opens instead.
+pub fn reveal(path: &Path, select: bool) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        if select {
+            command.arg("-R");
+        }
+        command.arg(path);
+        command
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("explorer");
+        if select {
+            command.arg(format!("/select,{}", path.display()));
+        } else {
+            command.arg(path);
+        }
+        command
+    } else {
+        let mut command = Command::new("xdg-open");
+        let target = if select {
+            path.parent().unwrap_or(path)
+        } else {
+            path
+        };
+        command.arg(target);
+        command
+    };
+    let mut child = command.spawn()?;
+    // Reap the launcher once it exits so it does not linger as a zombie.
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
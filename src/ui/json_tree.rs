@@ -0,0 +1,253 @@
+use std::collections::HashSet;
+
+use eframe::egui::{self, Color32, RichText};
+use serde_json::Value;
+
+use super::app::truncate_str;
+
+/// Containers nested deeper than this start collapsed.
+const DEFAULT_OPEN_DEPTH: usize = 1;
+/// Longest string leaf shown before it is cut with an ellipsis (hover shows it all).
+const MAX_LEAF_CHARS: usize = 160;
+
+/// Which nodes of one JSON tree are open, keyed by JSON Pointer (`""` is the root).
+/// Nodes start open down to `DEFAULT_OPEN_DEPTH`; toggles are stored as overrides
+/// so the default still applies to nodes the user never touched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonTreeState {
+    toggled: HashSet<String>,
+    /// Show the pretty-printed text instead of the tree.
+    pub raw: bool,
+}
+
+impl JsonTreeState {
+    pub fn is_expanded(&self, pointer: &str) -> bool {
+        let default_open = pointer_depth(pointer) < DEFAULT_OPEN_DEPTH;
+        default_open != self.toggled.contains(pointer)
+    }
+
+    pub fn toggle(&mut self, pointer: &str) {
+        if !self.toggled.remove(pointer) {
+            self.toggled.insert(pointer.to_string());
+        }
+    }
+
+    /// Opens every object and array in `value`.
+    pub fn expand_all(&mut self, value: &Value) {
+        self.set_all(value, true);
+    }
+
+    /// Closes every object and array in `value`, including the root.
+    pub fn collapse_all(&mut self, value: &Value) {
+        self.set_all(value, false);
+    }
+
+    fn set_all(&mut self, value: &Value, expanded: bool) {
+        let mut pointers = Vec::new();
+        collect_container_pointers(value, String::new(), &mut pointers);
+        for pointer in pointers {
+            if self.is_expanded(&pointer) != expanded {
+                self.toggle(&pointer);
+            }
+        }
+    }
+}
+
+/// Parses tool output text as JSON when it is an object or array. Scalars and
+/// anything that fails to parse (including half-streamed output) stay plain text.
+pub fn parse_structured_json(text: &str) -> Option<Value> {
+    let trimmed = text.trim();
+    if !(trimmed.starts_with('{') || trimmed.starts_with('[')) {
+        return None;
+    }
+    serde_json::from_str::<Value>(trimmed)
+        .ok()
+        .filter(|value| value.is_object() || value.is_array())
+}
+
+/// Draws `value` as a collapsible tree with a Tree/Raw toggle. Expansion state is
+/// kept in egui memory under `id_salt`, so callers need no extra fields.
+pub fn render_json_tree(ui: &mut egui::Ui, id_salt: impl std::hash::Hash, value: &Value) {
+    let state_id = ui.make_persistent_id(("json_tree", id_salt));
+    let mut state = ui
+        .data_mut(|data| data.get_temp::<JsonTreeState>(state_id))
+        .unwrap_or_default();
+    let before = state.clone();
+
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut state.raw, false, RichText::new("Tree").small());
+        ui.selectable_value(&mut state.raw, true, RichText::new("Raw").small());
+        if !state.raw {
+            if ui.small_button("Expand all").clicked() {
+                state.expand_all(value);
+            }
+            if ui.small_button("Collapse all").clicked() {
+                state.collapse_all(value);
+            }
+        }
+    });
+
+    if state.raw {
+        let pretty = serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string());
+        ui.add(egui::Label::new(RichText::new(pretty).monospace().small()).wrap());
+    } else {
+        render_node(ui, &mut state, None, value, String::new());
+    }
+
+    if state != before {
+        ui.data_mut(|data| data.insert_temp(state_id, state));
+    }
+}
+
+fn render_node(
+    ui: &mut egui::Ui,
+    state: &mut JsonTreeState,
+    key: Option<&str>,
+    value: &Value,
+    pointer: String,
+) {
+    let children: Vec<(String, &Value)> = match value {
+        Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(idx, v)| (idx.to_string(), v))
+            .collect(),
+        leaf => {
+            ui.horizontal_wrapped(|ui| {
+                if let Some(key) = key {
+                    ui.label(RichText::new(format!("{key}:")).monospace().small());
+                }
+                let (text, color) = leaf_text(leaf);
+                let shown = truncate_str(&text, MAX_LEAF_CHARS);
+                let truncated = shown != text;
+                let response = ui.label(RichText::new(shown).monospace().small().color(color));
+                if truncated {
+                    response.on_hover_text(text);
+                }
+            });
+            return;
+        }
+    };
+
+    let expanded = state.is_expanded(&pointer);
+    let summary = match value {
+        Value::Object(_) => format!("{{{}}}", children.len()),
+        _ => format!("[{}]", children.len()),
+    };
+    let header = match key {
+        Some(key) => format!("{} {key}: {summary}", if expanded { "▾" } else { "▸" }),
+        None => format!("{} {summary}", if expanded { "▾" } else { "▸" }),
+    };
+    if ui
+        .add(
+            egui::Label::new(
+                RichText::new(header)
+                    .monospace()
+                    .small()
+                    .color(Color32::LIGHT_BLUE),
+            )
+            .sense(egui::Sense::click()),
+        )
+        .clicked()
+    {
+        state.toggle(&pointer);
+    }
+    if expanded {
+        ui.indent(("json_node", &pointer), |ui| {
+            for (child_key, child) in children {
+                let child_pointer = format!("{pointer}/{}", escape_pointer_token(&child_key));
+                render_node(ui, state, Some(&child_key), child, child_pointer);
+            }
+        });
+    }
+}
+
+fn leaf_text(value: &Value) -> (String, Color32) {
+    match value {
+        Value::String(text) => (format!("\"{text}\""), Color32::from_rgb(206, 145, 120)),
+        Value::Number(number) => (number.to_string(), Color32::from_rgb(181, 206, 168)),
+        Value::Bool(flag) => (flag.to_string(), Color32::from_rgb(86, 156, 214)),
+        _ => ("null".to_string(), Color32::GRAY),
+    }
+}
+
+fn collect_container_pointers(value: &Value, pointer: String, out: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                collect_container_pointers(
+                    child,
+                    format!("{pointer}/{}", escape_pointer_token(key)),
+                    out,
+                );
+            }
+        }
+        Value::Array(items) => {
+            for (idx, child) in items.iter().enumerate() {
+                collect_container_pointers(child, format!("{pointer}/{idx}"), out);
+            }
+        }
+        _ => return,
+    }
+    out.push(pointer);
+}
+
+/// RFC 6901 escaping so keys containing `/` cannot collide with nested paths.
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn pointer_depth(pointer: &str) -> usize {
+    pointer.matches('/').count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn root_starts_open_and_nested_nodes_start_closed() {
+        let mut state = JsonTreeState::default();
+        assert!(state.is_expanded(""));
+        assert!(!state.is_expanded("/items"));
+
+        state.toggle("/items");
+        state.toggle("");
+        assert!(state.is_expanded("/items"));
+        assert!(!state.is_expanded(""));
+
+        state.toggle("/items");
+        assert!(!state.is_expanded("/items"));
+        assert!(!state.toggled.contains("/items"));
+    }
+
+    #[test]
+    fn expand_and_collapse_all_cover_every_container() {
+        let value = json!({ "a/b": { "deep": [1, { "x": true }] }, "n": 1 });
+        let mut state = JsonTreeState::default();
+
+        state.expand_all(&value);
+        for pointer in ["", "/a~1b", "/a~1b/deep", "/a~1b/deep/1"] {
+            assert!(state.is_expanded(pointer), "{pointer} should be open");
+        }
+
+        state.collapse_all(&value);
+        for pointer in ["", "/a~1b", "/a~1b/deep", "/a~1b/deep/1"] {
+            assert!(!state.is_expanded(pointer), "{pointer} should be closed");
+        }
+        // Collapsing back to the defaults below the root leaves no stale overrides.
+        assert_eq!(state.toggled.len(), 1);
+    }
+
+    #[test]
+    fn only_objects_and_arrays_render_as_trees() {
+        assert!(parse_structured_json(" {\"ok\": true} ").is_some());
+        assert!(parse_structured_json("[1, 2]").is_some());
+        assert!(parse_structured_json("\"text\"").is_none());
+        assert!(parse_structured_json("42").is_none());
+        assert!(parse_structured_json("{\"partial\": [1, 2").is_none());
+        assert!(parse_structured_json("plain output").is_none());
+    }
+}
@@ -2,7 +2,19 @@ pub mod app;
 pub mod avatar;
 pub mod character;
 pub mod chat;
+pub mod command_palette;
+pub mod conversation_archive;
+pub mod conversation_groups;
+pub mod conversation_summary;
+pub mod debug_dump;
+pub mod diagnostics;
+pub mod file_manager;
+pub mod json_tree;
+pub mod model_compare;
+pub mod mood;
 pub mod plugin_settings_form;
+pub mod prefs;
+pub mod quick_ask;
 pub mod settings;
 pub mod sprite;
 pub mod token_monitor;
@@ -0,0 +1,232 @@
+use eframe::egui::{self, Color32, RichText};
+use flume::Receiver;
+
+use crate::api::{CompareModelsRequest, ModelComparison, ModelComparisonResult};
+
+type ComparisonOutcome = Result<ModelComparison, String>;
+
+/// Debug window that runs one message through two models and shows the replies
+/// side by side. Nothing it produces is written to a conversation.
+#[derive(Default)]
+pub struct ModelCompareWindow {
+    pub open: bool,
+    message: String,
+    models: [String; 2],
+    use_conversation_context: bool,
+    pending: Option<Receiver<ComparisonOutcome>>,
+    outcome: Option<ComparisonOutcome>,
+}
+
+impl ModelCompareWindow {
+    /// Opens the window, filling empty model slots with the configured model.
+    pub fn open(&mut self, configured_model: &str) {
+        self.open = true;
+        for model in &mut self.models {
+            if model.trim().is_empty() {
+                *model = configured_model.to_string();
+            }
+        }
+    }
+
+    /// Hands over the in-flight comparison; `render` picks up its result.
+    pub fn start(&mut self, result_rx: Receiver<ComparisonOutcome>) {
+        self.pending = Some(result_rx);
+        self.outcome = None;
+    }
+
+    /// Draws the window and returns a request when the user clicks Compare.
+    /// `conversation_id` is attached only if "Use this chat's context" is ticked.
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        conversation_id: &str,
+    ) -> Option<CompareModelsRequest> {
+        if let Some(outcome) = self.pending.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.outcome = Some(outcome);
+            self.pending = None;
+        }
+        if !self.open {
+            return None;
+        }
+
+        let mut request = None;
+        let mut open = self.open;
+        egui::Window::new("Compare Models")
+            .open(&mut open)
+            .resizable(true)
+            .default_width(760.0)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(
+                        "Runs the message through both models with side-effecting tools disabled. Nothing is saved.",
+                    )
+                    .small()
+                    .weak(),
+                );
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.label("Model A:");
+                    ui.add(egui::TextEdit::singleline(&mut self.models[0]).desired_width(220.0));
+                    ui.label("Model B:");
+                    ui.add(egui::TextEdit::singleline(&mut self.models[1]).desired_width(220.0));
+                });
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.message)
+                        .desired_rows(3)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("Message to send to both models"),
+                );
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.use_conversation_context, "Use this chat's context");
+                    let running = self.pending.is_some();
+                    let ready = !running
+                        && !self.message.trim().is_empty()
+                        && self.models.iter().all(|model| !model.trim().is_empty());
+                    if ui
+                        .add_enabled(ready, egui::Button::new("Compare"))
+                        .clicked()
+                    {
+                        request = Some(CompareModelsRequest {
+                            conversation_id: self
+                                .use_conversation_context
+                                .then(|| conversation_id.to_string()),
+                            content: self.message.trim().to_string(),
+                            models: self
+                                .models
+                                .iter()
+                                .map(|model| model.trim().to_string())
+                                .collect(),
+                        });
+                    }
+                    if running {
+                        ui.spinner();
+                        ui.label(RichText::new("Running both turns…").small().weak());
+                    }
+                });
+                ui.separator();
+
+                match &self.outcome {
+                    None => {}
+                    Some(Err(error)) => {
+                        ui.colored_label(Color32::from_rgb(220, 120, 120), error);
+                    }
+                    Some(Ok(comparison)) => {
+                        let columns = align_results(&self.models, &comparison.results);
+                        ui.columns(2, |columns_ui| {
+                            for (idx, (ui, (model, result))) in
+                                columns_ui.iter_mut().zip(columns).enumerate()
+                            {
+                                render_result_column(ui, idx, &model, result);
+                            }
+                        });
+                    }
+                }
+            });
+        self.open = open;
+        request
+    }
+}
+
+fn render_result_column(
+    ui: &mut egui::Ui,
+    column: usize,
+    model: &str,
+    result: Option<&ModelComparisonResult>,
+) {
+    ui.label(RichText::new(model).strong());
+    let Some(result) = result else {
+        ui.label(RichText::new("No result returned").weak().italics());
+        return;
+    };
+    if let Some(latency_ms) = result.latency_ms {
+        ui.label(
+            RichText::new(format!("{:.1}s", latency_ms as f64 / 1000.0))
+                .small()
+                .weak(),
+        );
+    }
+    if !result.tool_calls.is_empty() {
+        ui.label(
+            RichText::new(format!("Tools attempted: {}", result.tool_calls.join(", ")))
+                .small()
+                .color(Color32::from_rgb(230, 170, 80)),
+        );
+    }
+    if let Some(error) = result.error.as_deref() {
+        ui.colored_label(Color32::from_rgb(220, 120, 120), error);
+    }
+    egui::ScrollArea::vertical()
+        .id_salt(("model_compare_column", column))
+        .max_height(420.0)
+        .show(ui, |ui| {
+            let mut response = result.response.as_str();
+            ui.add(
+                egui::TextEdit::multiline(&mut response)
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(12),
+            );
+        });
+}
+
+/// Pairs each requested model with its result, by name first and then by
+/// position for backends that echo a resolved model name (e.g. with a tag).
+/// Two slots with the same model are paired by position only, since a name
+/// match would give both the first result.
+fn align_results<'a>(
+    models: &[String; 2],
+    results: &'a [ModelComparisonResult],
+) -> Vec<(String, Option<&'a ModelComparisonResult>)> {
+    let same_model = models[0].trim() == models[1].trim();
+    models
+        .iter()
+        .enumerate()
+        .map(|(idx, model)| {
+            let model = model.trim();
+            let result = results
+                .iter()
+                .find(|result| !same_model && result.model == model)
+                .or_else(|| results.get(idx));
+            (model.to_string(), result)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(model: &str, response: &str) -> ModelComparisonResult {
+        ModelComparisonResult {
+            model: model.to_string(),
+            response: response.to_string(),
+            error: None,
+            latency_ms: None,
+            tool_calls: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn results_align_by_model_name_then_position() {
+        let models = ["llama3.1:8b".to_string(), "qwen2.5".to_string()];
+        let results = [result("qwen2.5", "b"), result("llama3.1:8b", "a")];
+        let aligned = align_results(&models, &results);
+        assert_eq!(aligned[0].1.map(|r| r.response.as_str()), Some("a"));
+        assert_eq!(aligned[1].1.map(|r| r.response.as_str()), Some("b"));
+
+        let renamed = [
+            result("llama3.1:8b-instruct", "a"),
+            result("qwen2.5:latest", "b"),
+        ];
+        let aligned = align_results(&models, &renamed);
+        assert_eq!(aligned[1].1.map(|r| r.response.as_str()), Some("b"));
+
+        let both = ["llama3.1:8b".to_string(), "llama3.1:8b".to_string()];
+        let repeated = [result("llama3.1:8b", "a"), result("llama3.1:8b", "b")];
+        let aligned = align_results(&both, &repeated);
+        assert_eq!(aligned[0].1.map(|r| r.response.as_str()), Some("a"));
+        assert_eq!(aligned[1].1.map(|r| r.response.as_str()), Some("b"));
+
+        let aligned = align_results(&models, &[]);
+        assert!(aligned.iter().all(|(_, result)| result.is_none()));
+    }
+}
@@ -0,0 +1,189 @@
+use std::time::{Duration, Instant};
+
+use crate::api::AgentVisualState;
+
+/// How long a mood reaction stays on the sprite after a reply finishes.
+pub const MOOD_REACTION_DURATION: Duration = Duration::from_secs(8);
+
+const POSITIVE_WORDS: &[&str] = &[
+    "glad",
+    "great",
+    "happy",
+    "love",
+    "lovely",
+    "wonderful",
+    "excited",
+    "delighted",
+    "awesome",
+    "fantastic",
+    "enjoy",
+    "enjoyed",
+    "fun",
+    "thanks",
+    "thank you",
+    "congratulations",
+];
+const NEGATIVE_WORDS: &[&str] = &[
+    "sorry",
+    "sad",
+    "unfortunately",
+    "frustrating",
+    "afraid",
+    "worried",
+    "failed",
+    "error",
+];
+const UNCERTAIN_PHRASES: &[&str] = &[
+    "not sure",
+    "unsure",
+    "i don't know",
+    "i do not know",
+    "unclear",
+    "confused",
+    "confusing",
+    "i'm not certain",
+    "can't tell",
+    "hard to say",
+];
+
+/// Lexicon read of a finished reply. `valence` runs from -1 (negative) to 1
+/// (positive); `uncertainty` from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplyMood {
+    pub valence: f32,
+    pub uncertainty: f32,
+}
+
+impl ReplyMood {
+    pub fn from_text(text: &str) -> Self {
+        let normalized: String = text
+            .to_lowercase()
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '\'' {
+                    c
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        let padded = format!(
+            " {} ",
+            normalized.split_whitespace().collect::<Vec<_>>().join(" ")
+        );
+        let hits = |words: &[&str]| -> f32 {
+            words
+                .iter()
+                .map(|word| padded.matches(&format!(" {} ", word)).count())
+                .sum::<usize>() as f32
+        };
+        let positive = hits(POSITIVE_WORDS);
+        let negative = hits(NEGATIVE_WORDS);
+        let uncertain = hits(UNCERTAIN_PHRASES);
+        Self {
+            // The +1 keeps a single stray word from reading as full conviction.
+            valence: (positive - negative) / (positive + negative + 1.0),
+            uncertainty: (uncertain / 2.0).min(1.0),
+        }
+    }
+
+    /// Sprite state this mood should show, if any. Uncertainty wins over valence
+    /// so a cheerful "I'm not sure" still reads as confused.
+    pub fn visual_state(self) -> Option<AgentVisualState> {
+        if self.uncertainty >= 0.5 {
+            Some(AgentVisualState::Confused)
+        } else if self.valence >= 0.5 {
+            Some(AgentVisualState::Happy)
+        } else {
+            None
+        }
+    }
+}
+
+/// A short-lived sprite state set after a reply; `app.rs` shows it only while
+/// the backend reports `Idle`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoodReaction {
+    pub state: AgentVisualState,
+    until: Instant,
+}
+
+impl MoodReaction {
+    pub fn for_reply(text: &str, now: Instant) -> Option<Self> {
+        ReplyMood::from_text(text).visual_state().map(|state| Self {
+            state,
+            until: now + MOOD_REACTION_DURATION,
+        })
+    }
+
+    pub fn is_active(&self, now: Instant) -> bool {
+        now < self.until
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mood_maps_to_visual_state() {
+        let cases = [
+            (
+                ReplyMood {
+                    valence: 0.8,
+                    uncertainty: 0.0,
+                },
+                Some(AgentVisualState::Happy),
+            ),
+            (
+                ReplyMood {
+                    valence: 0.8,
+                    uncertainty: 0.5,
+                },
+                Some(AgentVisualState::Confused),
+            ),
+            (
+                ReplyMood {
+                    valence: -0.6,
+                    uncertainty: 1.0,
+                },
+                Some(AgentVisualState::Confused),
+            ),
+            (
+                ReplyMood {
+                    valence: 0.2,
+                    uncertainty: 0.0,
+                },
+                None,
+            ),
+            (
+                ReplyMood {
+                    valence: -0.9,
+                    uncertainty: 0.0,
+                },
+                None,
+            ),
+        ];
+        for (mood, expected) in cases {
+            assert_eq!(mood.visual_state(), expected, "{mood:?}");
+        }
+    }
+
+    #[test]
+    fn lexicon_reads_replies_and_reactions_expire() {
+        let now = Instant::now();
+        let happy = MoodReaction::for_reply("Great news, I'm so glad it worked!", now)
+            .expect("positive reply reacts");
+        assert_eq!(happy.state, AgentVisualState::Happy);
+        assert!(happy.is_active(now + MOOD_REACTION_DURATION / 2));
+        assert!(!happy.is_active(now + MOOD_REACTION_DURATION));
+
+        let unsure = MoodReaction::for_reply("Honestly I'm not sure which file you meant.", now);
+        assert_eq!(unsure.map(|r| r.state), Some(AgentVisualState::Confused));
+
+        // "Great" alone is offset by "sorry"; plain replies never react.
+        assert!(MoodReaction::for_reply("Sorry, great question but it failed.", now).is_none());
+        assert!(MoodReaction::for_reply("The file has 42 lines.", now).is_none());
+        assert!(MoodReaction::for_reply("Greatly reduced.", now).is_none());
+    }
+}
@@ -0,0 +1,225 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::sprite::SpriteFallback;
+use crate::config::AgentConfig;
+
+/// Desktop-only layout preferences. These never go to the backend, so they
+/// live in a small JSON file next to the agent config instead of `AgentConfig`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiPrefs {
+    /// Hide the activity panel, conversation bar, and header; chat + composer only.
+    pub focus_mode: bool,
+    /// Where the main window was when the app last closed.
+    pub window: Option<WindowPlacement>,
+    /// Render the Live Stream panel in a monospace font.
+    pub live_stream_monospace: bool,
+    /// How many trailing characters of the live token stream stay visible.
+    pub live_stream_window_chars: usize,
+    /// Truncation lengths for one-line previews across the main window.
+    pub limits: UiLimits,
+    /// Replace emoji in main-window labels with plain text (screen readers, emoji-less fonts).
+    pub plain_labels: bool,
+    /// Label chat bubbles with the character and operator names instead of "Agent"/"You".
+    pub chat_names: bool,
+    /// Operator name shown on your chat bubbles when `chat_names` is on.
+    pub operator_name: String,
+    /// Agent replies longer than this many characters get a "long reply" marker; 0 disables it.
+    pub long_reply_chars: usize,
+    /// The "raise max tool iterations" hint was dismissed; it is shown only once.
+    pub iteration_limit_hint_seen: bool,
+    /// Context budget the prompt inspector measures token estimates against; 0 disables flagging.
+    pub prompt_token_budget: usize,
+    /// Let the idle sprite breathe now and then and glance at new activity.
+    pub idle_animation: bool,
+    /// Briefly show `Happy` or `Confused` on the sprite based on the wording of a finished reply.
+    pub mood_reactions: bool,
+    /// Window title and header name; blank uses the character card name, then "Ponderer".
+    pub agent_name: String,
+    /// Emoji or short text per visual state for the header sprite when no avatars are configured.
+    pub sprite_fallback: SpriteFallback,
+    /// Conversation quick asks go to; a new one is created only once this is gone.
+    pub quick_ask_conversation_id: Option<String>,
+}
+
+pub const LIVE_STREAM_WINDOW_RANGE: std::ops::RangeInclusive<usize> = 200..=20_000;
+pub const PROMPT_TOKEN_BUDGET_RANGE: std::ops::RangeInclusive<usize> = 0..=2_000_000;
+pub const LONG_REPLY_RANGE: std::ops::RangeInclusive<usize> = 0..=100_000;
+
+impl Default for UiPrefs {
+    fn default() -> Self {
+        Self {
+            focus_mode: false,
+            window: None,
+            live_stream_monospace: false,
+            live_stream_window_chars: 4_000,
+            limits: UiLimits::default(),
+            plain_labels: false,
+            chat_names: true,
+            operator_name: String::new(),
+            long_reply_chars: 3_000,
+            iteration_limit_hint_seen: false,
+            prompt_token_budget: 8_192,
+            idle_animation: true,
+            mood_reactions: false,
+            agent_name: String::new(),
+            sprite_fallback: SpriteFallback::new(),
+            quick_ask_conversation_id: None,
+        }
+    }
+}
+
+/// Character limits for the previews `app.rs` truncates with `truncate_str`.
+/// Edited from the Settings "Display" tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiLimits {
+    /// Last action and last journal line in the Mind panel.
+    pub status_line_chars: usize,
+    /// Current activity line ("Requesting LLM...") in the Mind panel.
+    pub activity_chars: usize,
+    /// Last action shown in the header strip.
+    pub header_action_chars: usize,
+    /// Working-memory entry previews.
+    pub memory_entry_chars: usize,
+    /// Live tool output previews during a turn.
+    pub tool_output_chars: usize,
+    /// Conversation title or id in a working-memory entry's scope label.
+    pub scope_label_chars: usize,
+    /// Short labels: the "Why" popup title, turn timing phases, config change values.
+    pub short_label_chars: usize,
+    /// Section titles in the prompt inspector's token breakdown.
+    pub section_title_chars: usize,
+}
+
+pub const UI_LIMIT_RANGE: std::ops::RangeInclusive<usize> = 10..=2_000;
+
+impl Default for UiLimits {
+    fn default() -> Self {
+        Self {
+            status_line_chars: 80,
+            activity_chars: 90,
+            header_action_chars: 50,
+            memory_entry_chars: 240,
+            tool_output_chars: 200,
+            scope_label_chars: 24,
+            short_label_chars: 40,
+            section_title_chars: 60,
+        }
+    }
+}
+
+/// Saved main-window placement, in egui points.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowPlacement {
+    /// Outer (decorated) top-left corner.
+    pub position: [f32; 2],
+    pub inner_size: [f32; 2],
+    /// Size of the monitor the window was on, used to notice a changed display layout.
+    #[serde(default)]
+    pub monitor_size: Option<[f32; 2]>,
+}
+
+impl UiPrefs {
+    pub fn path() -> PathBuf {
+        std::env::var("PONDERER_UI_PREFS_FILE")
+            .ok()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| AgentConfig::config_path().with_file_name("ponderer_ui.json"))
+    }
+
+    /// Missing or unreadable prefs fall back to defaults rather than blocking startup.
+    pub fn load() -> Self {
+        Self::load_from(&Self::path())
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        let Ok(raw) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&raw).unwrap_or_else(|error| {
+            tracing::warn!(
+                "Ignoring malformed UI prefs at {}: {}",
+                path.display(),
+                error
+            );
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::path())
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let payload = serde_json::to_vec_pretty(self)?;
+        fs::write(path, payload).with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UiLimits, UiPrefs, WindowPlacement};
+    use crate::api::AgentVisualState;
+
+    #[test]
+    fn prefs_round_trip_and_tolerate_missing_or_bad_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("ponderer_ui.json");
+
+        assert_eq!(UiPrefs::load_from(&path), UiPrefs::default());
+
+        let prefs = UiPrefs {
+            focus_mode: true,
+            window: Some(WindowPlacement {
+                position: [-1200.0, 40.0],
+                inner_size: [600.0, 800.0],
+                monitor_size: Some([1920.0, 1080.0]),
+            }),
+            live_stream_monospace: true,
+            live_stream_window_chars: 8_000,
+            limits: UiLimits {
+                status_line_chars: 160,
+                tool_output_chars: 1_000,
+                ..UiLimits::default()
+            },
+            plain_labels: true,
+            chat_names: false,
+            operator_name: "Sam".to_string(),
+            long_reply_chars: 0,
+            iteration_limit_hint_seen: true,
+            prompt_token_budget: 32_768,
+            idle_animation: false,
+            mood_reactions: true,
+            agent_name: "Aria".to_string(),
+            sprite_fallback: [(AgentVisualState::WaitingForUser, "?!".to_string())]
+                .into_iter()
+                .collect(),
+            quick_ask_conversation_id: Some("qa-1".to_string()),
+        };
+        prefs.save_to(&path).unwrap();
+        assert_eq!(UiPrefs::load_from(&path), prefs);
+
+        std::fs::write(&path, r#"{ "limits": { "activity_chars": 30 } }"#).unwrap();
+        let partial = UiPrefs::load_from(&path);
+        assert_eq!(partial.limits.activity_chars, 30);
+        assert_eq!(
+            partial.limits.status_line_chars,
+            UiLimits::default().status_line_chars
+        );
+
+        std::fs::write(&path, "{ not json").unwrap();
+        assert_eq!(UiPrefs::load_from(&path), UiPrefs::default());
+    }
+}
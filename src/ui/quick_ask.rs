@@ -0,0 +1,339 @@
+use eframe::egui::{self, Color32, RichText};
+use global_hotkey::hotkey::{Code, HotKey, Modifiers};
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+
+use crate::api::ChatConversation;
+
+/// Title given to the conversation quick asks create; routing never uses it.
+pub const QUICK_ASK_TITLE: &str = "Quick Ask";
+/// Human-readable form of the shortcut `QuickAskHotkey::register` binds.
+pub const QUICK_ASK_SHORTCUT_LABEL: &str = "Ctrl+Shift+Space";
+/// Clipboard text beyond this is cut before it reaches the composer.
+pub const MAX_QUICK_ASK_CONTEXT_CHARS: usize = 20_000;
+
+/// Where a quick ask should be posted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuickAskRoute {
+    Existing(String),
+    /// The stored conversation is not in the main list: it may be archived or
+    /// deleted, so the caller has to ask the backend before creating a new one.
+    Unlisted(String),
+    /// No quick-ask conversation is stored yet; create one first.
+    Create,
+}
+
+/// Picks the conversation stored in `UiPrefs::quick_ask_conversation_id`,
+/// independent of whichever chat the main window has open. Titles play no
+/// part, so renaming that conversation keeps it as the quick-ask chat.
+pub fn route_quick_ask(stored: Option<&str>, conversations: &[ChatConversation]) -> QuickAskRoute {
+    match stored {
+        None => QuickAskRoute::Create,
+        Some(id)
+            if conversations
+                .iter()
+                .any(|conversation| conversation.id == id) =>
+        {
+            QuickAskRoute::Existing(id.to_string())
+        }
+        Some(id) => QuickAskRoute::Unlisted(id.to_string()),
+    }
+}
+
+/// The message sent for a quick ask: the question, then the clipboard text in a
+/// fence long enough that backticks inside the context cannot close it early.
+pub fn compose_quick_ask(context: &str, question: &str) -> String {
+    let question = question.trim();
+    let context = context.trim_end();
+    if context.trim().is_empty() {
+        return question.to_string();
+    }
+    let longest_run = context.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{}\n\n{}\n{}\n{}", question, fence, context, fence)
+}
+
+/// System-wide Ctrl+Shift+Space. Only X11 is supported on Linux, so
+/// registration failing (Wayland, headless) just leaves the palette entry.
+pub struct QuickAskHotkey {
+    _manager: GlobalHotKeyManager,
+    hotkey_id: u32,
+}
+
+impl QuickAskHotkey {
+    pub fn register() -> anyhow::Result<Self> {
+        let manager = GlobalHotKeyManager::new()
+            .map_err(|error| anyhow::anyhow!("global shortcuts unavailable: {}", error))?;
+        let hotkey = HotKey::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::Space);
+        manager.register(hotkey).map_err(|error| {
+            anyhow::anyhow!("failed to register {}: {}", QUICK_ASK_SHORTCUT_LABEL, error)
+        })?;
+        Ok(Self {
+            _manager: manager,
+            hotkey_id: hotkey.id(),
+        })
+    }
+
+    /// Drains pending hotkey events; `true` if ours was pressed since the last call.
+    pub fn take_pressed(&self) -> bool {
+        let mut pressed = false;
+        while let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
+            if event.id() == self.hotkey_id && event.state() == HotKeyState::Pressed {
+                pressed = true;
+            }
+        }
+        pressed
+    }
+}
+
+/// Current clipboard text, or `None` if it is empty or not text.
+pub fn read_clipboard_text() -> Option<String> {
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+        Ok(text) if !text.trim().is_empty() => Some(text),
+        Ok(_) => None,
+        Err(error) => {
+            tracing::debug!("Clipboard read failed: {}", error);
+            None
+        }
+    }
+}
+
+/// What the mini window asks `app.rs` to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuickAskAction {
+    /// Post this message to the quick-ask conversation.
+    Ask(String),
+    /// Switch the main window to the quick-ask conversation and close the mini window.
+    OpenInApp(String),
+}
+
+/// Always-on-top mini composer. Lifecycle: `open_with` (hotkey or palette)
+/// replaces the context and clears the last exchange; `Ask` keeps it open and
+/// `on_stream` fills in the reply; Esc, the close button, or `Open in app`
+/// hide it. Nothing survives a close except the conversation itself.
+#[derive(Default)]
+pub struct QuickAskWindow {
+    pub open: bool,
+    context: String,
+    context_truncated: bool,
+    question: String,
+    /// Set once a question has been posted; replies are matched against it.
+    conversation_id: Option<String>,
+    reply: Option<String>,
+    waiting: bool,
+    error: Option<String>,
+    focus_pending: bool,
+}
+
+impl QuickAskWindow {
+    pub fn open_with(&mut self, context: String) {
+        self.context_truncated = context.chars().count() > MAX_QUICK_ASK_CONTEXT_CHARS;
+        self.context = if self.context_truncated {
+            context.chars().take(MAX_QUICK_ASK_CONTEXT_CHARS).collect()
+        } else {
+            context
+        };
+        self.question.clear();
+        self.conversation_id = None;
+        self.reply = None;
+        self.waiting = false;
+        self.error = None;
+        self.focus_pending = true;
+        self.open = true;
+    }
+
+    /// Records a successful post so streamed replies for `conversation_id` show here.
+    pub fn sent(&mut self, conversation_id: &str) {
+        self.conversation_id = Some(conversation_id.to_string());
+        self.question.clear();
+        self.reply = None;
+        self.waiting = true;
+        self.error = None;
+    }
+
+    pub fn failed(&mut self, error: String) {
+        self.waiting = false;
+        self.error = Some(error);
+    }
+
+    /// Fed every chat stream update; ignores other conversations.
+    pub fn on_stream(&mut self, conversation_id: &str, content: &str, done: bool) {
+        if !self.open || self.conversation_id.as_deref() != Some(conversation_id) {
+            return;
+        }
+        if !content.trim().is_empty() {
+            self.reply = Some(content.to_string());
+        }
+        if done {
+            self.waiting = false;
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) -> Option<QuickAskAction> {
+        if !self.open {
+            return None;
+        }
+        let viewport_id = egui::ViewportId::from_hash_of("quick_ask_viewport");
+        let builder = egui::ViewportBuilder::default()
+            .with_title(QUICK_ASK_TITLE)
+            .with_inner_size([420.0, 360.0])
+            .with_always_on_top()
+            .with_active(true);
+        if self.focus_pending {
+            ctx.send_viewport_cmd_to(viewport_id, egui::ViewportCommand::Focus);
+        }
+        ctx.show_viewport_immediate(viewport_id, builder, |ctx, class| {
+            if ctx.input(|i| i.viewport().close_requested() || i.key_pressed(egui::Key::Escape)) {
+                self.open = false;
+                return None;
+            }
+            if class == egui::ViewportClass::Embedded {
+                // Backends without multi-viewport support get a floating egui window.
+                let mut open = self.open;
+                let action = egui::Window::new(QUICK_ASK_TITLE)
+                    .id(egui::Id::new("quick_ask_window"))
+                    .open(&mut open)
+                    .default_width(420.0)
+                    .show(ctx, |ui| self.body(ui))
+                    .and_then(|response| response.inner.flatten());
+                self.open = open && self.open;
+                action
+            } else {
+                egui::CentralPanel::default()
+                    .show(ctx, |ui| self.body(ui))
+                    .inner
+            }
+        })
+    }
+
+    fn body(&mut self, ui: &mut egui::Ui) -> Option<QuickAskAction> {
+        let mut action = None;
+        ui.label(
+            RichText::new(format!(
+                "Clipboard context · {} chars{}",
+                self.context.chars().count(),
+                if self.context_truncated {
+                    " (truncated)"
+                } else {
+                    ""
+                }
+            ))
+            .small()
+            .weak(),
+        );
+        egui::ScrollArea::vertical()
+            .id_salt("quick_ask_context")
+            .max_height(110.0)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.context)
+                        .hint_text("Clipboard was empty — paste or type context here")
+                        .desired_rows(3)
+                        .desired_width(f32::INFINITY)
+                        .code_editor(),
+                );
+            });
+
+        let question = ui.add(
+            egui::TextEdit::singleline(&mut self.question)
+                .hint_text("Ask about it… (Enter to send)")
+                .desired_width(f32::INFINITY),
+        );
+        if self.focus_pending {
+            question.request_focus();
+            self.focus_pending = false;
+        }
+        let submitted = question.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        ui.horizontal(|ui| {
+            let can_send = !self.waiting && !self.question.trim().is_empty();
+            if (ui.add_enabled(can_send, egui::Button::new("Ask")).clicked() || submitted)
+                && can_send
+            {
+                action = Some(QuickAskAction::Ask(compose_quick_ask(
+                    &self.context,
+                    &self.question,
+                )));
+            }
+            if let Some(conversation_id) = &self.conversation_id {
+                if ui.button("Open in app").clicked() {
+                    action = Some(QuickAskAction::OpenInApp(conversation_id.clone()));
+                }
+            }
+            if self.waiting {
+                ui.spinner();
+            }
+        });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(Color32::from_rgb(220, 120, 120), error);
+        }
+        if let Some(reply) = &self.reply {
+            ui.separator();
+            egui::ScrollArea::vertical()
+                .id_salt("quick_ask_reply")
+                .show(ui, |ui| {
+                    ui.add(egui::Label::new(reply).wrap().selectable(true));
+                });
+        } else if self.waiting {
+            ui.label(RichText::new("Waiting for the reply…").small().weak());
+        }
+
+        if matches!(action, Some(QuickAskAction::OpenInApp(_))) {
+            self.open = false;
+        }
+        action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation(id: &str, title: &str) -> ChatConversation {
+        ChatConversation::fixture(id, serde_json::json!({ "title": title }))
+    }
+
+    #[test]
+    fn quick_asks_route_to_the_stored_conversation_not_by_title() {
+        let conversations = vec![
+            conversation("default", "Default"),
+            conversation("qa-titled", "Quick Ask"),
+            conversation("qa", "Renamed by the operator"),
+        ];
+        assert_eq!(route_quick_ask(None, &conversations), QuickAskRoute::Create);
+        assert_eq!(
+            route_quick_ask(Some("qa"), &conversations),
+            QuickAskRoute::Existing("qa".to_string())
+        );
+        assert_eq!(
+            route_quick_ask(Some("archived"), &conversations),
+            QuickAskRoute::Unlisted("archived".to_string())
+        );
+    }
+
+    #[test]
+    fn composed_message_fences_the_clipboard_context() {
+        assert_eq!(
+            compose_quick_ask("  \n", " What is this? "),
+            "What is this?"
+        );
+        assert_eq!(
+            compose_quick_ask("let x = 1;\n", "Explain"),
+            "Explain\n\n```\nlet x = 1;\n```"
+        );
+        let nested = compose_quick_ask("```rust\nfn main() {}\n```", "Explain");
+        assert!(nested.starts_with("Explain\n\n````\n```rust"));
+        assert!(nested.ends_with("```\n````"));
+
+        let mut window = QuickAskWindow::default();
+        window.open_with("x".repeat(MAX_QUICK_ASK_CONTEXT_CHARS + 5));
+        assert!(window.context_truncated);
+        assert_eq!(window.context.chars().count(), MAX_QUICK_ASK_CONTEXT_CHARS);
+        window.sent("qa");
+        window.on_stream("other", "not ours", false);
+        assert_eq!(window.reply, None);
+        window.on_stream("qa", "It is a", false);
+        window.on_stream("qa", "", true);
+        assert_eq!(window.reply.as_deref(), Some("It is a"));
+        assert!(!window.waiting);
+    }
+}
@@ -1,17 +1,27 @@
+use super::file_manager;
 use super::plugin_settings_form::PluginSettingsForm;
+use super::prefs::{UiLimits, LONG_REPLY_RANGE, PROMPT_TOKEN_BUDGET_RANGE, UI_LIMIT_RANGE};
+use super::sprite::{resolve_fallback, SpriteFallback};
 use crate::api::{
-    PluginManifest, PluginSettingsSchemaManifest, PluginSettingsTabManifest, ScheduledJob,
+    AgentVisualState, ConfigChange, ConfigChangeSource, ConfigFlags, PluginManifest,
+    PluginSettingsSchemaManifest, PluginSettingsTabManifest, RetentionPolicy, RetentionStatus,
+    ScheduledJob, StorageInfo, ToolInfo, AGENT_RESET_CONFIRMATION,
 };
 use crate::config::AgentConfig;
 use eframe::egui;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
 const CORE_TAB_GENERAL: &str = "core.general";
-const CORE_TAB_BEHAVIOR: &str = "core.behavior";
+pub const CORE_TAB_BEHAVIOR: &str = "core.behavior";
 const CORE_TAB_LOOPS: &str = "core.loops";
 const CORE_TAB_MEMORY: &str = "core.memory";
 const CORE_TAB_SYSTEM: &str = "core.system";
 const CORE_TAB_SCHEDULES: &str = "core.schedules";
+const CORE_TAB_TOOLS: &str = "core.tools";
+const CORE_TAB_DISPLAY: &str = "core.display";
+/// Typed to run a pruning pass by hand; the pass deletes data for good.
+const PRUNE_CONFIRMATION: &str = "PRUNE";
 
 #[derive(Debug, Clone)]
 pub enum ScheduledJobAction {
@@ -34,6 +44,37 @@ pub enum ScheduledJobAction {
     },
 }
 
+#[derive(Debug, Clone)]
+pub enum StorageAction {
+    BackupTo(PathBuf),
+    RestoreFrom(PathBuf),
+    ResetAgent,
+    SetRetention(RetentionPolicy),
+    PruneNow,
+}
+
+#[derive(Debug, Clone)]
+pub enum ToolSettingsAction {
+    Refresh,
+    SetEnabled { tool_name: String, enabled: bool },
+}
+
+/// Desktop-only display preferences edited on the Display tab; `app.rs` stores
+/// them in `UiPrefs`, never in `AgentConfig`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplaySettings {
+    pub limits: UiLimits,
+    pub plain_labels: bool,
+    pub chat_names: bool,
+    pub operator_name: String,
+    pub long_reply_chars: usize,
+    pub prompt_token_budget: usize,
+    pub idle_animation: bool,
+    pub mood_reactions: bool,
+    pub agent_name: String,
+    pub sprite_fallback: SpriteFallback,
+}
+
 #[derive(Debug, Clone)]
 struct ScheduledJobEditor {
     name: String,
@@ -64,6 +105,32 @@ pub struct SettingsPanel {
     new_job_interval_minutes: u64,
     new_job_enabled: bool,
     next_local_scheduled_job_id: u64,
+    tools: Vec<ToolInfo>,
+    tool_enabled_edits: HashMap<String, bool>,
+    tool_actions: Vec<ToolSettingsAction>,
+    tools_error: Option<String>,
+    storage_info: Option<StorageInfo>,
+    /// Recent config edits for the System tab; `None` until loaded or when the backend lacks the log.
+    config_history: Option<Vec<ConfigChange>>,
+    diagnostics_requested: bool,
+    debug_dump_requested: bool,
+    storage_actions: Vec<StorageAction>,
+    /// Restore file chosen but not yet confirmed.
+    pending_restore: Option<PathBuf>,
+    storage_status: Option<String>,
+    reset_confirmation: String,
+    /// `Some` while the Prune now confirmation is open; holds the typed text.
+    prune_confirmation: Option<String>,
+    /// Policy as last loaded from the backend; `None` when the backend has no retention API.
+    retention: Option<RetentionStatus>,
+    retention_draft: RetentionPolicy,
+    /// Flags as last loaded from the backend; `None` when it has no flags endpoint.
+    config_flags: Option<ConfigFlags>,
+    config_flags_draft: ConfigFlags,
+    pending_config_flags: Option<ConfigFlags>,
+    /// Handed back through `take_display_settings` on save, never sent to the backend.
+    display: DisplaySettings,
+    pending_display: Option<DisplaySettings>,
 }
 
 impl SettingsPanel {
@@ -84,6 +151,37 @@ impl SettingsPanel {
             new_job_interval_minutes: 60,
             new_job_enabled: true,
             next_local_scheduled_job_id: 1,
+            tools: Vec::new(),
+            tool_enabled_edits: HashMap::new(),
+            tool_actions: Vec::new(),
+            tools_error: None,
+            storage_info: None,
+            config_history: None,
+            diagnostics_requested: false,
+            debug_dump_requested: false,
+            storage_actions: Vec::new(),
+            pending_restore: None,
+            storage_status: None,
+            reset_confirmation: String::new(),
+            prune_confirmation: None,
+            retention: None,
+            retention_draft: RetentionPolicy::default(),
+            display: DisplaySettings {
+                limits: UiLimits::default(),
+                plain_labels: false,
+                chat_names: true,
+                operator_name: String::new(),
+                long_reply_chars: 0,
+                prompt_token_budget: 0,
+                idle_animation: true,
+                mood_reactions: false,
+                agent_name: String::new(),
+                sprite_fallback: SpriteFallback::new(),
+            },
+            config_flags: None,
+            config_flags_draft: ConfigFlags::default(),
+            pending_config_flags: None,
+            pending_display: None,
         }
     }
 
@@ -96,6 +194,32 @@ impl SettingsPanel {
         self.config = config;
     }
 
+    /// Adopts fields the backend changed between `before` and `after` (e.g. the
+    /// tool set after a toggle) while keeping any unsaved edits to other fields,
+    /// so the next Save & Apply doesn't write the old values back.
+    pub fn adopt_backend_changes(
+        &mut self,
+        before: &AgentConfig,
+        after: &AgentConfig,
+    ) -> anyhow::Result<()> {
+        let before = serde_json::to_value(before)?;
+        let after = serde_json::to_value(after)?;
+        let mut edited = serde_json::to_value(&self.config)?;
+        if let (Some(before), Some(after), Some(edited)) = (
+            before.as_object(),
+            after.as_object(),
+            edited.as_object_mut(),
+        ) {
+            for (field, value) in after {
+                if before.get(field) != Some(value) {
+                    edited.insert(field.clone(), value.clone());
+                }
+            }
+        }
+        self.config = serde_json::from_value(edited)?;
+        Ok(())
+    }
+
     pub fn set_scheduled_jobs(&mut self, scheduled_jobs: Vec<ScheduledJob>) {
         self.scheduled_jobs = scheduled_jobs;
         self.scheduled_job_editors.clear();
@@ -111,6 +235,69 @@ impl SettingsPanel {
         std::mem::take(&mut self.scheduled_job_actions)
     }
 
+    pub fn set_tools(&mut self, tools: Vec<ToolInfo>) {
+        self.tools = tools;
+        self.tool_enabled_edits.clear();
+    }
+
+    pub fn set_tools_error(&mut self, error: Option<String>) {
+        self.tools_error = error;
+    }
+
+    pub fn set_storage_info(&mut self, storage_info: Option<StorageInfo>) {
+        self.storage_info = storage_info;
+    }
+
+    pub fn take_diagnostics_request(&mut self) -> bool {
+        std::mem::take(&mut self.diagnostics_requested)
+    }
+
+    pub fn take_debug_dump_request(&mut self) -> bool {
+        std::mem::take(&mut self.debug_dump_requested)
+    }
+
+    pub fn set_config_history(&mut self, history: Option<Vec<ConfigChange>>) {
+        self.config_history = history;
+    }
+
+    pub fn set_retention(&mut self, retention: Option<RetentionStatus>) {
+        self.retention_draft = retention
+            .as_ref()
+            .map(|status| status.policy.clone())
+            .unwrap_or_default();
+        self.retention = retention;
+    }
+
+    pub fn set_storage_status(&mut self, status: Option<String>) {
+        self.storage_status = status;
+    }
+
+    pub fn take_storage_actions(&mut self) -> Vec<StorageAction> {
+        std::mem::take(&mut self.storage_actions)
+    }
+
+    pub fn set_display_settings(&mut self, display: DisplaySettings) {
+        self.display = display;
+    }
+
+    pub fn take_display_settings(&mut self) -> Option<DisplaySettings> {
+        self.pending_display.take()
+    }
+
+    pub fn set_config_flags(&mut self, flags: Option<ConfigFlags>) {
+        self.config_flags_draft = flags.unwrap_or_default();
+        self.config_flags = flags;
+    }
+
+    /// Flags edited since they were loaded, handed back once per Save & Apply.
+    pub fn take_config_flags_update(&mut self) -> Option<ConfigFlags> {
+        self.pending_config_flags.take()
+    }
+
+    pub fn take_tool_actions(&mut self) -> Vec<ToolSettingsAction> {
+        std::mem::take(&mut self.tool_actions)
+    }
+
     pub fn open(&mut self) {
         self.show = true;
         self.ensure_valid_selected_tab();
@@ -154,6 +341,8 @@ impl SettingsPanel {
                         CORE_TAB_MEMORY => self.render_memory_tab(ui),
                         CORE_TAB_SYSTEM => self.render_system_tab(ui),
                         CORE_TAB_SCHEDULES => self.render_schedules_tab(ui),
+                        CORE_TAB_TOOLS => self.render_tools_tab(ui),
+                        CORE_TAB_DISPLAY => self.render_display_tab(ui),
                         _ => {
                             if let Some((plugin_id, schema)) =
                                 self.dynamic_plugin_schema_for_tab(&selected_tab)
@@ -189,12 +378,37 @@ impl SettingsPanel {
                         if !self.queue_dirty_scheduled_job_updates() {
                             return;
                         }
+                        self.queue_dirty_tool_toggles();
+                        self.queue_dirty_retention();
+                        if self
+                            .config_flags
+                            .is_some_and(|flags| flags != self.config_flags_draft)
+                        {
+                            self.pending_config_flags = Some(self.config_flags_draft);
+                        }
+                        self.pending_display = Some(self.display.clone());
                         new_config = Some(self.config.clone());
                     }
 
                     if ui.button("Close").clicked() {
                         should_close = true;
                     }
+                    if ui
+                        .button("Diagnostics")
+                        .on_hover_text("Check the backend, LLM server, ComfyUI, and data directory")
+                        .clicked()
+                    {
+                        self.diagnostics_requested = true;
+                    }
+                    if ui
+                        .button("Debug dump")
+                        .on_hover_text(
+                            "Save recent events, agent state, and config (secrets redacted) to a JSON file for a bug report",
+                        )
+                        .clicked()
+                    {
+                        self.debug_dump_requested = true;
+                    }
                 });
             });
 
@@ -211,6 +425,8 @@ impl SettingsPanel {
                 (CORE_TAB_MEMORY, "Memory"),
                 (CORE_TAB_SYSTEM, "System"),
                 (CORE_TAB_SCHEDULES, "Schedules"),
+                (CORE_TAB_TOOLS, "Tools"),
+                (CORE_TAB_DISPLAY, "Display"),
             ] {
                 let selected = self.selected_tab == tab_id;
                 if ui.selectable_label(selected, label).clicked() {
@@ -327,6 +543,13 @@ impl SettingsPanel {
             ui.label("Poll interval (seconds):");
             ui.add(egui::DragValue::new(&mut self.config.poll_interval_secs).range(10..=600));
         });
+        ui.label(
+            egui::RichText::new(
+                "How often autonomous cycles run. Turn the ambient loop off under Living Loop to stop autonomy entirely.",
+            )
+            .small()
+            .weak(),
+        );
         ui.add_space(8.0);
 
         ui.checkbox(
@@ -348,6 +571,25 @@ impl SettingsPanel {
         );
         ui.add_space(8.0);
 
+        let flags_loaded = self.config_flags.is_some();
+        ui.add_enabled(
+            flags_loaded,
+            egui::Checkbox::new(
+                &mut self.config_flags_draft.confirm_tool_batches,
+                "Confirm tool calls before running",
+            ),
+        );
+        ui.label(
+            egui::RichText::new(if flags_loaded {
+                "Each turn pauses before running its tool calls; a card above the chat lets you pick which ones run."
+            } else {
+                "Tool confirmation is unavailable from this backend."
+            })
+            .small()
+            .weak(),
+        );
+        ui.add_space(8.0);
+
         ui.horizontal(|ui| {
             ui.label("Max foreground chat turns:");
             ui.add(egui::DragValue::new(&mut self.config.max_chat_autonomous_turns).range(1..=64));
@@ -617,10 +859,291 @@ impl SettingsPanel {
         }
     }
 
+    fn render_display_tab(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Accessibility");
+        ui.add_space(8.0);
+        ui.checkbox(
+            &mut self.display.plain_labels,
+            "Plain-text labels (no emoji in buttons and headings)",
+        )
+        .on_hover_text("Easier on screen readers and fonts without emoji glyphs");
+        ui.checkbox(
+            &mut self.display.idle_animation,
+            "Idle animation (the sprite breathes now and then and glances at new activity)",
+        )
+        .on_hover_text("Turn off for a completely still header");
+        ui.checkbox(
+            &mut self.display.mood_reactions,
+            "Mood reactions (the sprite looks happy or confused after a reply, based on its wording)",
+        )
+        .on_hover_text("A simple word-list guess; it never changes what the agent does");
+
+        ui.add_space(12.0);
+        ui.heading("Branding");
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            ui.label("Agent name:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.display.agent_name)
+                    .hint_text("character card name"),
+            );
+        });
+        ui.label(
+            egui::RichText::new("Shown in the window title and header. Blank uses the character card name, then \"Ponderer\".")
+                .weak()
+                .small(),
+        );
+
+        ui.add_space(12.0);
+        ui.heading("Sprite Without Avatars");
+        ui.add_space(8.0);
+        egui::Grid::new("sprite_fallback_grid")
+            .num_columns(2)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                let defaults = SpriteFallback::new();
+                for state in AgentVisualState::ALL {
+                    ui.label(state.label());
+                    let (default_glyph, _) = resolve_fallback(&state, &defaults);
+                    let text = self.display.sprite_fallback.entry(state).or_default();
+                    ui.add(
+                        egui::TextEdit::singleline(text)
+                            .hint_text(default_glyph)
+                            .desired_width(90.0),
+                    );
+                    ui.end_row();
+                }
+            });
+        ui.label(
+            egui::RichText::new("Emoji or a few characters drawn in the header when no avatar images are set. Blank uses the default shown.")
+                .weak()
+                .small(),
+        );
+
+        ui.add_space(12.0);
+        ui.heading("Chat Names");
+        ui.add_space(8.0);
+        ui.checkbox(
+            &mut self.display.chat_names,
+            "Show names instead of roles on chat bubbles",
+        );
+        ui.add_enabled_ui(self.display.chat_names, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Your name:");
+                ui.text_edit_singleline(&mut self.display.operator_name);
+            });
+        });
+        ui.label(
+            egui::RichText::new(
+                "The agent uses the character card name, then the Username. Blank names show \"Agent\" / \"You\".",
+            )
+            .weak()
+            .small(),
+        );
+
+        ui.add_space(12.0);
+        ui.heading("Replies");
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            ui.label("Mark replies longer than:");
+            ui.add(
+                egui::DragValue::new(&mut self.display.long_reply_chars)
+                    .range(LONG_REPLY_RANGE)
+                    .suffix(" chars"),
+            );
+        });
+        ui.label(
+            egui::RichText::new("0 turns the marker off. To ask the model for shorter replies, use Concise replies on the System tab.")
+                .weak()
+                .small(),
+        );
+
+        ui.add_space(12.0);
+        ui.heading("Prompt Inspector");
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            ui.label("Token budget:");
+            ui.add(
+                egui::DragValue::new(&mut self.display.prompt_token_budget)
+                    .range(PROMPT_TOKEN_BUDGET_RANGE)
+                    .speed(256.0)
+                    .suffix(" tokens"),
+            );
+        });
+        ui.label(
+            egui::RichText::new("Usually your model's context window. The inspector flags sections over a quarter of it and totals over 80%; 0 turns flagging off.")
+                .weak()
+                .small(),
+        );
+
+        ui.add_space(12.0);
+        ui.heading("Preview Lengths");
+        ui.add_space(8.0);
+        ui.label(
+            egui::RichText::new("Maximum characters shown before a preview is cut off with …")
+                .weak()
+                .small(),
+        );
+        ui.add_space(4.0);
+
+        let limits = &mut self.display.limits;
+        egui::Grid::new("ui_limits_grid")
+            .num_columns(2)
+            .spacing([12.0, 6.0])
+            .show(ui, |ui| {
+                for (label, value) in [
+                    ("Last action / journal:", &mut limits.status_line_chars),
+                    ("Current activity:", &mut limits.activity_chars),
+                    ("Header last action:", &mut limits.header_action_chars),
+                    ("Working memory entries:", &mut limits.memory_entry_chars),
+                    ("Tool output previews:", &mut limits.tool_output_chars),
+                    ("Memory scope labels:", &mut limits.scope_label_chars),
+                    ("Short labels:", &mut limits.short_label_chars),
+                    ("Prompt section titles:", &mut limits.section_title_chars),
+                ] {
+                    ui.label(label);
+                    ui.add(egui::DragValue::new(value).range(UI_LIMIT_RANGE));
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(8.0);
+        if ui.button("Reset to defaults").clicked() {
+            *limits = UiLimits::default();
+        }
+    }
+
     fn render_memory_tab(&mut self, ui: &mut egui::Ui) {
         ui.heading("Memory & Database");
         ui.add_space(8.0);
 
+        match self.storage_info.as_ref() {
+            Some(info) => {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Data directory:");
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(&info.data_dir).monospace())
+                            .selectable(true),
+                    );
+                    if ui
+                        .small_button("Open folder")
+                        .on_hover_text("Only works when the backend runs on this machine")
+                        .clicked()
+                    {
+                        if let Err(error) =
+                            file_manager::reveal(std::path::Path::new(&info.data_dir), false)
+                        {
+                            tracing::warn!("Failed to open {}: {}", info.data_dir, error);
+                        }
+                    }
+                });
+                let source = if info.overridden {
+                    "Set by PONDERER_DATA_DIR."
+                } else {
+                    "Platform default; set PONDERER_DATA_DIR to move it."
+                };
+                ui.label(egui::RichText::new(source).small().weak());
+            }
+            None => {
+                ui.label(
+                    egui::RichText::new("Data directory: unavailable from this backend")
+                        .small()
+                        .weak(),
+                );
+            }
+        }
+        ui.add_space(8.0);
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("Back up database…")
+                .on_hover_text("Save a consistent snapshot of all agent state")
+                .clicked()
+            {
+                let default_name = format!(
+                    "ponderer-backup-{}.db",
+                    chrono::Local::now().format("%Y%m%d-%H%M%S")
+                );
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("SQLite database", &["db", "sqlite"])
+                    .set_file_name(default_name)
+                    .save_file()
+                {
+                    self.storage_actions.push(StorageAction::BackupTo(path));
+                }
+            }
+            if ui.button("Restore from backup…").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("SQLite database", &["db", "sqlite"])
+                    .pick_file()
+                {
+                    self.pending_restore = Some(path);
+                }
+            }
+        });
+        if let Some(path) = self.pending_restore.clone() {
+            ui.group(|ui| {
+                ui.colored_label(
+                    egui::Color32::from_rgb(230, 150, 90),
+                    format!(
+                        "Replace ALL conversations, memory, and journal with {}? This cannot be undone.",
+                        path.display()
+                    ),
+                );
+                ui.horizontal(|ui| {
+                    if ui.button("Restore").clicked() {
+                        self.storage_actions.push(StorageAction::RestoreFrom(path));
+                        self.pending_restore = None;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.pending_restore = None;
+                    }
+                });
+            });
+        }
+        if let Some(status) = self.storage_status.as_deref() {
+            ui.label(egui::RichText::new(status).small().weak());
+        }
+        ui.add_space(8.0);
+
+        self.render_retention_section(ui);
+        ui.add_space(8.0);
+
+        egui::CollapsingHeader::new(
+            egui::RichText::new("Danger zone").color(egui::Color32::from_rgb(220, 110, 110)),
+        )
+        .id_salt("settings_reset_agent")
+        .show(ui, |ui| {
+            ui.label(
+                egui::RichText::new(
+                    "Reset agent clears conversations, journal, concerns, and working memory. Config and character are kept. Back up first if you might want them back.",
+                )
+                .small()
+                .weak(),
+            );
+            ui.horizontal(|ui| {
+                ui.label(format!("Type {} to confirm:", AGENT_RESET_CONFIRMATION));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.reset_confirmation).desired_width(80.0),
+                );
+                let confirmed = self.reset_confirmation.trim() == AGENT_RESET_CONFIRMATION;
+                if ui
+                    .add_enabled(
+                        confirmed,
+                        egui::Button::new(
+                            egui::RichText::new("Reset agent")
+                                .color(egui::Color32::from_rgb(255, 120, 120)),
+                        ),
+                    )
+                    .clicked()
+                {
+                    self.storage_actions.push(StorageAction::ResetAgent);
+                    self.reset_confirmation.clear();
+                }
+            });
+        });
+        ui.add_space(8.0);
+
         ui.horizontal(|ui| {
             ui.label("Database path:");
             ui.text_edit_singleline(&mut self.config.database_path);
@@ -633,12 +1156,210 @@ impl SettingsPanel {
         });
     }
 
+    fn render_retention_section(&mut self, ui: &mut egui::Ui) {
+        ui.label(egui::RichText::new("Retention").strong());
+        let Some(status) = self.retention.as_ref() else {
+            ui.label(
+                egui::RichText::new("Automatic pruning is unavailable from this backend")
+                    .small()
+                    .weak(),
+            );
+            return;
+        };
+        ui.label(
+            egui::RichText::new(
+                "Pruned periodically by the backend. Pinned conversations and flagged concerns are never pruned. Changes apply on Save & Apply.",
+            )
+            .small()
+            .weak(),
+        );
+        let last_run = match (status.last_pruned_at, status.last_report) {
+            (Some(at), Some(report)) => Some(format!(
+                "Last pruned {}: {}",
+                at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+                report
+            )),
+            (Some(at), None) => Some(format!(
+                "Last pruned {}",
+                at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+            )),
+            _ => None,
+        };
+
+        let draft = &mut self.retention_draft;
+        egui::Grid::new("retention_grid")
+            .num_columns(2)
+            .spacing([12.0, 6.0])
+            .show(ui, |ui| {
+                for (label, default_days, value) in [
+                    (
+                        "Delete turn prompts older than",
+                        30,
+                        &mut draft.turn_prompt_days,
+                    ),
+                    (
+                        "Archive conversations untouched for",
+                        90,
+                        &mut draft.archive_conversation_days,
+                    ),
+                    (
+                        "Delete journal entries older than",
+                        365,
+                        &mut draft.journal_days,
+                    ),
+                ] {
+                    let mut enabled = value.is_some();
+                    if ui.checkbox(&mut enabled, label).changed() {
+                        *value = enabled.then_some(default_days);
+                    }
+                    ui.add_enabled_ui(value.is_some(), |ui| {
+                        let mut days = value.unwrap_or(default_days);
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut days)
+                                    .range(1..=3650)
+                                    .suffix(" days"),
+                            )
+                            .changed()
+                        {
+                            *value = Some(days);
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        let policy_edited = status.policy != self.retention_draft;
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    self.prune_confirmation.is_none(),
+                    egui::Button::new("Prune now…"),
+                )
+                .on_hover_text("Apply the saved policy immediately")
+                .clicked()
+            {
+                self.prune_confirmation = Some(String::new());
+            }
+            if let Some(last_run) = last_run {
+                ui.label(egui::RichText::new(last_run).small().weak());
+            }
+        });
+        if let Some(typed) = self.prune_confirmation.as_mut() {
+            let mut close = false;
+            ui.group(|ui| {
+                ui.colored_label(
+                    egui::Color32::from_rgb(230, 150, 90),
+                    "Delete everything the saved policy covers right now? Deleted prompts and journal entries cannot be restored.",
+                );
+                if policy_edited {
+                    ui.label(
+                        egui::RichText::new(
+                            "The edits above are not saved yet, so this pass uses the previous policy.",
+                        )
+                        .small()
+                        .weak(),
+                    );
+                }
+                ui.horizontal(|ui| {
+                    ui.label(format!("Type {} to confirm:", PRUNE_CONFIRMATION));
+                    ui.add(egui::TextEdit::singleline(typed).desired_width(80.0));
+                    if ui
+                        .add_enabled(
+                            typed.trim() == PRUNE_CONFIRMATION,
+                            egui::Button::new("Prune"),
+                        )
+                        .clicked()
+                    {
+                        self.storage_actions.push(StorageAction::PruneNow);
+                        close = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        close = true;
+                    }
+                });
+            });
+            if close {
+                self.prune_confirmation = None;
+            }
+        }
+    }
+
     fn render_system_tab(&mut self, ui: &mut egui::Ui) {
         ui.heading("System Prompt");
         ui.add_space(8.0);
 
+        let flags_loaded = self.config_flags.is_some();
+        ui.add_enabled(
+            flags_loaded,
+            egui::Checkbox::new(
+                &mut self.config_flags_draft.concise_replies,
+                "Concise replies",
+            ),
+        );
+        ui.label(
+            egui::RichText::new(if flags_loaded {
+                "The backend asks the model for short answers, for slow local models. The prompt below is left as written. Applies on Save & Apply."
+            } else {
+                "Concise replies are unavailable from this backend."
+            })
+            .weak()
+            .small(),
+        );
+        ui.add_space(6.0);
+
         ui.label("Customize how the agent behaves:");
         ui.text_edit_multiline(&mut self.config.system_prompt);
+
+        ui.add_space(12.0);
+        egui::CollapsingHeader::new("Change History")
+            .default_open(false)
+            .show(ui, |ui| match self.config_history.as_deref() {
+                None => {
+                    ui.label(
+                        egui::RichText::new("Change history is unavailable from this backend.")
+                            .weak()
+                            .small(),
+                    );
+                }
+                Some([]) => {
+                    ui.label(egui::RichText::new("No config changes recorded yet.").weak());
+                }
+                Some(history) => {
+                    egui::ScrollArea::vertical()
+                        .max_height(240.0)
+                        .id_salt("config_history_scroll")
+                        .show(ui, |ui| {
+                            for change in history {
+                                let source = match change.source {
+                                    ConfigChangeSource::User => "you",
+                                    ConfigChangeSource::Agent => "agent",
+                                };
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} · by {}",
+                                        change
+                                            .changed_at
+                                            .with_timezone(&chrono::Local)
+                                            .format("%Y-%m-%d %H:%M"),
+                                        source
+                                    ))
+                                    .small()
+                                    .strong(),
+                                );
+                                for field in &change.changes {
+                                    ui.label(
+                                        egui::RichText::new(
+                                            field.describe(self.display.limits.short_label_chars),
+                                        )
+                                        .small()
+                                        .monospace(),
+                                    );
+                                }
+                                ui.add_space(4.0);
+                            }
+                        });
+                }
+            });
     }
 
     fn render_schedules_tab(&mut self, ui: &mut egui::Ui) {
@@ -889,6 +1610,126 @@ impl SettingsPanel {
         }
     }
 
+    fn render_tools_tab(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Tools");
+        ui.add_space(8.0);
+        ui.label(
+            egui::RichText::new(
+                "Disabled tools are not offered to the model. Changes apply on Save & Apply and take effect from the next turn.",
+            )
+            .small()
+            .weak(),
+        );
+        ui.add_space(6.0);
+
+        if let Some(error) = self.tools_error.as_deref() {
+            ui.colored_label(egui::Color32::from_rgb(230, 120, 120), error);
+            ui.add_space(6.0);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Refresh Tools").clicked() {
+                self.tool_actions.push(ToolSettingsAction::Refresh);
+            }
+            let enabled_count = self
+                .tools
+                .iter()
+                .filter(|tool| {
+                    *self
+                        .tool_enabled_edits
+                        .get(&tool.name)
+                        .unwrap_or(&tool.enabled)
+                })
+                .count();
+            ui.label(
+                egui::RichText::new(format!("{} of {} enabled", enabled_count, self.tools.len()))
+                    .small()
+                    .weak(),
+            );
+        });
+        ui.add_space(8.0);
+
+        if self.tools.is_empty() {
+            ui.label(
+                egui::RichText::new("No tools reported by the backend.")
+                    .small()
+                    .italics()
+                    .weak(),
+            );
+            return;
+        }
+
+        for tool in &self.tools {
+            let mut enabled = *self
+                .tool_enabled_edits
+                .get(&tool.name)
+                .unwrap_or(&tool.enabled);
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .checkbox(&mut enabled, egui::RichText::new(&tool.name).monospace())
+                        .changed()
+                    {
+                        if enabled == tool.enabled {
+                            self.tool_enabled_edits.remove(&tool.name);
+                        } else {
+                            self.tool_enabled_edits.insert(tool.name.clone(), enabled);
+                        }
+                    }
+                    if tool.side_effecting {
+                        ui.label(
+                            egui::RichText::new("side effects")
+                                .small()
+                                .color(egui::Color32::from_rgb(230, 160, 90)),
+                        );
+                    }
+                    if self.tool_enabled_edits.contains_key(&tool.name) {
+                        ui.label(
+                            egui::RichText::new("Unsaved")
+                                .small()
+                                .color(egui::Color32::from_rgb(220, 190, 110)),
+                        );
+                    }
+                });
+                if !tool.description.trim().is_empty() {
+                    ui.label(egui::RichText::new(tool.description.trim()).small().weak());
+                }
+                if !tool.parameters.is_null() {
+                    egui::CollapsingHeader::new(egui::RichText::new("Parameters").small())
+                        .id_salt(("tool_parameters", tool.name.as_str()))
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            let schema = serde_json::to_string_pretty(&tool.parameters)
+                                .unwrap_or_else(|_| tool.parameters.to_string());
+                            ui.label(egui::RichText::new(schema).monospace().small());
+                        });
+                }
+            });
+            ui.add_space(4.0);
+        }
+    }
+
+    fn queue_dirty_retention(&mut self) {
+        if self
+            .retention
+            .as_ref()
+            .is_some_and(|status| status.policy != self.retention_draft)
+        {
+            self.storage_actions
+                .push(StorageAction::SetRetention(self.retention_draft.clone()));
+        }
+    }
+
+    fn queue_dirty_tool_toggles(&mut self) {
+        let mut edits = self.tool_enabled_edits.drain().collect::<Vec<_>>();
+        edits.sort_by(|left, right| left.0.cmp(&right.0));
+        self.tool_actions.extend(
+            edits
+                .into_iter()
+                .map(|(tool_name, enabled)| ToolSettingsAction::SetEnabled { tool_name, enabled }),
+        );
+    }
+
     fn editor_for_job(&mut self, job: &ScheduledJob) -> &mut ScheduledJobEditor {
         self.scheduled_job_editors
             .entry(job.id.clone())
@@ -1020,6 +1861,8 @@ impl SettingsPanel {
             CORE_TAB_MEMORY.to_string(),
             CORE_TAB_SYSTEM.to_string(),
             CORE_TAB_SCHEDULES.to_string(),
+            CORE_TAB_TOOLS.to_string(),
+            CORE_TAB_DISPLAY.to_string(),
         ];
         ids.extend(self.skill_tabs().into_iter().map(|tab| tab.id));
         ids
@@ -1055,3 +1898,28 @@ impl SettingsPanel {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::SettingsPanel;
+    use crate::config::AgentConfig;
+
+    #[test]
+    fn backend_changes_are_adopted_without_losing_unsaved_edits() {
+        let before = AgentConfig {
+            llm_model: "llama3".to_string(),
+            max_tool_iterations: 10,
+            ..AgentConfig::default()
+        };
+        let mut panel = SettingsPanel::new(before.clone());
+        panel.config.llm_model = "qwen2.5".to_string();
+
+        let after = AgentConfig {
+            max_tool_iterations: 12,
+            ..before.clone()
+        };
+        panel.adopt_backend_changes(&before, &after).expect("merge");
+        assert_eq!(panel.config.max_tool_iterations, 12);
+        assert_eq!(panel.config.llm_model, "qwen2.5");
+    }
+}
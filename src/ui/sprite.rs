@@ -1,13 +1,139 @@
-use eframe::egui::{self, RichText};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use eframe::egui::{self, FontId};
+use unicode_segmentation::UnicodeSegmentation;
 
 use super::avatar::AvatarSet;
 use crate::api::AgentVisualState;
 
+/// Side of the square the header sprite is drawn in.
+const SPRITE_SIZE: f32 = 64.0;
+/// Time between idle "breaths" while nothing is happening.
+pub const IDLE_BEAT_INTERVAL: Duration = Duration::from_secs(7);
+const BEAT_DURATION: Duration = Duration::from_millis(1_200);
+const GLANCE_DURATION: Duration = Duration::from_millis(800);
+/// Peak growth of a breath, as a fraction of the sprite size.
+const BREATH_SCALE: f32 = 0.05;
+/// How far the sprite leans toward the activity panel when it glances.
+const GLANCE_OFFSET: f32 = 4.0;
+/// Fallback glyph size at scale 1; longer text is shrunk to fit the sprite box.
+const FALLBACK_FONT_SIZE: f32 = 48.0;
+
+/// Per-state emoji or short text drawn when no avatar image is loaded. States
+/// that are missing or mapped to blank text use the built-in glyph.
+pub type SpriteFallback = HashMap<AgentVisualState, String>;
+
+/// Scale and offset applied to the sprite for one frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdlePose {
+    pub scale: f32,
+    pub offset_x: f32,
+}
+
+impl Default for IdlePose {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            offset_x: 0.0,
+        }
+    }
+}
+
+/// Client-side timer that makes an idle sprite breathe now and then and glance
+/// at new activity, so the window does not look frozen.
+#[derive(Debug, Clone)]
+pub struct IdleHeartbeat {
+    interval: Duration,
+    next_beat: Instant,
+    beat_started: Option<Instant>,
+    glance_started: Option<Instant>,
+}
+
+impl IdleHeartbeat {
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        Self {
+            interval,
+            next_beat: now + interval,
+            beat_started: None,
+            glance_started: None,
+        }
+    }
+
+    /// Starts a beat once `interval` has passed since the previous one.
+    /// Returns `true` on the frame a beat starts.
+    pub fn advance(&mut self, now: Instant) -> bool {
+        if now < self.next_beat {
+            return false;
+        }
+        self.beat_started = Some(now);
+        self.next_beat = now + self.interval;
+        true
+    }
+
+    /// Leans the sprite toward the activity panel for a moment.
+    pub fn glance(&mut self, now: Instant) {
+        self.glance_started = Some(now);
+    }
+
+    /// Restarts the wait so a beat never lands right after the agent goes idle.
+    pub fn rest(&mut self, now: Instant) {
+        self.next_beat = now + self.interval;
+        self.beat_started = None;
+        self.glance_started = None;
+    }
+
+    pub fn pose(&mut self, now: Instant) -> IdlePose {
+        self.advance(now);
+        let breath = swell(self.beat_started, BEAT_DURATION, now);
+        let glance = swell(self.glance_started, GLANCE_DURATION, now);
+        IdlePose {
+            scale: 1.0 + BREATH_SCALE * breath,
+            offset_x: GLANCE_OFFSET * glance,
+        }
+    }
+
+    /// How long the UI can sleep before the pose changes again.
+    pub fn repaint_after(&self, now: Instant) -> Duration {
+        let animating = [
+            (self.beat_started, BEAT_DURATION),
+            (self.glance_started, GLANCE_DURATION),
+        ]
+        .into_iter()
+        .any(|(started, length)| started.is_some_and(|started| now < started + length));
+        if animating {
+            Duration::from_millis(16)
+        } else {
+            self.next_beat.saturating_duration_since(now)
+        }
+    }
+}
+
+/// 0 → 1 → 0 over `length` after `started`, eased with a half sine.
+fn swell(started: Option<Instant>, length: Duration, now: Instant) -> f32 {
+    let Some(elapsed) = started.and_then(|started| now.checked_duration_since(started)) else {
+        return 0.0;
+    };
+    if elapsed >= length {
+        return 0.0;
+    }
+    (elapsed.as_secs_f32() / length.as_secs_f32() * std::f32::consts::PI).sin()
+}
+
 pub fn render_agent_sprite(
     ui: &mut egui::Ui,
     state: &AgentVisualState,
     avatars: Option<&mut AvatarSet>,
+    pose: IdlePose,
+    fallback: &SpriteFallback,
 ) {
+    let (slot, _) =
+        ui.allocate_exact_size(egui::vec2(SPRITE_SIZE, SPRITE_SIZE), egui::Sense::hover());
+    let rect = egui::Rect::from_center_size(
+        slot.center() + egui::vec2(pose.offset_x, 0.0),
+        egui::vec2(SPRITE_SIZE, SPRITE_SIZE) * pose.scale,
+    );
+
     // Try to render avatar if available
     if let Some(avatar_set) = avatars {
         if let Some(avatar) = avatar_set.get_for_state(state) {
@@ -16,9 +142,7 @@ pub fn render_agent_sprite(
 
             // Render avatar
             let texture = avatar.current_texture();
-            let size = egui::vec2(64.0, 64.0);
-
-            ui.add(egui::Image::new(texture).fit_to_exact_size(size));
+            egui::Image::new(texture).paint_at(ui, rect);
 
             // Request repaint for animations
             if avatar.is_animated() {
@@ -30,11 +154,54 @@ pub fn render_agent_sprite(
     }
 
     // Fallback to emoji if no avatar
-    render_agent_emoji(ui, state);
+    render_agent_emoji(ui, state, rect, pose.scale, fallback);
+}
+
+/// Glyph and color for `state`: the user's mapping when set, else the built-in emoji.
+pub fn resolve_fallback<'a>(
+    state: &AgentVisualState,
+    overrides: &'a SpriteFallback,
+) -> (&'a str, egui::Color32) {
+    let (default, color) = default_fallback(state);
+    let glyph = overrides
+        .get(state)
+        .map(|text| text.trim())
+        .filter(|text| !text.is_empty())
+        .unwrap_or(default);
+    (glyph, color)
+}
+
+/// Font size that keeps `glyph` inside the sprite box: full size for one or two
+/// graphemes, shrinking for short words like "zzz" or "...".
+fn fallback_font_size(glyph: &str, scale: f32) -> f32 {
+    let graphemes = glyph.graphemes(true).count().max(1) as f32;
+    let size = if graphemes <= 2.0 {
+        FALLBACK_FONT_SIZE
+    } else {
+        (FALLBACK_FONT_SIZE * 2.5 / graphemes).max(12.0)
+    };
+    size * scale
+}
+
+fn render_agent_emoji(
+    ui: &mut egui::Ui,
+    state: &AgentVisualState,
+    rect: egui::Rect,
+    scale: f32,
+    fallback: &SpriteFallback,
+) {
+    let (glyph, color) = resolve_fallback(state, fallback);
+    ui.painter().text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        glyph,
+        FontId::proportional(fallback_font_size(glyph, scale)),
+        color,
+    );
 }
 
-fn render_agent_emoji(ui: &mut egui::Ui, state: &AgentVisualState) {
-    let (emoji, color) = match state {
+fn default_fallback(state: &AgentVisualState) -> (&'static str, egui::Color32) {
+    match state {
         AgentVisualState::Idle => ("😴", egui::Color32::GRAY),
         AgentVisualState::Reading => ("📖", egui::Color32::LIGHT_BLUE),
         AgentVisualState::Thinking => ("🤔", egui::Color32::YELLOW),
@@ -42,7 +209,73 @@ fn render_agent_emoji(ui: &mut egui::Ui, state: &AgentVisualState) {
         AgentVisualState::Happy => ("😊", egui::Color32::GREEN),
         AgentVisualState::Confused => ("😕", egui::Color32::ORANGE),
         AgentVisualState::Paused => ("⏸️", egui::Color32::LIGHT_RED),
-    };
+        AgentVisualState::WaitingForUser => ("✋", egui::Color32::from_rgb(230, 170, 80)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_beat_starts_only_after_the_interval() {
+        let start = Instant::now();
+        let mut heartbeat = IdleHeartbeat::new(Duration::from_secs(5), start);
+
+        assert!(!heartbeat.advance(start));
+        assert!(!heartbeat.advance(start + Duration::from_millis(4_999)));
+        assert_eq!(
+            heartbeat.pose(start + Duration::from_secs(4)),
+            IdlePose::default()
+        );
+
+        let beat = start + Duration::from_secs(5);
+        assert!(heartbeat.advance(beat));
+        assert!(!heartbeat.advance(beat + Duration::from_secs(1)));
+        assert!(heartbeat.pose(beat + BEAT_DURATION / 2).scale > 1.0);
+        assert_eq!(heartbeat.pose(beat + BEAT_DURATION).scale, 1.0);
+        assert!(heartbeat.advance(beat + Duration::from_secs(5)));
 
-    ui.heading(RichText::new(emoji).size(48.0).color(color));
+        heartbeat.rest(beat + Duration::from_secs(6));
+        assert!(!heartbeat.advance(beat + Duration::from_secs(10)));
+        assert!(heartbeat.advance(beat + Duration::from_secs(11)));
+    }
+
+    #[test]
+    fn fallback_prefers_custom_text_and_keeps_state_colors() {
+        let mut overrides = SpriteFallback::new();
+        overrides.insert(AgentVisualState::Idle, " zzz ".to_string());
+        overrides.insert(AgentVisualState::Thinking, "   ".to_string());
+        overrides.insert(AgentVisualState::Happy, "(^_^)".to_string());
+
+        let (idle, idle_color) = resolve_fallback(&AgentVisualState::Idle, &overrides);
+        assert_eq!(idle, "zzz");
+        assert_eq!(idle_color, default_fallback(&AgentVisualState::Idle).1);
+        assert_eq!(
+            resolve_fallback(&AgentVisualState::Thinking, &overrides).0,
+            "🤔",
+            "blank entries fall back to the built-in glyph"
+        );
+        assert_eq!(
+            resolve_fallback(&AgentVisualState::Paused, &overrides).0,
+            default_fallback(&AgentVisualState::Paused).0
+        );
+
+        assert_eq!(fallback_font_size("😴", 1.0), FALLBACK_FONT_SIZE);
+        assert!(fallback_font_size("(^_^)", 1.0) < FALLBACK_FONT_SIZE);
+        assert!(fallback_font_size("a very long status word", 1.0) >= 12.0);
+    }
+
+    #[test]
+    fn glance_leans_briefly_toward_activity() {
+        let start = Instant::now();
+        let mut heartbeat = IdleHeartbeat::new(Duration::from_secs(60), start);
+        heartbeat.glance(start);
+        assert!(heartbeat.pose(start + GLANCE_DURATION / 2).offset_x > 0.0);
+        assert_eq!(heartbeat.pose(start + GLANCE_DURATION).offset_x, 0.0);
+        assert_eq!(
+            heartbeat.repaint_after(start + GLANCE_DURATION),
+            Duration::from_secs(60) - GLANCE_DURATION
+        );
+    }
 }